@@ -0,0 +1,12 @@
+use gpui::*;
+use gpui_component::{label::Label, ActiveTheme};
+
+/// Small bordered label, like the host an item comes from
+pub fn badge(label: impl Into<SharedString>, cx: &App) -> Div {
+    div()
+        .px_1()
+        .rounded_md()
+        .border_1()
+        .border_color(cx.theme().border)
+        .child(Label::new(label).text_xs())
+}
@@ -0,0 +1,44 @@
+use std::rc::Rc;
+
+use gpui::*;
+use gpui_component::{
+    button::{Button, ButtonVariants},
+    WindowExt,
+};
+
+/// Open a dialog asking to confirm an action before running `on_confirm`
+pub fn open_confirm_dialog(
+    window: &mut Window,
+    cx: &mut App,
+    title: impl Into<SharedString>,
+    message: impl Into<SharedString>,
+    on_confirm: impl Fn(&mut Window, &mut App) + 'static,
+) {
+    let title = title.into();
+    let message = message.into();
+    let on_confirm = Rc::new(on_confirm);
+
+    window.open_dialog(cx, move |dialog, _window, _cx| {
+        dialog.title(title.clone()).child(message.clone()).footer({
+            let on_confirm = on_confirm.clone();
+
+            move |_, _, _, _cx| {
+                vec![
+                    Button::new("confirm").primary().label("Confirm").on_click({
+                        let on_confirm = on_confirm.clone();
+
+                        move |_, window, cx| {
+                            window.close_dialog(cx);
+                            on_confirm(window, cx);
+                        }
+                    }),
+                    Button::new("cancel")
+                        .label("Cancel")
+                        .on_click(move |_, window, cx| {
+                            window.close_dialog(cx);
+                        }),
+                ]
+            }
+        })
+    })
+}
@@ -1,9 +1,13 @@
 use gpui::*;
 use gpui_component::spinner::Spinner;
 
+pub mod badge;
 pub mod bool;
+pub mod confirm;
 pub mod error;
 pub mod list;
+pub mod sparkline;
+pub mod table;
 
 pub enum LoadState<T: Render, E: Render> {
     Loading,
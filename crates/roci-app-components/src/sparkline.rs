@@ -0,0 +1,85 @@
+use gpui::*;
+use gpui_component::{h_flex, tooltip::Tooltip, ActiveTheme};
+
+/// Value of a sparkline bar, with its own color and tooltip
+#[derive(Debug, Clone)]
+pub struct SparklinePoint {
+    pub value: f64,
+    pub color: Option<Hsla>,
+    pub tooltip: SharedString,
+}
+
+impl SparklinePoint {
+    pub fn new(value: f64, tooltip: impl Into<SharedString>) -> Self {
+        Self {
+            value,
+            color: None,
+            tooltip: tooltip.into(),
+        }
+    }
+
+    pub fn color(mut self, color: Hsla) -> Self {
+        self.color = Some(color);
+        self
+    }
+}
+
+/// Small bar chart of values, oldest first, scaled on the greatest one
+#[derive(IntoElement)]
+pub struct Sparkline {
+    id: ElementId,
+    points: Vec<SparklinePoint>,
+    height: Pixels,
+    bar_width: Pixels,
+}
+
+impl Sparkline {
+    pub fn new(id: impl Into<ElementId>, points: Vec<SparklinePoint>) -> Self {
+        Self {
+            id: id.into(),
+            points,
+            height: px(16.),
+            bar_width: px(3.),
+        }
+    }
+
+    pub fn height(mut self, height: Pixels) -> Self {
+        self.height = height;
+        self
+    }
+
+    pub fn bar_width(mut self, bar_width: Pixels) -> Self {
+        self.bar_width = bar_width;
+        self
+    }
+}
+
+impl RenderOnce for Sparkline {
+    fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let max = self
+            .points
+            .iter()
+            .map(|point| point.value)
+            .fold(0., f64::max);
+        let default_color = cx.theme().muted_foreground;
+
+        h_flex()
+            .id(self.id)
+            .h(self.height)
+            .items_end()
+            .gap_px()
+            .children(self.points.into_iter().enumerate().map(|(index, point)| {
+                let ratio = if max > 0. { point.value / max } else { 0. };
+                let tooltip = point.tooltip;
+
+                // Kept visible (and hoverable) for null values
+                div()
+                    .id(index)
+                    .w(self.bar_width)
+                    .h(self.height * (ratio as f32).max(0.1))
+                    .rounded_t_sm()
+                    .bg(point.color.unwrap_or(default_color))
+                    .tooltip(move |window, cx| Tooltip::new(tooltip.clone()).build(window, cx))
+            }))
+    }
+}
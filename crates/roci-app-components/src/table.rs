@@ -0,0 +1,202 @@
+use derive_more::Constructor;
+use gpui::{prelude::FluentBuilder, *};
+use gpui_component::{
+    button::{Button, ButtonVariants},
+    h_flex,
+    input::{Input, InputEvent, InputState},
+    switch::Switch,
+    v_flex, ActiveTheme, IconName, Sizable,
+};
+
+/// Row of a `SortableTable`, columns are identified by their index in `columns`
+pub trait TableRow: 'static {
+    fn columns() -> Vec<TableColumn>;
+    fn cell(&self, column: usize, window: &mut Window, cx: &mut App) -> AnyElement;
+    fn sort_value(&self, column: usize) -> SortValue;
+    /// Rows kept by the text filter (`filter` is lowercase)
+    fn matches(&self, filter: &str) -> bool;
+    /// Rows kept by the toggle filter, see `SortableTable::flag_filter`
+    fn flagged(&self) -> bool {
+        false
+    }
+    fn highlighted(&self, _cx: &App) -> bool {
+        false
+    }
+}
+
+#[derive(Debug, Clone, Constructor)]
+pub struct TableColumn {
+    pub title: SharedString,
+    /// Take remaining space if none
+    pub width: Option<Pixels>,
+}
+
+/// Value used to sort a column, `None` values come first in ascending order
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SortValue {
+    None,
+    Number(i64),
+    Text(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortDirection {
+    Ascending,
+    Descending,
+}
+
+pub struct SortableTable<R: TableRow> {
+    rows: Vec<R>,
+    sort: Option<(usize, SortDirection)>,
+    filter: Entity<InputState>,
+    flag_filter: Option<SharedString>,
+    only_flagged: bool,
+}
+
+impl<R: TableRow> SortableTable<R> {
+    pub fn new(window: &mut Window, cx: &mut Context<Self>, rows: Vec<R>) -> Self {
+        let filter = cx.new(|cx| InputState::new(window, cx).placeholder("Filter"));
+        cx.subscribe(&filter, |_this, _, event: &InputEvent, cx| {
+            if matches!(event, InputEvent::Change) {
+                cx.notify();
+            }
+        })
+        .detach();
+
+        Self {
+            rows,
+            sort: None,
+            filter,
+            flag_filter: None,
+            only_flagged: false,
+        }
+    }
+
+    /// Display a toggle with given label to only show flagged rows
+    pub fn flag_filter(mut self, label: impl Into<SharedString>) -> Self {
+        self.flag_filter = Some(label.into());
+        self
+    }
+
+    pub fn rows(&self) -> &[R] {
+        &self.rows
+    }
+
+    pub fn set_rows(&mut self, rows: Vec<R>, cx: &mut Context<Self>) {
+        self.rows = rows;
+        cx.notify();
+    }
+
+    fn sort_by(&mut self, column: usize, cx: &mut Context<Self>) {
+        self.sort = match self.sort {
+            Some((column_, SortDirection::Ascending)) if column_ == column => {
+                Some((column, SortDirection::Descending))
+            }
+            Some((column_, SortDirection::Descending)) if column_ == column => None,
+            _ => Some((column, SortDirection::Ascending)),
+        };
+        cx.notify();
+    }
+
+    fn visible_rows(&self, cx: &App) -> Vec<usize> {
+        let filter = self.filter.read(cx).value().trim().to_lowercase();
+        let mut indexes = self
+            .rows
+            .iter()
+            .enumerate()
+            .filter(|(_, row)| !self.only_flagged || row.flagged())
+            .filter(|(_, row)| filter.is_empty() || row.matches(&filter))
+            .map(|(index, _)| index)
+            .collect::<Vec<usize>>();
+
+        if let Some((column, direction)) = self.sort {
+            indexes.sort_by_key(|index| self.rows[*index].sort_value(column));
+            if direction == SortDirection::Descending {
+                indexes.reverse();
+            }
+        }
+
+        indexes
+    }
+}
+
+impl<R: TableRow> Render for SortableTable<R> {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let columns = R::columns();
+        let visible_rows = self.visible_rows(cx);
+
+        let sized = |element: Div, column: &TableColumn| match column.width {
+            Some(width) => element.w(width),
+            None => element.flex_1(),
+        };
+
+        v_flex()
+            .gap_1()
+            .child(
+                h_flex()
+                    .gap_3()
+                    .child(div().w(px(300.)).child(Input::new(&self.filter).small()))
+                    .when_some(self.flag_filter.clone(), |element, label| {
+                        element.child(
+                            Switch::new("only-flagged")
+                                .checked(self.only_flagged)
+                                .label(label)
+                                .on_click(cx.listener(|this, checked: &bool, _window, cx| {
+                                    this.only_flagged = *checked;
+                                    cx.notify();
+                                })),
+                        )
+                    }),
+            )
+            .child(
+                h_flex()
+                    .border_b_1()
+                    .border_color(cx.theme().border)
+                    .children(columns.iter().enumerate().map(|(index, column)| {
+                        let icon = match self.sort {
+                            Some((column_, SortDirection::Ascending)) if column_ == index => {
+                                IconName::SortAscending
+                            }
+                            Some((column_, SortDirection::Descending)) if column_ == index => {
+                                IconName::SortDescending
+                            }
+                            _ => IconName::ChevronsUpDown,
+                        };
+
+                        sized(div(), column).child(
+                            Button::new(ElementId::NamedInteger(
+                                "table-column".into(),
+                                index as u64,
+                            ))
+                            .ghost()
+                            .small()
+                            .label(column.title.clone())
+                            .icon(icon)
+                            .on_click(cx.listener(
+                                move |this, _, _window, cx| {
+                                    this.sort_by(index, cx);
+                                },
+                            )),
+                        )
+                    })),
+            )
+            .when(visible_rows.is_empty(), |element| {
+                element.child("n/a".to_string())
+            })
+            .children(visible_rows.into_iter().map(|row_index| {
+                let row = &self.rows[row_index];
+                let accent = cx.theme().accent;
+
+                h_flex()
+                    .id(ElementId::NamedInteger(
+                        "table-row".into(),
+                        row_index as u64,
+                    ))
+                    .when(row.highlighted(cx), |element| element.bg(accent))
+                    .children(columns.iter().enumerate().map(|(index, column)| {
+                        sized(div().px_2().overflow_hidden(), column)
+                            .child(row.cell(index, window, cx))
+                    }))
+            }))
+    }
+}
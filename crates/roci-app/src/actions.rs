@@ -0,0 +1,60 @@
+use gpui::{actions, App, KeyBinding};
+
+actions!(
+    roci,
+    [
+        OpenSearch,
+        Refresh,
+        SelectNext,
+        SelectPrevious,
+        SelectNextSection,
+        SelectPreviousSection,
+        OpenSelected,
+        CopySelectedUrl,
+        MarkSelectedTodoDone,
+        OpenConfigure,
+        ShowShortcuts,
+    ]
+);
+
+/// Navigation keys must not be triggered while typing in an input
+const NAVIGATION_CONTEXT: &str = "Dashboard && !Input";
+
+/// Displayed by the shortcuts cheat-sheet, keep in sync with `bind_keys`
+pub const SHORTCUTS: &[(&str, &str)] = &[
+    ("Ctrl+K", "Search everywhere"),
+    ("F5 / Ctrl+R", "Refresh now"),
+    ("Down / J", "Select next item"),
+    ("Up / K", "Select previous item"),
+    ("Right / L", "Select next section"),
+    ("Left / H", "Select previous section"),
+    ("Enter / O", "Open selected item in browser"),
+    ("Y / Ctrl+C", "Copy selected item URL"),
+    ("D", "Mark selected todo as done"),
+    ("Ctrl+,", "Configure access"),
+    ("? / F1", "Show shortcuts"),
+];
+
+pub fn bind_keys(cx: &mut App) {
+    cx.bind_keys([
+        KeyBinding::new("secondary-k", OpenSearch, None),
+        KeyBinding::new("f5", Refresh, Some("Dashboard")),
+        KeyBinding::new("secondary-r", Refresh, Some("Dashboard")),
+        KeyBinding::new("secondary-,", OpenConfigure, Some("Dashboard")),
+        KeyBinding::new("f1", ShowShortcuts, Some("Dashboard")),
+        KeyBinding::new("down", SelectNext, Some(NAVIGATION_CONTEXT)),
+        KeyBinding::new("j", SelectNext, Some(NAVIGATION_CONTEXT)),
+        KeyBinding::new("up", SelectPrevious, Some(NAVIGATION_CONTEXT)),
+        KeyBinding::new("k", SelectPrevious, Some(NAVIGATION_CONTEXT)),
+        KeyBinding::new("right", SelectNextSection, Some(NAVIGATION_CONTEXT)),
+        KeyBinding::new("l", SelectNextSection, Some(NAVIGATION_CONTEXT)),
+        KeyBinding::new("left", SelectPreviousSection, Some(NAVIGATION_CONTEXT)),
+        KeyBinding::new("h", SelectPreviousSection, Some(NAVIGATION_CONTEXT)),
+        KeyBinding::new("enter", OpenSelected, Some(NAVIGATION_CONTEXT)),
+        KeyBinding::new("o", OpenSelected, Some(NAVIGATION_CONTEXT)),
+        KeyBinding::new("y", CopySelectedUrl, Some(NAVIGATION_CONTEXT)),
+        KeyBinding::new("secondary-c", CopySelectedUrl, Some(NAVIGATION_CONTEXT)),
+        KeyBinding::new("d", MarkSelectedTodoDone, Some(NAVIGATION_CONTEXT)),
+        KeyBinding::new("shift-/", ShowShortcuts, Some(NAVIGATION_CONTEXT)),
+    ]);
+}
@@ -0,0 +1,302 @@
+use std::{
+    fs,
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{SocketAddr, TcpListener},
+    path::PathBuf,
+    sync::{
+        mpsc::{self, Receiver, Sender},
+        Arc, Mutex,
+    },
+    thread,
+    time::Duration,
+};
+
+use gpui::*;
+use thiserror::Error;
+use tracing_unwrap::ResultExt;
+
+use crate::{api::snapshot::Snapshot, config::api::ApiConfig, dashboard::Dashboard};
+
+pub mod snapshot;
+
+/// API reads dashboard state, it doesn't query Gitlab itself
+pub const API_UPDATE_EVERY: Duration = Duration::from_secs(2);
+/// Longer request heads are rejected
+pub const MAX_REQUEST_HEAD_SIZE: u64 = 8 * 1024;
+/// Accepted `Host` header names, others may be a DNS rebinding from a web page
+pub const LOCAL_HOSTS: [&str; 3] = ["localhost", "127.0.0.1", "[::1]"];
+
+#[derive(Debug, Error)]
+pub enum ApiError {
+    #[error("Invalid address {0}: {1}")]
+    Address(String, String),
+    #[error("Address {0} is not a loopback one, the API is local only")]
+    NotLocal(String),
+    #[error("Unable to listen on {0}: {1}")]
+    Bind(String, io::Error),
+}
+
+/// Last snapshot (as JSON) and subscribers waiting for the next ones
+struct Shared {
+    snapshot: Mutex<(Snapshot, String)>,
+    subscribers: Mutex<Vec<Sender<String>>>,
+}
+
+impl Shared {
+    fn new() -> Self {
+        let snapshot = Snapshot::default();
+        let json = serde_json::to_string(&snapshot).unwrap_or_log();
+
+        Self {
+            snapshot: Mutex::new((snapshot, json)),
+            subscribers: Mutex::new(vec![]),
+        }
+    }
+
+    fn json(&self) -> String {
+        self.snapshot.lock().unwrap_or_log().1.clone()
+    }
+
+    fn subscribe(&self) -> Receiver<String> {
+        let (sender, receiver) = mpsc::channel();
+        self.subscribers.lock().unwrap_or_log().push(sender);
+
+        receiver
+    }
+
+    /// Store and broadcast `snapshot` if it changed
+    fn update(&self, snapshot: Snapshot) {
+        let mut current = self.snapshot.lock().unwrap_or_log();
+        if current.0 == snapshot {
+            return;
+        }
+        let json = serde_json::to_string(&snapshot).unwrap_or_log();
+        self.subscribers
+            .lock()
+            .unwrap_or_log()
+            .retain(|subscriber| subscriber.send(json.clone()).is_ok());
+        *current = (snapshot, json);
+    }
+}
+
+enum Listener {
+    Tcp(TcpListener),
+    #[cfg(unix)]
+    Unix(std::os::unix::net::UnixListener),
+}
+
+trait Stream: Read + Write + Send {}
+
+impl<T: Read + Write + Send> Stream for T {}
+
+impl Listener {
+    /// Listened TCP port, `None` for a Unix socket
+    fn port(&self) -> Option<u16> {
+        match self {
+            Listener::Tcp(listener) => listener.local_addr().ok().map(|address| address.port()),
+            #[cfg(unix)]
+            Listener::Unix(_) => None,
+        }
+    }
+
+    fn accept(&self) -> io::Result<Box<dyn Stream>> {
+        match self {
+            Listener::Tcp(listener) => Ok(Box::new(listener.accept()?.0)),
+            #[cfg(unix)]
+            Listener::Unix(listener) => Ok(Box::new(listener.accept()?.0)),
+        }
+    }
+}
+
+/// Running local API, its Unix socket (if any) is removed when dropped
+pub struct Api {
+    shared: Arc<Shared>,
+    socket: Option<PathBuf>,
+}
+
+impl Global for Api {}
+
+impl Api {
+    pub fn spawn(config: &ApiConfig) -> Result<Self, ApiError> {
+        let (listener, socket) = bind(config)?;
+        let shared = Arc::new(Shared::new());
+        let port = listener.port();
+
+        thread::spawn({
+            let shared = shared.clone();
+
+            move || loop {
+                match listener.accept() {
+                    Ok(stream) => {
+                        let shared = shared.clone();
+                        thread::spawn(move || {
+                            if let Err(error) = handle(stream, &shared, port) {
+                                tracing::debug!("API connection closed: {}", error);
+                            }
+                        });
+                    }
+                    Err(error) => tracing::warn!("Unable to accept API connection: {}", error),
+                }
+            }
+        });
+
+        Ok(Self { shared, socket })
+    }
+
+    fn update(&self, snapshot: Snapshot) {
+        self.shared.update(snapshot);
+    }
+}
+
+impl Drop for Api {
+    fn drop(&mut self) {
+        if let Some(socket) = &self.socket {
+            let _ = fs::remove_file(socket);
+        }
+    }
+}
+
+/// Feed API with dashboard state
+pub fn run(window: &mut Window, cx: &mut App, dashboard: Entity<Dashboard>) {
+    window
+        .spawn(cx, {
+            let dashboard = dashboard.downgrade();
+
+            async move |cx| loop {
+                let Ok(snapshot) =
+                    dashboard.read_with(cx, |dashboard, cx| dashboard.api_snapshot(cx))
+                else {
+                    break;
+                };
+                let _ = cx.update(|_window, cx| {
+                    if let Some(api) = cx.try_global::<Api>() {
+                        api.update(snapshot);
+                    }
+                });
+
+                Timer::after(API_UPDATE_EVERY).await;
+            }
+        })
+        .detach();
+}
+
+fn bind(config: &ApiConfig) -> Result<(Listener, Option<PathBuf>), ApiError> {
+    #[cfg(unix)]
+    if let Some(socket) = &config.socket {
+        use std::os::unix::fs::FileTypeExt;
+
+        // Left by a previous run, anything else at this path is not ours to remove
+        if fs::symlink_metadata(socket).is_ok_and(|metadata| metadata.file_type().is_socket()) {
+            let _ = fs::remove_file(socket);
+        }
+        let listener = std::os::unix::net::UnixListener::bind(socket)
+            .map_err(|error| ApiError::Bind(socket.display().to_string(), error))?;
+        tracing::info!("Serving API on {}", socket.display());
+
+        return Ok((Listener::Unix(listener), Some(socket.clone())));
+    }
+
+    let address = config
+        .address
+        .parse::<SocketAddr>()
+        .map_err(|error| ApiError::Address(config.address.clone(), error.to_string()))?;
+    if !address.ip().is_loopback() {
+        return Err(ApiError::NotLocal(config.address.clone()));
+    }
+    let listener = TcpListener::bind(address)
+        .map_err(|error| ApiError::Bind(config.address.clone(), error))?;
+    tracing::info!("Serving API on {}", address);
+
+    Ok((Listener::Tcp(listener), None))
+}
+
+/// Answer `GET /snapshot` with current snapshot, `GET /subscribe` with current snapshot then
+/// each new one, as JSON lines until client disconnects. Requests from web pages (with an
+/// `Origin` or a foreign `Host` on TCP `port`) are rejected.
+fn handle(mut stream: Box<dyn Stream>, shared: &Shared, port: Option<u16>) -> io::Result<()> {
+    let mut request_line = String::new();
+    let mut host = None;
+    let mut origin = false;
+    {
+        let mut reader = BufReader::new((&mut stream).take(MAX_REQUEST_HEAD_SIZE));
+        reader.read_line(&mut request_line)?;
+        let mut line = String::new();
+        while reader.read_line(&mut line)? > 2 {
+            if let Some((name, value)) = line.split_once(':') {
+                match name.trim().to_lowercase().as_str() {
+                    "host" => host = Some(value.trim().to_lowercase()),
+                    "origin" => origin = true,
+                    _ => {}
+                }
+            }
+            line.clear();
+        }
+    }
+
+    let local = match port {
+        Some(port) => host.is_some_and(|host| is_local_host(&host, port)),
+        // Not reachable by browsers
+        None => true,
+    };
+    if origin || !local {
+        return respond(&mut *stream, "403 Forbidden", "text/plain", "Forbidden");
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let (method, path) = (
+        parts.next(),
+        parts.next().map(|path| path.split('?').next()),
+    );
+    match (method, path.flatten()) {
+        (Some("GET"), Some("/snapshot")) => {
+            respond(&mut *stream, "200 OK", "application/json", &shared.json())
+        }
+        (Some("GET"), Some("/subscribe")) => {
+            let receiver = shared.subscribe();
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: application/x-ndjson\r\n\
+                Cache-Control: no-cache\r\nConnection: close\r\n\r\n{}\n",
+                shared.json()
+            )?;
+            stream.flush()?;
+            while let Ok(json) = receiver.recv() {
+                writeln!(stream, "{}", json)?;
+                stream.flush()?;
+            }
+            Ok(())
+        }
+        (Some("GET"), _) => respond(&mut *stream, "404 Not Found", "text/plain", "Not found"),
+        _ => respond(
+            &mut *stream,
+            "405 Method Not Allowed",
+            "text/plain",
+            "Method not allowed",
+        ),
+    }
+}
+
+/// `host` (a `Host` header value) is a loopback name with given `port`
+fn is_local_host(host: &str, port: u16) -> bool {
+    LOCAL_HOSTS.iter().any(|name| {
+        host.strip_prefix(name)
+            .is_some_and(|rest| rest == format!(":{}", port) || (rest.is_empty() && port == 80))
+    })
+}
+
+fn respond(
+    stream: &mut dyn Stream,
+    status: &str,
+    content_type: &str,
+    body: &str,
+) -> io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
@@ -0,0 +1,152 @@
+use serde::Serialize;
+
+use crate::{
+    state::gitlab::{
+        issue::Issue,
+        project::{merge_request::MergeRequest, pipeline::Pipeline},
+        todo::Todo,
+    },
+    utils::{project_path, snake_case},
+};
+
+/// Aggregated dashboard state served to local tools
+#[derive(Debug, Clone, PartialEq, Default, Serialize)]
+pub struct Snapshot {
+    pub instances: Vec<InstanceSnapshot>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct InstanceSnapshot {
+    pub host: String,
+    pub projects: Vec<ProjectSnapshot>,
+    /// Assigned opened issues
+    pub issues: Vec<IssueSnapshot>,
+    /// Pending todos
+    pub todos: Vec<TodoSnapshot>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ProjectSnapshot {
+    pub id: u64,
+    /// Full path, eg. `group/project`
+    pub path: String,
+    pub web_url: String,
+    /// Last default branch pipeline
+    pub pipeline: Option<PipelineSnapshot>,
+    /// Displayed opened merge requests
+    pub merge_requests: Vec<MergeRequestSnapshot>,
+}
+
+impl ProjectSnapshot {
+    pub fn new(
+        id: u64,
+        web_url: &str,
+        pipeline: Option<&Pipeline>,
+        merge_requests: &[MergeRequest],
+    ) -> Self {
+        // Card layout only knows `/projects/:id` URL, path is read from nested URLs when possible
+        let path = pipeline
+            .map(|pipeline| pipeline.web_url.as_str())
+            .or(merge_requests
+                .first()
+                .map(|merge_request| merge_request.web_url.as_str()))
+            .unwrap_or(web_url);
+
+        Self {
+            id,
+            path: project_path(path).to_string(),
+            web_url: web_url.to_string(),
+            pipeline: pipeline.map(PipelineSnapshot::from),
+            merge_requests: merge_requests
+                .iter()
+                .map(MergeRequestSnapshot::from)
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PipelineSnapshot {
+    pub id: u64,
+    #[serde(rename = "ref")]
+    pub ref_: String,
+    /// Gitlab status, eg. `success`, `failed` or `waiting_for_resource`
+    pub status: String,
+    pub web_url: String,
+    pub created_at: String,
+}
+
+impl From<&Pipeline> for PipelineSnapshot {
+    fn from(pipeline: &Pipeline) -> Self {
+        Self {
+            id: pipeline.id,
+            ref_: pipeline.ref_.clone(),
+            status: snake_case(&pipeline.status.to_string()),
+            web_url: pipeline.web_url.clone(),
+            created_at: pipeline.created_at.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MergeRequestSnapshot {
+    pub iid: u64,
+    pub title: String,
+    /// Detailed merge status, eg. `mergeable` or `not_approved`
+    pub status: String,
+    pub draft: bool,
+    pub web_url: String,
+    pub updated_at: String,
+}
+
+impl From<&MergeRequest> for MergeRequestSnapshot {
+    fn from(merge_request: &MergeRequest) -> Self {
+        Self {
+            iid: merge_request.iid,
+            title: merge_request.title.clone(),
+            status: merge_request.detailed_merge_status.clone(),
+            draft: merge_request.draft,
+            web_url: merge_request.web_url.clone(),
+            updated_at: merge_request.updated_at.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct IssueSnapshot {
+    pub iid: u64,
+    pub title: String,
+    pub web_url: String,
+    pub updated_at: String,
+}
+
+impl From<&Issue> for IssueSnapshot {
+    fn from(issue: &Issue) -> Self {
+        Self {
+            iid: issue.iid,
+            title: issue.title.clone(),
+            web_url: issue.web_url.clone(),
+            updated_at: issue.updated_at.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TodoSnapshot {
+    pub id: u64,
+    /// eg. `assigned`, `mentioned` or `review_requested`
+    pub action: String,
+    pub body: String,
+    pub target_url: String,
+}
+
+impl From<&Todo> for TodoSnapshot {
+    fn from(todo: &Todo) -> Self {
+        Self {
+            id: todo.id,
+            action: todo.action_name.clone(),
+            body: todo.body.clone(),
+            target_url: todo.target_url.clone(),
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct ApiConfig {
+    /// Serve dashboard state as JSON to local tools, taken into account at startup
+    #[serde(default)]
+    pub enabled: bool,
+    /// Loopback address the API binds to (eg. `127.0.0.1:8789`)
+    #[serde(default = "address_default")]
+    pub address: String,
+    /// Unix socket path, used instead of `address` if any
+    #[serde(default)]
+    pub socket: Option<PathBuf>,
+}
+
+fn address_default() -> String {
+    "127.0.0.1:8789".to_string()
+}
+
+impl Default for ApiConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            address: address_default(),
+            socket: None,
+        }
+    }
+}
@@ -1,11 +1,33 @@
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 
+use crate::config::refresh::RefreshEvery;
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Gitlab {
     pub host: String,
     pub insecure: bool,
     pub cert_insecure: bool,
     pub project_ids: Vec<u64>,
+    /// Override global refresh interval for this instance
+    #[serde(default)]
+    pub refresh_every: Option<RefreshEvery>,
+    /// Override instance refresh interval for given projects
+    #[serde(default)]
+    pub project_refresh_every: HashMap<u64, RefreshEvery>,
+    /// How projects, pipelines and merge requests are fetched
+    #[serde(default)]
+    pub fetcher: Fetcher,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub enum Fetcher {
+    /// Some REST requests per project and merge request
+    #[default]
+    Rest,
+    /// A few GraphQL requests for all projects at once
+    GraphQl,
 }
 
 impl Gitlab {
@@ -15,6 +37,9 @@ impl Gitlab {
             insecure,
             cert_insecure,
             project_ids: vec![],
+            refresh_every: None,
+            project_refresh_every: HashMap::new(),
+            fetcher: Fetcher::default(),
         }
     }
 
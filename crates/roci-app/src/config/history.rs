@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct HistoryConfig {
+    /// Store fetched pipelines in a local database, taken into account at startup
+    #[serde(default = "enabled_default")]
+    pub enabled: bool,
+    /// Pipelines created before are deleted
+    #[serde(default = "retention_days_default")]
+    pub retention_days: u32,
+    /// Older pipelines of a project are deleted above this count
+    #[serde(default = "max_pipelines_per_project_default")]
+    pub max_pipelines_per_project: u32,
+}
+
+fn enabled_default() -> bool {
+    true
+}
+
+fn retention_days_default() -> u32 {
+    90
+}
+
+fn max_pipelines_per_project_default() -> u32 {
+    2000
+}
+
+impl Default for HistoryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: enabled_default(),
+            retention_days: retention_days_default(),
+            max_pipelines_per_project: max_pipelines_per_project_default(),
+        }
+    }
+}
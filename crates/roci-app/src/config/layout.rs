@@ -0,0 +1,78 @@
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumIter, IntoEnumIterator};
+
+/// Host of sections merging items of every instance
+pub const ALL_INSTANCES: &str = "all instances";
+
+#[derive(EnumIter, Display, Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[strum(serialize_all = "lowercase")]
+pub enum SectionKind {
+    Todos,
+    Projects,
+    Issues,
+    Activity,
+    #[strum(serialize = "stale merge requests")]
+    StaleMergeRequests,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Section {
+    pub host: String,
+    pub kind: SectionKind,
+    #[serde(default)]
+    pub collapsed: bool,
+    #[serde(default)]
+    pub hidden: bool,
+}
+
+impl Section {
+    pub fn new(host: String, kind: SectionKind) -> Self {
+        Self {
+            host,
+            kind,
+            collapsed: false,
+            hidden: false,
+        }
+    }
+
+    pub fn is(&self, host: &str, kind: SectionKind) -> bool {
+        self.host == host && self.kind == kind
+    }
+
+    pub fn title(&self) -> String {
+        format!("{} {}", self.host, self.kind)
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct Layout {
+    #[serde(default)]
+    pub sections: Vec<Section>,
+    #[serde(default)]
+    pub compact: bool,
+    #[serde(default)]
+    pub unified: bool,
+}
+
+impl Layout {
+    /// Sections of given instances in user order, sections never customized
+    /// (like new instances ones) are appended in default order
+    pub fn sections(&self, hosts: &[String]) -> Vec<Section> {
+        let mut sections = self
+            .sections
+            .iter()
+            .filter(|section| hosts.contains(&section.host))
+            .cloned()
+            .collect::<Vec<Section>>();
+
+        for kind in SectionKind::iter() {
+            for host in hosts {
+                if !sections.iter().any(|section| section.is(host, kind)) {
+                    sections.push(Section::new(host.clone(), kind));
+                }
+            }
+        }
+
+        sections
+    }
+}
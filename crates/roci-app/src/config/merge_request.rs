@@ -29,3 +29,34 @@ impl Default for ShowMergeRequest {
         Self::OnlyMine
     }
 }
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct MergeRequestAging {
+    /// Opened merge requests icons fade and show their age once older (days)
+    #[serde(default = "fade_after_days_default")]
+    pub fade_after_days: u32,
+    /// Opened merge requests without activity for this long are listed as stale (days)
+    #[serde(default = "stale_after_days_default")]
+    pub stale_after_days: u32,
+    /// Notify when one of my merge requests waits for review for this long (days), never if none
+    #[serde(default)]
+    pub notify_waiting_review_after_days: Option<u32>,
+}
+
+fn fade_after_days_default() -> u32 {
+    7
+}
+
+fn stale_after_days_default() -> u32 {
+    14
+}
+
+impl Default for MergeRequestAging {
+    fn default() -> Self {
+        Self {
+            fade_after_days: fade_after_days_default(),
+            stale_after_days: stale_after_days_default(),
+            notify_waiting_review_after_days: None,
+        }
+    }
+}
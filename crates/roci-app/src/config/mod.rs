@@ -4,12 +4,32 @@ use serde::{Deserialize, Serialize};
 use std::{fs, io, path::PathBuf};
 use thiserror::Error;
 
-use crate::config::{merge_request::ShowMergeRequest, refresh::RefreshEvery, theme::ThemeMode};
+use crate::config::{
+    api::ApiConfig,
+    history::HistoryConfig,
+    layout::Layout,
+    merge_request::{MergeRequestAging, ShowMergeRequest},
+    project::ProjectLayout,
+    prometheus::PrometheusConfig,
+    refresh::{AdaptiveRefresh, RefreshEvery},
+    status::StatusStyles,
+    theme::ThemeMode,
+    tray::TrayConfig,
+    webhook::WebhookConfig,
+};
 
+pub mod api;
 pub mod gitlab_;
+pub mod history;
+pub mod layout;
 pub mod merge_request;
+pub mod project;
+pub mod prometheus;
 pub mod refresh;
+pub mod status;
 pub mod theme;
+pub mod tray;
+pub mod webhook;
 
 const KEYRING_SERVICE_NAME: &str = "roci";
 
@@ -17,9 +37,35 @@ const KEYRING_SERVICE_NAME: &str = "roci";
 pub struct Config {
     pub gitlabs: Vec<gitlab_::Gitlab>,
     pub refresh_every: RefreshEvery,
+    #[serde(default)]
+    pub adaptive_refresh: AdaptiveRefresh,
     pub show_merge_request: ShowMergeRequest,
     #[serde(default)]
+    pub merge_request_aging: MergeRequestAging,
+    #[serde(default)]
     pub theme_mode: ThemeMode,
+    /// Theme name used in dark mode, default one if none
+    #[serde(default)]
+    pub dark_theme: Option<String>,
+    /// Theme name used in light mode, default one if none
+    #[serde(default)]
+    pub light_theme: Option<String>,
+    #[serde(default)]
+    pub project_layout: ProjectLayout,
+    #[serde(default)]
+    pub layout: Layout,
+    #[serde(default)]
+    pub status_styles: StatusStyles,
+    #[serde(default)]
+    pub tray: TrayConfig,
+    #[serde(default)]
+    pub webhook: WebhookConfig,
+    #[serde(default)]
+    pub history: HistoryConfig,
+    #[serde(default)]
+    pub prometheus: PrometheusConfig,
+    #[serde(default)]
+    pub api: ApiConfig,
 }
 
 impl Config {
@@ -29,6 +75,33 @@ impl Config {
             .join(".roci"))
     }
 
+    /// Directory where additional `ThemeSet` JSON files are loaded from
+    pub fn themes_dir() -> Result<PathBuf, ConfigError> {
+        Ok(homedir::my_home()?
+            .ok_or(ConfigError::NoHome)?
+            .join(".config")
+            .join("roci")
+            .join("themes"))
+    }
+
+    /// Directory where `report.md` and `report.html` export templates are loaded from
+    pub fn templates_dir() -> Result<PathBuf, ConfigError> {
+        Ok(homedir::my_home()?
+            .ok_or(ConfigError::NoHome)?
+            .join(".config")
+            .join("roci")
+            .join("templates"))
+    }
+
+    /// Directory of local data (pipelines history)
+    pub fn data_dir() -> Result<PathBuf, ConfigError> {
+        Ok(homedir::my_home()?
+            .ok_or(ConfigError::NoHome)?
+            .join(".local")
+            .join("share")
+            .join("roci"))
+    }
+
     pub fn from_env() -> Result<(Self, Option<ConfigLoadInfo>), ConfigError> {
         let path = Self::path()?;
 
@@ -0,0 +1,31 @@
+use gpui::*;
+use gpui_component::select::SelectItem;
+use serde::{Deserialize, Serialize};
+use strum::EnumIter;
+
+#[derive(EnumIter, Debug, PartialEq, Clone, Deserialize, Serialize)]
+pub enum ProjectLayout {
+    List,
+    Table,
+}
+
+impl SelectItem for ProjectLayout {
+    type Value = ProjectLayout;
+
+    fn title(&self) -> SharedString {
+        match self {
+            ProjectLayout::List => SharedString::new("Projects as list".to_string()),
+            ProjectLayout::Table => SharedString::new("Projects as table".to_string()),
+        }
+    }
+
+    fn value(&self) -> &Self::Value {
+        self
+    }
+}
+
+impl Default for ProjectLayout {
+    fn default() -> Self {
+        Self::List
+    }
+}
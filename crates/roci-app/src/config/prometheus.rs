@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct PrometheusConfig {
+    /// Expose Prometheus metrics on `/metrics`, taken into account at startup
+    #[serde(default)]
+    pub enabled: bool,
+    /// Address the endpoint binds to (eg. `127.0.0.1:9464`)
+    #[serde(default = "address_default")]
+    pub address: String,
+}
+
+fn address_default() -> String {
+    "127.0.0.1:9464".to_string()
+}
+
+impl Default for PrometheusConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            address: address_default(),
+        }
+    }
+}
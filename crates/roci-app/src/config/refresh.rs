@@ -3,7 +3,10 @@ use std::time::Duration;
 use gpui::*;
 use gpui_component::select::SelectItem;
 use serde::{Deserialize, Serialize};
-use strum::EnumIter;
+use strum::{EnumIter, IntoEnumIterator};
+
+/// Custom durations can't be shorter than this
+pub const MIN_REFRESH_SECONDS: u64 = 10;
 
 #[derive(EnumIter, Debug, PartialEq, Clone, Deserialize, Serialize)]
 pub enum RefreshEvery {
@@ -13,8 +16,23 @@ pub enum RefreshEvery {
     X30Minutes,
     X1Hour,
     X6Hours,
+    /// Custom duration in seconds
+    Custom(u64),
 }
+
 impl RefreshEvery {
+    /// Values proposed in refresh select
+    pub fn presets() -> Vec<Self> {
+        Self::iter()
+            .filter(|value| !matches!(value, Self::Custom(_)))
+            .collect()
+    }
+
+    /// Parse a custom duration in seconds
+    pub fn parse(raw: &str) -> Option<Self> {
+        raw.trim().parse::<u64>().ok().map(Self::Custom)
+    }
+
     pub fn duration(&self) -> Duration {
         match self {
             RefreshEvery::X60Seconds => Duration::from_secs(60),
@@ -23,6 +41,9 @@ impl RefreshEvery {
             RefreshEvery::X30Minutes => Duration::from_secs(60 * 30),
             RefreshEvery::X1Hour => Duration::from_secs(60 * 60 * 1),
             RefreshEvery::X6Hours => Duration::from_secs(60 * 60 * 6),
+            RefreshEvery::Custom(seconds) => {
+                Duration::from_secs((*seconds).max(MIN_REFRESH_SECONDS))
+            }
         }
     }
 }
@@ -38,6 +59,9 @@ impl SelectItem for RefreshEvery {
             RefreshEvery::X30Minutes => SharedString::new("Refresh every 30 minutes".to_string()),
             RefreshEvery::X1Hour => SharedString::new("Refresh every 1 hour".to_string()),
             RefreshEvery::X6Hours => SharedString::new("Refresh every 6 hours".to_string()),
+            RefreshEvery::Custom(seconds) => {
+                SharedString::new(format!("Refresh every {} seconds", seconds))
+            }
         }
     }
 
@@ -51,3 +75,38 @@ impl Default for RefreshEvery {
         Self::X1Hour
     }
 }
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct AdaptiveRefresh {
+    /// Interval used while a pipeline is running, if shorter than regular one
+    #[serde(default = "while_running_default")]
+    pub while_running: RefreshEvery,
+    /// Interval is doubled, up to this factor, while nothing changes or on errors
+    #[serde(default = "max_backoff_default")]
+    pub max_backoff: u32,
+    /// Don't refresh while window is minimized or covered
+    #[serde(default = "pause_when_hidden_default")]
+    pub pause_when_hidden: bool,
+}
+
+fn while_running_default() -> RefreshEvery {
+    RefreshEvery::Custom(30)
+}
+
+fn max_backoff_default() -> u32 {
+    4
+}
+
+fn pause_when_hidden_default() -> bool {
+    true
+}
+
+impl Default for AdaptiveRefresh {
+    fn default() -> Self {
+        Self {
+            while_running: while_running_default(),
+            max_backoff: max_backoff_default(),
+            pause_when_hidden: pause_when_hidden_default(),
+        }
+    }
+}
@@ -0,0 +1,232 @@
+use std::collections::HashMap;
+
+use gpui::*;
+use gpui_component::{ActiveTheme, Icon, IconName};
+use serde::{Deserialize, Serialize};
+
+use crate::state::gitlab::project::{
+    merge_request::MERGE_STATUS_MERGEABLE, pipeline::PipelineStatus,
+};
+
+/// Statuses displayed with an icon, pipeline ones then merge request ones
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum StatusKind {
+    Success,
+    Failed,
+    Running,
+    Pending,
+    Manual,
+    Scheduled,
+    Canceled,
+    Skipped,
+    Mergeable,
+    Waiting,
+    Draft,
+    Conflict,
+    NeedsRebase,
+    Blocked,
+    /// Opened merge request with a failed last pipeline
+    FailingPipeline,
+}
+
+impl StatusKind {
+    /// Kind of an opened merge request from its `detailed_merge_status`
+    pub fn of_merge_request(detailed_merge_status: &str) -> Self {
+        match detailed_merge_status {
+            MERGE_STATUS_MERGEABLE => Self::Mergeable,
+            "draft_status" => Self::Draft,
+            "conflict" | "broken_status" => Self::Conflict,
+            "need_rebase" => Self::NeedsRebase,
+            "blocked_status"
+            | "not_approved"
+            | "requested_changes"
+            | "merge_request_blocked"
+            | "discussions_not_resolved" => Self::Blocked,
+            _ => Self::Waiting,
+        }
+    }
+}
+
+impl From<PipelineStatus> for StatusKind {
+    fn from(status: PipelineStatus) -> Self {
+        match status {
+            PipelineStatus::Success => Self::Success,
+            PipelineStatus::Failed => Self::Failed,
+            PipelineStatus::Running => Self::Running,
+            PipelineStatus::Pending
+            | PipelineStatus::Created
+            | PipelineStatus::Preparing
+            | PipelineStatus::WaitingForResource => Self::Pending,
+            PipelineStatus::Manual => Self::Manual,
+            PipelineStatus::Scheduled => Self::Scheduled,
+            PipelineStatus::Canceled => Self::Canceled,
+            PipelineStatus::Skipped => Self::Skipped,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum Severity {
+    Ok,
+    Info,
+    Warning,
+    Error,
+}
+
+/// Theme color names, resolved with current theme
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum StatusColor {
+    Green,
+    Cyan,
+    Blue,
+    Yellow,
+    Red,
+    Muted,
+}
+
+impl StatusColor {
+    pub fn hsla(&self, cx: &App) -> Hsla {
+        match self {
+            StatusColor::Green => cx.theme().green,
+            StatusColor::Cyan => cx.theme().cyan,
+            StatusColor::Blue => cx.theme().blue,
+            StatusColor::Yellow => cx.theme().yellow,
+            StatusColor::Red => cx.theme().red,
+            StatusColor::Muted => cx.theme().muted_foreground,
+        }
+    }
+}
+
+/// Subset of icons usable for statuses (`IconName` can't be written in config)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum StatusIcon {
+    CircleCheck,
+    CircleX,
+    Check,
+    Close,
+    Ellipsis,
+    Loader,
+    User,
+    Calendar,
+    ChevronRight,
+    Minus,
+    File,
+    TriangleAlert,
+    Replace,
+    LayoutDashboard,
+}
+
+impl StatusIcon {
+    pub fn name(&self) -> IconName {
+        match self {
+            StatusIcon::CircleCheck => IconName::CircleCheck,
+            StatusIcon::CircleX => IconName::CircleX,
+            StatusIcon::Check => IconName::Check,
+            StatusIcon::Close => IconName::Close,
+            StatusIcon::Ellipsis => IconName::Ellipsis,
+            StatusIcon::Loader => IconName::Loader,
+            StatusIcon::User => IconName::User,
+            StatusIcon::Calendar => IconName::Calendar,
+            StatusIcon::ChevronRight => IconName::ChevronRight,
+            StatusIcon::Minus => IconName::Minus,
+            StatusIcon::File => IconName::File,
+            StatusIcon::TriangleAlert => IconName::TriangleAlert,
+            StatusIcon::Replace => IconName::Replace,
+            StatusIcon::LayoutDashboard => IconName::LayoutDashboard,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub struct StatusStyle {
+    pub icon: StatusIcon,
+    pub color: StatusColor,
+    pub severity: Severity,
+}
+
+impl StatusStyle {
+    pub const fn new(icon: StatusIcon, color: StatusColor, severity: Severity) -> Self {
+        Self {
+            icon,
+            color,
+            severity,
+        }
+    }
+
+    pub fn icon(&self, cx: &App) -> Icon {
+        Icon::new(self.icon.name()).text_color(self.color.hsla(cx))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+pub enum StatusPreset {
+    #[default]
+    Default,
+    /// Statuses are distinguished by shapes, colors avoid red/green pairs
+    Colorblind,
+}
+
+impl StatusPreset {
+    pub fn style(&self, kind: StatusKind) -> StatusStyle {
+        use Severity as S;
+        use StatusColor as C;
+        use StatusIcon as I;
+
+        match self {
+            StatusPreset::Default => match kind {
+                StatusKind::Success => StatusStyle::new(I::CircleCheck, C::Green, S::Ok),
+                StatusKind::Failed => StatusStyle::new(I::CircleX, C::Red, S::Error),
+                StatusKind::Running => StatusStyle::new(I::Ellipsis, C::Cyan, S::Info),
+                StatusKind::Pending => StatusStyle::new(I::Ellipsis, C::Cyan, S::Info),
+                StatusKind::Manual => StatusStyle::new(I::User, C::Cyan, S::Info),
+                StatusKind::Scheduled => StatusStyle::new(I::Calendar, C::Cyan, S::Info),
+                StatusKind::Canceled => StatusStyle::new(I::Close, C::Muted, S::Warning),
+                StatusKind::Skipped => StatusStyle::new(I::ChevronRight, C::Muted, S::Info),
+                StatusKind::Mergeable => StatusStyle::new(I::LayoutDashboard, C::Green, S::Ok),
+                StatusKind::Waiting => StatusStyle::new(I::LayoutDashboard, C::Cyan, S::Info),
+                StatusKind::Draft => StatusStyle::new(I::File, C::Muted, S::Info),
+                StatusKind::Conflict => StatusStyle::new(I::TriangleAlert, C::Red, S::Error),
+                StatusKind::NeedsRebase => StatusStyle::new(I::Replace, C::Yellow, S::Warning),
+                StatusKind::Blocked => StatusStyle::new(I::Minus, C::Yellow, S::Warning),
+                StatusKind::FailingPipeline => {
+                    StatusStyle::new(I::LayoutDashboard, C::Red, S::Error)
+                }
+            },
+            StatusPreset::Colorblind => match kind {
+                StatusKind::Success => StatusStyle::new(I::Check, C::Blue, S::Ok),
+                StatusKind::Failed => StatusStyle::new(I::CircleX, C::Yellow, S::Error),
+                StatusKind::Running => StatusStyle::new(I::Loader, C::Cyan, S::Info),
+                StatusKind::Pending => StatusStyle::new(I::Ellipsis, C::Muted, S::Info),
+                StatusKind::Manual => StatusStyle::new(I::User, C::Muted, S::Info),
+                StatusKind::Scheduled => StatusStyle::new(I::Calendar, C::Muted, S::Info),
+                StatusKind::Canceled => StatusStyle::new(I::Close, C::Muted, S::Warning),
+                StatusKind::Skipped => StatusStyle::new(I::ChevronRight, C::Muted, S::Info),
+                StatusKind::Mergeable => StatusStyle::new(I::CircleCheck, C::Blue, S::Ok),
+                StatusKind::Waiting => StatusStyle::new(I::LayoutDashboard, C::Muted, S::Info),
+                StatusKind::Draft => StatusStyle::new(I::File, C::Muted, S::Info),
+                StatusKind::Conflict => StatusStyle::new(I::TriangleAlert, C::Yellow, S::Error),
+                StatusKind::NeedsRebase => StatusStyle::new(I::Replace, C::Yellow, S::Warning),
+                StatusKind::Blocked => StatusStyle::new(I::Minus, C::Yellow, S::Warning),
+                StatusKind::FailingPipeline => StatusStyle::new(I::CircleX, C::Yellow, S::Error),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct StatusStyles {
+    #[serde(default)]
+    pub preset: StatusPreset,
+    /// Per status customization, taking precedence over preset
+    #[serde(default)]
+    pub overrides: HashMap<StatusKind, StatusStyle>,
+}
+
+impl StatusStyles {
+    pub fn style(&self, kind: StatusKind) -> StatusStyle {
+        self.overrides
+            .get(&kind)
+            .copied()
+            .unwrap_or_else(|| self.preset.style(kind))
+    }
+}
@@ -1,10 +1,16 @@
-use gpui::SharedString;
+use std::{fs, path::PathBuf};
+
+use gpui::{SharedString, WindowAppearance};
 use gpui_component::{notification::NotificationType, select::SelectItem, ThemeConfig, ThemeSet};
 use serde::{Deserialize, Serialize};
 use strum::EnumIter;
 use thiserror::Error;
 
+use crate::config::Config;
+
 const AYU: &str = include_str!("../../themes/ayu.json");
+pub const DEFAULT_DARK_THEME: &str = "Ayu Dark";
+pub const DEFAULT_LIGHT_THEME: &str = "Ayu Light";
 
 #[derive(EnumIter, Debug, PartialEq, Clone, Copy, Deserialize, Serialize)]
 pub enum ThemeMode {
@@ -14,42 +20,107 @@ pub enum ThemeMode {
 }
 
 impl ThemeMode {
-    pub fn resolve(&self) -> Result<Self, dark_light::Error> {
+    /// Resolve system mode with window appearance when known (to follow live OS changes),
+    /// with OS preference otherwise
+    pub fn is_dark(&self, appearance: Option<WindowAppearance>) -> Result<bool, dark_light::Error> {
         Ok(match self {
-            ThemeMode::System => match dark_light::detect()? {
-                dark_light::Mode::Dark => Self::Dark,
-                dark_light::Mode::Light => Self::Light,
-                dark_light::Mode::Unspecified => Self::Light,
+            ThemeMode::System => match appearance {
+                Some(WindowAppearance::Dark | WindowAppearance::VibrantDark) => true,
+                Some(WindowAppearance::Light | WindowAppearance::VibrantLight) => false,
+                None => match dark_light::detect()? {
+                    dark_light::Mode::Dark => true,
+                    dark_light::Mode::Light => false,
+                    dark_light::Mode::Unspecified => false,
+                },
             },
-            ThemeMode::Dark => ThemeMode::Dark,
-            ThemeMode::Light => ThemeMode::Light,
+            ThemeMode::Dark => true,
+            ThemeMode::Light => false,
         })
     }
+}
+
+/// Embedded themes followed by the ones found in user themes dir
+pub fn load_themes() -> Result<(Vec<ThemeConfig>, Vec<ThemeWarning>), LoadthemeError> {
+    let mut themes = serde_json::from_str::<ThemeSet>(AYU)?.themes;
+    let mut warnings = vec![];
+
+    let dir = match Config::themes_dir() {
+        Ok(dir) => dir,
+        Err(error) => {
+            warnings.push(ThemeWarning::Invalid(PathBuf::new(), error.to_string()));
+            return Ok((themes, warnings));
+        }
+    };
+    let Ok(entries) = fs::read_dir(&dir) else {
+        // No themes dir is not an error
+        return Ok((themes, warnings));
+    };
+
+    let mut paths = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|extension| extension == "json")
+        })
+        .collect::<Vec<PathBuf>>();
+    paths.sort();
 
-    pub fn theme_name(&self) -> Result<&str, dark_light::Error> {
-        match self.resolve()? {
-            ThemeMode::System => unreachable!(),
-            ThemeMode::Dark => Ok("Ayu Dark"),
-            ThemeMode::Light => Ok("Ayu Light"),
+    for path in paths {
+        let theme_set = fs::read_to_string(&path)
+            .map_err(|error| error.to_string())
+            .and_then(|raw| {
+                serde_json::from_str::<ThemeSet>(&raw).map_err(|error| error.to_string())
+            });
+
+        match theme_set {
+            Ok(theme_set) => {
+                for theme in theme_set.themes {
+                    // User themes override embedded ones with same name
+                    themes.retain(|theme_| theme_.name != theme.name);
+                    themes.push(theme);
+                }
+            }
+            Err(error) => warnings.push(ThemeWarning::Invalid(path, error)),
         }
     }
+
+    Ok((themes, warnings))
 }
 
 pub fn load_theme(
-    mode: ThemeMode,
-) -> Result<(ThemeConfig, Option<DarkLightError>), LoadthemeError> {
-    let theme_set = serde_json::from_str::<ThemeSet>(AYU)?;
-    let (theme_name, dark_light_error) = match mode.theme_name() {
-        Ok(theme_name) => (theme_name, None),
-        Err(error) => ("Ayu Light", Some(DarkLightError(error))),
+    themes: &[ThemeConfig],
+    config: &Config,
+    appearance: Option<WindowAppearance>,
+) -> Result<(ThemeConfig, Vec<ThemeWarning>), LoadthemeError> {
+    let mut warnings = vec![];
+    let is_dark = match config.theme_mode.is_dark(appearance) {
+        Ok(is_dark) => is_dark,
+        Err(error) => {
+            warnings.push(ThemeWarning::DarkLight(error));
+            false
+        }
+    };
+    let (theme_name, default_name) = if is_dark {
+        (config.dark_theme.as_deref(), DEFAULT_DARK_THEME)
+    } else {
+        (config.light_theme.as_deref(), DEFAULT_LIGHT_THEME)
+    };
+    let find = |name: &str| {
+        themes
+            .iter()
+            .find(|theme| theme.name == SharedString::new(name))
+            .cloned()
     };
 
-    let theme = theme_set
-        .themes
-        .into_iter()
-        .find(|theme| theme.name == SharedString::new(theme_name))
-        .ok_or(LoadthemeError::UnknownTheme(theme_name.to_string()))?;
-    Ok((theme, dark_light_error))
+    if let Some(theme_name) = theme_name {
+        match find(theme_name) {
+            Some(theme) => return Ok((theme, warnings)),
+            None => warnings.push(ThemeWarning::Missing(theme_name.to_string())),
+        }
+    }
+
+    let theme = find(default_name).ok_or(LoadthemeError::UnknownTheme(default_name.to_string()))?;
+    Ok((theme, warnings))
 }
 
 #[derive(Debug, Error)]
@@ -60,16 +131,21 @@ pub enum LoadthemeError {
     UnknownTheme(String),
 }
 
-pub struct DarkLightError(dark_light::Error);
+#[derive(Debug, Error)]
+pub enum ThemeWarning {
+    #[error("Can't determine system dark/light preference: {0}")]
+    DarkLight(dark_light::Error),
+    #[error("Theme '{0}' not found, default one used")]
+    Missing(String),
+    #[error("Can't load theme file {0}: {1}")]
+    Invalid(PathBuf, String),
+}
 
-impl DarkLightError {
+impl ThemeWarning {
     pub fn into_notification(&self) -> (NotificationType, SharedString) {
         (
             NotificationType::Warning,
-            SharedString::new(format!(
-                "Can't determine system dark/light preference: {}",
-                self.0
-            )),
+            SharedString::new(self.to_string()),
         )
     }
 }
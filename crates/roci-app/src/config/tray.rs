@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct TrayConfig {
+    /// Display a tray icon (StatusNotifierItem on Linux), taken into account at startup
+    #[serde(default = "enabled_default")]
+    pub enabled: bool,
+    /// Closing the window minimizes it instead of quitting while tray icon is displayed
+    #[serde(default)]
+    pub keep_running: bool,
+}
+
+fn enabled_default() -> bool {
+    true
+}
+
+impl Default for TrayConfig {
+    fn default() -> Self {
+        Self {
+            enabled: enabled_default(),
+            keep_running: false,
+        }
+    }
+}
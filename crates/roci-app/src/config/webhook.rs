@@ -0,0 +1,27 @@
+use serde::{Deserialize, Serialize};
+
+/// Keyring entry of the secret token Gitlab webhooks must send
+pub const WEBHOOK_SECRET_KEY: &str = "roci-webhook";
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct WebhookConfig {
+    /// Listen for Gitlab webhooks, taken into account at startup
+    #[serde(default)]
+    pub enabled: bool,
+    /// Address the listener binds to (eg. `127.0.0.1:8787`, `0.0.0.0:8787`)
+    #[serde(default = "address_default")]
+    pub address: String,
+}
+
+fn address_default() -> String {
+    "127.0.0.1:8787".to_string()
+}
+
+impl Default for WebhookConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            address: address_default(),
+        }
+    }
+}
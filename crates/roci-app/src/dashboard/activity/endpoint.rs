@@ -0,0 +1,49 @@
+use derive_more::Constructor;
+use gitlab::api::{Endpoint, QueryParams};
+
+use crate::dashboard::activity::EVENTS_PER_PAGE;
+
+#[derive(Debug, Constructor)]
+pub struct MyEvents {
+    page: u64,
+}
+
+impl Endpoint for MyEvents {
+    fn method(&self) -> reqwest::Method {
+        reqwest::Method::GET
+    }
+
+    fn endpoint(&self) -> std::borrow::Cow<'static, str> {
+        "events".into()
+    }
+
+    fn parameters(&self) -> QueryParams<'_> {
+        let mut params = QueryParams::default();
+        params.push("page", self.page);
+        params.push("per_page", EVENTS_PER_PAGE);
+        params
+    }
+}
+
+#[derive(Debug, Constructor)]
+pub struct ProjectEvents {
+    project_id: u64,
+    page: u64,
+}
+
+impl Endpoint for ProjectEvents {
+    fn method(&self) -> reqwest::Method {
+        reqwest::Method::GET
+    }
+
+    fn endpoint(&self) -> std::borrow::Cow<'static, str> {
+        format!("projects/{}/events", self.project_id).into()
+    }
+
+    fn parameters(&self) -> QueryParams<'_> {
+        let mut params = QueryParams::default();
+        params.push("page", self.page);
+        params.push("per_page", EVENTS_PER_PAGE);
+        params
+    }
+}
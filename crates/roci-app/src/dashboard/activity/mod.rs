@@ -0,0 +1,428 @@
+use std::collections::{HashMap, HashSet};
+
+use gitlab::api::{projects, AsyncQuery};
+use gpui::{prelude::FluentBuilder, *};
+use gpui_component::{
+    button::{Button, ButtonVariants},
+    h_flex,
+    label::Label,
+    notification::NotificationType,
+    select::{Select, SelectEvent, SelectItem, SelectState},
+    v_flex, Icon, IconName, IndexPath, StyledExt, WindowExt,
+};
+use roci_app_components::{error::WithButtonModalError, with_button_error, LoadState};
+use strum::{EnumIter, IntoEnumIterator};
+use tracing_unwrap::ResultExt;
+
+use crate::{
+    dashboard::{
+        activity::endpoint::{MyEvents, ProjectEvents},
+        error::GitlabError,
+        refresh::wait_refresh,
+    },
+    state::{
+        gitlab::{
+            event::{Event, EventKind},
+            GitlabClient,
+        },
+        refresh::RefreshKey,
+        AppState,
+    },
+    utils::{short_datetime, Truncate},
+};
+
+mod endpoint;
+
+pub const EVENTS_PER_PAGE: u64 = 20;
+
+#[derive(EnumIter, Debug, PartialEq, Clone)]
+pub enum ActivityFilter {
+    All,
+    Pushes,
+    Comments,
+    MergeRequests,
+    Issues,
+    Others,
+}
+
+impl ActivityFilter {
+    fn accept(&self, kind: EventKind) -> bool {
+        match self {
+            ActivityFilter::All => true,
+            ActivityFilter::Pushes => kind == EventKind::Push,
+            ActivityFilter::Comments => kind == EventKind::Comment,
+            ActivityFilter::MergeRequests => kind == EventKind::MergeRequest,
+            ActivityFilter::Issues => kind == EventKind::Issue,
+            ActivityFilter::Others => kind == EventKind::Other,
+        }
+    }
+}
+
+impl SelectItem for ActivityFilter {
+    type Value = ActivityFilter;
+
+    fn title(&self) -> SharedString {
+        match self {
+            ActivityFilter::All => SharedString::new("All activity".to_string()),
+            ActivityFilter::Pushes => SharedString::new("Pushes".to_string()),
+            ActivityFilter::Comments => SharedString::new("Comments".to_string()),
+            ActivityFilter::MergeRequests => SharedString::new("Merge requests".to_string()),
+            ActivityFilter::Issues => SharedString::new("Issues".to_string()),
+            ActivityFilter::Others => SharedString::new("Others".to_string()),
+        }
+    }
+
+    fn value(&self) -> &Self::Value {
+        self
+    }
+}
+
+pub struct Activities {
+    inner: Entity<LoadState<ActivitiesInner, WithButtonModalError<GitlabError>>>,
+    config: crate::config::gitlab_::Gitlab,
+    filter: Entity<SelectState<Vec<ActivityFilter>>>,
+}
+
+impl Activities {
+    pub fn new(
+        window: &mut Window,
+        cx: &mut Context<Self>,
+        config: crate::config::gitlab_::Gitlab,
+    ) -> Self {
+        let filter = cx.new(|cx| {
+            SelectState::new(
+                ActivityFilter::iter().collect(),
+                Some(IndexPath::default()),
+                window,
+                cx,
+            )
+        });
+        cx.subscribe_in(&filter, window, Self::on_select_filter)
+            .detach();
+
+        let refresh_key = RefreshKey::Activity(config.host.clone());
+        cx.spawn_in(window, async move |this, cx| loop {
+            wait_refresh(cx, &refresh_key).await;
+            if this
+                .update_in(cx, |this, window, cx| this.load(window, cx))
+                .is_err()
+            {
+                break;
+            }
+        })
+        .detach();
+
+        let mut this = Self {
+            inner: cx.new(|_cx| LoadState::Loading),
+            config,
+            filter,
+        };
+        this.load(window, cx);
+
+        this
+    }
+
+    /// Load first page of events, merged into already loaded ones to keep
+    /// the selected filter and the "load more" pages
+    fn load(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let config = self.config.clone();
+        let host = config.host.clone();
+        let gitlabs = AppState::global(cx).gitlabs();
+        let filter = self.filter.clone();
+        let refresh_key = RefreshKey::Activity(config.host.clone());
+
+        cx.spawn_in(window, async move |this, cx| {
+            match gitlabs.get(&config).await {
+                Ok(gitlab) => {
+                    let mut project_urls = HashMap::new();
+                    match get_events(&gitlab, &config.project_ids, 1, &mut project_urls).await {
+                        Ok(events) => {
+                            let _ = this.update_in(cx, |this, _window, cx| {
+                                AppState::global_mut(cx).refreshes_mut().loaded(
+                                    &refresh_key,
+                                    events
+                                        .iter()
+                                        .map(|event| event.id.to_string())
+                                        .collect::<Vec<String>>()
+                                        .join(","),
+                                    false,
+                                );
+                                if let LoadState::Ready(inner) = this.inner.read(cx) {
+                                    inner.clone().update(cx, |inner, cx| {
+                                        inner.project_urls.extend(project_urls);
+                                        inner.push(events);
+                                        cx.notify();
+                                    });
+                                } else {
+                                    let inner = cx.new(|_cx| {
+                                        ActivitiesInner::new(
+                                            gitlab,
+                                            config.project_ids.clone(),
+                                            events,
+                                            project_urls,
+                                            filter,
+                                        )
+                                    });
+                                    this.inner = cx.new(|_cx| LoadState::Ready(inner));
+                                }
+                                cx.notify();
+                            });
+                        }
+                        Err(error) => {
+                            let _ = this.update_in(cx, |this, _window, cx| {
+                                AppState::global_mut(cx)
+                                    .refreshes_mut()
+                                    .failed(&refresh_key);
+                                this.inner = cx.new(|cx| {
+                                    with_button_error!(
+                                        cx,
+                                        "Load error".into(),
+                                        format!("Error during load activity of {}", host),
+                                        error.into()
+                                    )
+                                });
+                                cx.notify();
+                            });
+                        }
+                    }
+                }
+                Err(error) => {
+                    let _ = this.update_in(cx, |this, _window, cx| {
+                        AppState::global_mut(cx)
+                            .refreshes_mut()
+                            .failed(&refresh_key);
+                        this.inner = cx.new(|cx| {
+                            with_button_error!(
+                                cx,
+                                "Load error".into(),
+                                format!("Error during connect gitlab {}", host),
+                                error.into()
+                            )
+                        });
+                        cx.notify();
+                    });
+                }
+            };
+        })
+        .detach();
+    }
+
+    fn on_select_filter(
+        &mut self,
+        _: &Entity<SelectState<Vec<ActivityFilter>>>,
+        event: &SelectEvent<Vec<ActivityFilter>>,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let SelectEvent::Confirm(Some(_)) = event else {
+            return;
+        };
+        cx.notify();
+    }
+}
+
+impl Render for Activities {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        let host = self.config.host.clone();
+
+        div()
+            .child(
+                h_flex()
+                    .child(Icon::new(IconName::ArrowRight))
+                    .child(Label::new(format!("{} activity", host)).text_xl())
+                    .child(" ".to_string())
+                    .child(div().w(px(200.)).child(Select::new(&self.filter))),
+            )
+            .child(self.inner.clone())
+    }
+}
+
+pub struct ActivitiesInner {
+    gitlab: GitlabClient,
+    project_ids: Vec<u64>,
+    events: Vec<Event>,
+    project_urls: HashMap<u64, String>,
+    filter: Entity<SelectState<Vec<ActivityFilter>>>,
+    page: u64,
+    loading: bool,
+    exhausted: bool,
+}
+
+impl ActivitiesInner {
+    fn new(
+        gitlab: GitlabClient,
+        project_ids: Vec<u64>,
+        events: Vec<Event>,
+        project_urls: HashMap<u64, String>,
+        filter: Entity<SelectState<Vec<ActivityFilter>>>,
+    ) -> Self {
+        let mut this = Self {
+            gitlab,
+            project_ids,
+            events: vec![],
+            project_urls,
+            filter,
+            page: 1,
+            loading: false,
+            exhausted: events.is_empty(),
+        };
+        this.push(events);
+
+        this
+    }
+
+    fn push(&mut self, events: Vec<Event>) {
+        let known = self
+            .events
+            .iter()
+            .map(|event| event.id)
+            .collect::<HashSet<u64>>();
+        self.events.extend(
+            events
+                .into_iter()
+                .filter(|event| !known.contains(&event.id)),
+        );
+        self.events
+            .sort_by(|a, b| b.created_at.cmp(&a.created_at).then(b.id.cmp(&a.id)));
+        self.events.dedup_by_key(|event| event.id);
+    }
+
+    fn load_more(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.loading {
+            return;
+        }
+        self.loading = true;
+        cx.notify();
+
+        let gitlab = self.gitlab.clone();
+        let project_ids = self.project_ids.clone();
+        let page = self.page + 1;
+        let mut project_urls = self.project_urls.clone();
+
+        cx.spawn_in(window, async move |this, cx| {
+            let result = get_events(&gitlab, &project_ids, page, &mut project_urls).await;
+            let _ = this.update_in(cx, |this, window, cx| {
+                this.loading = false;
+
+                match result {
+                    Ok(events) => {
+                        this.page = page;
+                        this.project_urls = project_urls;
+                        this.exhausted = events.is_empty();
+                        this.push(events);
+                    }
+                    Err(error) => window.push_notification(
+                        (
+                            NotificationType::Error,
+                            SharedString::new(format!("Can't load more activity: {}", error)),
+                        ),
+                        cx,
+                    ),
+                }
+
+                cx.notify();
+            });
+        })
+        .detach();
+    }
+}
+
+impl Render for ActivitiesInner {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let filter = self
+            .filter
+            .read(cx)
+            .selected_value()
+            .cloned()
+            .unwrap_or(ActivityFilter::All);
+
+        let mut days: Vec<(String, Vec<&Event>)> = vec![];
+        for event in self
+            .events
+            .iter()
+            .filter(|event| filter.accept(event.kind()))
+        {
+            match days.last_mut() {
+                Some((day, events)) if day == &event.day() => events.push(event),
+                _ => days.push((event.day(), vec![event])),
+            }
+        }
+        let is_empty = days.is_empty();
+
+        v_flex()
+            .paddings(Edges::all(px(5.)))
+            .children(days.into_iter().map(|(day, events)| {
+                v_flex()
+                    .child(Label::new(day).font_semibold())
+                    .children(events.into_iter().map(|event| {
+                        let target_url = event
+                            .project_id
+                            .and_then(|project_id| self.project_urls.get(&project_id))
+                            .map(|project_url| event.target_url(project_url));
+
+                        h_flex()
+                            .id(ElementId::Integer(event.id))
+                            .gap_1()
+                            .child(Label::new(short_datetime(&event.created_at)))
+                            .child(Label::new(event.author.name.clone()).font_semibold())
+                            .child(
+                                Button::new("target-link")
+                                    .link()
+                                    .label(event.summary().truncated(254))
+                                    .on_click(move |_, _, _| {
+                                        if let Some(target_url) = target_url.clone() {
+                                            open::that(target_url).unwrap_or_log();
+                                        }
+                                    }),
+                            )
+                    }))
+            }))
+            .when(is_empty, |element| element.child("n/a".to_string()))
+            .when(!self.exhausted, |element| {
+                element.child(
+                    Button::new("load-more")
+                        .link()
+                        .label(if self.loading {
+                            "Loading..."
+                        } else {
+                            "Load more"
+                        })
+                        .on_click(cx.listener(|this, _, window, cx| this.load_more(window, cx))),
+                )
+            })
+    }
+}
+
+async fn get_events(
+    gitlab: &GitlabClient,
+    project_ids: &[u64],
+    page: u64,
+    project_urls: &mut HashMap<u64, String>,
+) -> Result<Vec<Event>, GitlabError> {
+    let mut events: Vec<Event> = MyEvents::new(page).query_async(gitlab).await?;
+    for project_id in project_ids {
+        let project_events: Vec<Event> = ProjectEvents::new(*project_id, page)
+            .query_async(gitlab)
+            .await?;
+        events.extend(project_events);
+    }
+
+    let project_ids = events
+        .iter()
+        .filter_map(|event| event.project_id)
+        .collect::<HashSet<u64>>();
+    for project_id in project_ids {
+        if project_urls.contains_key(&project_id) {
+            continue;
+        }
+
+        let project: crate::state::gitlab::project::Project = projects::Project::builder()
+            .project(project_id)
+            .build()?
+            .query_async(gitlab)
+            .await?;
+        project_urls.insert(project_id, project.web_url);
+    }
+
+    Ok(events)
+}
@@ -0,0 +1,54 @@
+use gpui::*;
+
+use crate::{
+    api::snapshot::{InstanceSnapshot, Snapshot},
+    dashboard::Dashboard,
+};
+
+impl Dashboard {
+    pub fn api_snapshot(&self, cx: &App) -> Snapshot {
+        let mut snapshot = Snapshot::default();
+
+        for projects in &self.projects.read(cx).0 {
+            let projects = projects.read(cx);
+            instance(&mut snapshot, projects.host())
+                .projects
+                .extend(projects.snapshots(cx));
+        }
+        for issues in &self.issues.read(cx).0 {
+            let issues = issues.read(cx);
+            instance(&mut snapshot, issues.host())
+                .issues
+                .extend(issues.snapshots(cx));
+        }
+        for todos in &self.todos.read(cx).0 {
+            let todos = todos.read(cx);
+            instance(&mut snapshot, todos.host())
+                .todos
+                .extend(todos.snapshots(cx));
+        }
+
+        snapshot
+    }
+}
+
+fn instance<'a>(snapshot: &'a mut Snapshot, host: &str) -> &'a mut InstanceSnapshot {
+    let index = match snapshot
+        .instances
+        .iter()
+        .position(|instance| instance.host == host)
+    {
+        Some(index) => index,
+        None => {
+            snapshot.instances.push(InstanceSnapshot {
+                host: host.to_string(),
+                projects: vec![],
+                issues: vec![],
+                todos: vec![],
+            });
+            snapshot.instances.len() - 1
+        }
+    };
+
+    &mut snapshot.instances[index]
+}
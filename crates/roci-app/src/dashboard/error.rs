@@ -3,7 +3,8 @@ use gitlab::{
         projects::{
             issues::IssuesBuilderError,
             merge_requests::pipelines::MergeRequestPipelinesBuilderError,
-            merge_requests::MergeRequestsBuilderError, pipelines::PipelinesBuilderError,
+            merge_requests::MergeRequestsBuilderError,
+            pipelines::{PipelineBuilderError, PipelinesBuilderError},
             ProjectBuilderError,
         },
         users::CurrentUserBuilderError,
@@ -27,6 +28,8 @@ pub enum GitlabError {
     Project(#[from] ProjectBuilderError),
     #[error("Pipelines error: {0}")]
     Pipelines(#[from] PipelinesBuilderError),
+    #[error("Pipeline error: {0}")]
+    Pipeline(#[from] PipelineBuilderError),
     #[error("Current user error: {0}")]
     User(#[from] CurrentUserBuilderError),
     #[error("Merge requests error: {0}")]
@@ -35,4 +38,10 @@ pub enum GitlabError {
     Issues(#[from] IssuesBuilderError),
     #[error("Merge requests pipeline error: {0}")]
     MergeRequestPipelines(#[from] MergeRequestPipelinesBuilderError),
+    #[error("Merge request has no diff refs yet")]
+    MissingDiffRefs,
+    #[error("File {0} is not changed by the merge request")]
+    FileNotChanged(String),
+    #[error("GraphQL error: {0}")]
+    GraphQl(String),
 }
@@ -0,0 +1,24 @@
+use gitlab::api::{Endpoint, Pageable, QueryParams};
+
+/// Opened merge requests of current user, on every project
+#[derive(Debug, Default)]
+pub struct MyOpenedMergeRequests;
+
+impl Endpoint for MyOpenedMergeRequests {
+    fn method(&self) -> reqwest::Method {
+        reqwest::Method::GET
+    }
+
+    fn endpoint(&self) -> std::borrow::Cow<'static, str> {
+        "merge_requests".into()
+    }
+
+    fn parameters(&self) -> QueryParams<'_> {
+        let mut params = QueryParams::default();
+        params.push("scope", "created_by_me");
+        params.push("state", "opened");
+        params
+    }
+}
+
+impl Pageable for MyOpenedMergeRequests {}
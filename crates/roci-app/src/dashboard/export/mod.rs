@@ -0,0 +1,223 @@
+use std::fs;
+
+use gitlab::api::{self, AsyncQuery};
+use gpui::{prelude::FluentBuilder, *};
+use gpui_component::{
+    button::{Button, ButtonVariants},
+    label::Label,
+    notification::NotificationType,
+    ActiveTheme, WindowExt,
+};
+
+use crate::{
+    config::gitlab_::Gitlab,
+    dashboard::{error::GitlabError, export::endpoint::MyOpenedMergeRequests, Dashboard},
+    state::{
+        gitlab::{project::merge_request::MergeRequest, Gitlabs},
+        report::{Report, ReportFormat, ReportItem},
+        AppState,
+    },
+    utils::project_path,
+};
+
+mod endpoint;
+
+/// My opened merge requests listed at most per instance
+pub const REPORT_MERGE_REQUESTS_COUNT: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportTarget {
+    Clipboard,
+    File,
+}
+
+impl Dashboard {
+    pub fn show_export_dialog(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let view = cx.entity().clone();
+
+        window.open_dialog(cx, move |dialog, _window, cx| {
+            dialog
+                .title("Export report")
+                .child(
+                    Label::new(
+                        "Failing pipelines, my open merge requests, assigned issues and todos \
+                        count. Templates are read from `~/.config/roci/templates/report.md` and \
+                        `report.html` when present",
+                    )
+                    .text_color(cx.theme().muted_foreground),
+                )
+                .footer({
+                    let view = view.clone();
+
+                    move |_, _, _, _cx| {
+                        [
+                            (
+                                "Copy Markdown",
+                                ReportFormat::Markdown,
+                                ReportTarget::Clipboard,
+                            ),
+                            ("Save Markdown", ReportFormat::Markdown, ReportTarget::File),
+                            ("Copy HTML", ReportFormat::Html, ReportTarget::Clipboard),
+                            ("Save HTML", ReportFormat::Html, ReportTarget::File),
+                        ]
+                        .into_iter()
+                        .map(|(label, format, target)| {
+                            let view = view.clone();
+
+                            Button::new(label)
+                                .label(label)
+                                .when(target == ReportTarget::Clipboard, |button| button.primary())
+                                .on_click(move |_, window, cx| {
+                                    window.close_dialog(cx);
+                                    view.update(cx, |this, cx| {
+                                        this.export_report(window, cx, format, target)
+                                    });
+                                })
+                        })
+                        .collect()
+                    }
+                })
+        });
+    }
+
+    fn export_report(
+        &mut self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+        format: ReportFormat,
+        target: ReportTarget,
+    ) {
+        let gitlabs = AppState::global(cx).gitlabs();
+        let configs = AppState::global(cx).config().gitlabs.clone();
+
+        cx.spawn_in(window, async move |this, cx| {
+            let merge_requests = get_merge_requests(&gitlabs, &configs).await;
+            let _ = this.update_in(cx, |this, window, cx| {
+                let rendered =
+                    merge_requests
+                        .map_err(|error| error.to_string())
+                        .and_then(|merge_requests| {
+                            let template = format.template().map_err(|error| error.to_string())?;
+                            Ok(this.report(merge_requests, cx).render(format, &template))
+                        });
+
+                match (rendered, target) {
+                    (Ok(rendered), ReportTarget::Clipboard) => {
+                        cx.write_to_clipboard(ClipboardItem::new_string(rendered));
+                        window.push_notification(SharedString::new("Report copied"), cx);
+                    }
+                    (Ok(rendered), ReportTarget::File) => {
+                        this.save_report(window, cx, format, rendered);
+                    }
+                    (Err(error), _) => window.push_notification(
+                        (
+                            NotificationType::Error,
+                            SharedString::new(format!("Can't export report: {}", error)),
+                        ),
+                        cx,
+                    ),
+                }
+            });
+        })
+        .detach();
+    }
+
+    /// Current dashboard state with given merge requests
+    fn report(&self, merge_requests: Vec<ReportItem>, cx: &App) -> Report {
+        let mut report = Report {
+            date: chrono::Local::now().format("%Y-%m-%d %H:%M").to_string(),
+            merge_requests,
+            ..Default::default()
+        };
+
+        for projects in &self.projects.read(cx).0 {
+            report.failing_pipelines.extend(
+                projects
+                    .read(cx)
+                    .failing_pipelines(cx)
+                    .into_iter()
+                    .map(|item| ReportItem::new(item.label, item.url, None)),
+            );
+        }
+        for issues in &self.issues.read(cx).0 {
+            report.issues.extend(issues.read(cx).report_items(cx));
+        }
+        for todos in &self.todos.read(cx).0 {
+            let (todos, review_requests) = todos.read(cx).tray_items(cx);
+            report.todos += todos.len();
+            report.review_requests += review_requests.len();
+        }
+
+        report
+    }
+
+    fn save_report(
+        &mut self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+        format: ReportFormat,
+        rendered: String,
+    ) {
+        let directory = homedir::my_home().ok().flatten().unwrap_or_default();
+        let path = cx.prompt_for_new_path(
+            &directory,
+            Some(&format!("roci-report.{}", format.extension())),
+        );
+
+        cx.spawn_in(window, async move |_this, cx| {
+            let Ok(Ok(Some(path))) = path.await else {
+                return;
+            };
+            let notification = match fs::write(&path, rendered) {
+                Ok(()) => (
+                    NotificationType::Success,
+                    SharedString::new(format!("Report saved to {}", path.display())),
+                ),
+                Err(error) => (
+                    NotificationType::Error,
+                    SharedString::new(format!("Can't save report: {}", error)),
+                ),
+            };
+            let _ = cx.update(|window, cx| window.push_notification(notification, cx));
+        })
+        .detach();
+    }
+}
+
+/// My opened merge requests of every instance with their status
+async fn get_merge_requests(
+    gitlabs: &Gitlabs,
+    configs: &[Gitlab],
+) -> Result<Vec<ReportItem>, GitlabError> {
+    let mut items = vec![];
+    for config in configs {
+        let gitlab = gitlabs.get(config).await?;
+        let merge_requests: Vec<MergeRequest> = api::paged(
+            MyOpenedMergeRequests,
+            api::Pagination::Limit(REPORT_MERGE_REQUESTS_COUNT),
+        )
+        .query_async(&gitlab)
+        .await?;
+
+        items.extend(merge_requests.into_iter().map(|merge_request| {
+            let status = if merge_request.draft {
+                "draft".to_string()
+            } else {
+                merge_request.detailed_merge_status.replace('_', " ")
+            };
+
+            ReportItem::new(
+                format!(
+                    "{}!{} {}",
+                    project_path(&merge_request.web_url),
+                    merge_request.iid,
+                    merge_request.title
+                ),
+                merge_request.web_url.clone(),
+                Some(status),
+            )
+        }));
+    }
+
+    Ok(items)
+}
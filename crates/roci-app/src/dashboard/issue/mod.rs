@@ -1,17 +1,28 @@
-use gitlab::{api::AsyncQuery, AsyncGitlab};
+use gitlab::api::AsyncQuery;
 use gpui::{prelude::FluentBuilder, *};
 use gpui_component::{
     button::{Button, ButtonVariants},
     h_flex,
     label::Label,
-    v_flex, Icon, IconName, StyledExt,
+    spinner::Spinner,
+    v_flex, ActiveTheme, Icon, IconName, StyledExt,
+};
+use roci_app_components::{
+    badge::badge, error::WithButtonModalError, with_button_error, LoadState,
 };
-use roci_app_components::{error::WithButtonModalError, with_button_error, LoadState};
 use tracing_unwrap::ResultExt;
 
 use crate::{
-    dashboard::{error::GitlabError, issue::endpoint::MyIssues},
-    state::{gitlab::issue::Issue, AppState},
+    api::snapshot::IssueSnapshot,
+    config::layout::ALL_INSTANCES,
+    dashboard::{error::GitlabError, issue::endpoint::MyIssues, refresh::wait_refresh},
+    state::{
+        gitlab::{issue::Issue, GitlabClient},
+        navigation::{NavItem, NavKey, NavKind},
+        refresh::RefreshKey,
+        report::ReportItem,
+        AppState,
+    },
 };
 
 mod endpoint;
@@ -32,17 +43,31 @@ impl Issues {
         let gitlabs = AppState::global(cx).gitlabs();
 
         let config_ = config.clone();
+        let refresh_key = RefreshKey::Issues(config.host.clone());
         cx.spawn_in(window, async move |issues, cx| {
             match gitlabs.get(&config_).await {
                 Ok(gitlab) => match get_issues(gitlab).await {
                     Ok(issues_) => {
                         let _ = issues.update_in(cx, |issues, _window, cx| {
+                            AppState::global_mut(cx).refreshes_mut().loaded(
+                                &refresh_key,
+                                issues_
+                                    .iter()
+                                    .map(|issue| format!("{}:{}", issue.id, issue.updated_at))
+                                    .collect::<Vec<String>>()
+                                    .join(","),
+                                false,
+                            );
                             let issues_ = cx.new(|_cx| IssuesInner(issues_));
                             issues.inner = cx.new(|_cx| LoadState::Ready(issues_));
+                            cx.notify();
                         });
                     }
                     Err(error) => {
                         let _ = issues.update_in(cx, |issues, _window, cx| {
+                            AppState::global_mut(cx)
+                                .refreshes_mut()
+                                .failed(&refresh_key);
                             issues.inner = cx.new(|cx| {
                                 with_button_error!(
                                     cx,
@@ -51,11 +76,15 @@ impl Issues {
                                     error.into()
                                 )
                             });
+                            cx.notify();
                         });
                     }
                 },
                 Err(error) => {
                     let _ = issues.update_in(cx, |issues, _window, cx| {
+                        AppState::global_mut(cx)
+                            .refreshes_mut()
+                            .failed(&refresh_key);
                         issues.inner = cx.new(|cx| {
                             with_button_error!(
                                 cx,
@@ -64,6 +93,7 @@ impl Issues {
                                 error.into()
                             )
                         });
+                        cx.notify();
                     });
                 }
             };
@@ -71,9 +101,9 @@ impl Issues {
         .detach();
 
         let config_ = config.clone();
-        let interval = AppState::global(cx).config().refresh_every.duration();
+        let refresh_key = RefreshKey::Issues(config.host.clone());
         cx.spawn_in(window, async move |issues, cx| {
-            Timer::after(interval).await;
+            wait_refresh(cx, &refresh_key).await;
             let _ = issues.update_in(cx, |issues, window, cx| {
                 *issues = Self::new(window, cx, config_.clone());
                 cx.notify();
@@ -86,6 +116,54 @@ impl Issues {
             host: host_,
         }
     }
+
+    pub fn nav_items(&self, cx: &App) -> Vec<NavItem> {
+        let LoadState::Ready(issues) = self.inner.read(cx) else {
+            return vec![];
+        };
+
+        issues
+            .read(cx)
+            .0
+            .iter()
+            .map(|issue| NavItem {
+                key: NavKey::new(NavKind::Issue, issue.web_url.clone()),
+                host: self.host.clone(),
+                id: issue.id,
+            })
+            .collect()
+    }
+
+    pub fn snapshots(&self, cx: &App) -> Vec<IssueSnapshot> {
+        let LoadState::Ready(issues) = self.inner.read(cx) else {
+            return vec![];
+        };
+
+        issues.read(cx).0.iter().map(IssueSnapshot::from).collect()
+    }
+
+    pub fn host(&self) -> &str {
+        &self.host
+    }
+
+    pub fn report_items(&self, cx: &App) -> Vec<ReportItem> {
+        let LoadState::Ready(issues) = self.inner.read(cx) else {
+            return vec![];
+        };
+
+        issues
+            .read(cx)
+            .0
+            .iter()
+            .map(|issue| {
+                ReportItem::new(
+                    format!("#{} {}", issue.iid, issue.title),
+                    issue.web_url.clone(),
+                    Some(self.host.clone()),
+                )
+            })
+            .collect()
+    }
 }
 
 impl Render for Issues {
@@ -105,26 +183,11 @@ impl Render for Issues {
 pub struct IssuesInner(Vec<Issue>);
 
 impl Render for IssuesInner {
-    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         v_flex()
             .paddings(Edges::all(px(5.)))
             .when(!self.0.is_empty(), |element| {
-                element.children(self.0.iter().map(|issue| {
-                    let web_url = issue.web_url.clone();
-
-                    h_flex()
-                        .id(ElementId::Integer(issue.id))
-                        .child(
-                            Button::new("id-link")
-                                .link()
-                                .label(format!("#{}", issue.iid))
-                                .on_click(move |_, _, _| {
-                                    open::that(web_url.clone()).unwrap_or_log();
-                                }),
-                        )
-                        .child(" ".to_string())
-                        .child(issue.title.clone())
-                }))
+                element.children(self.0.iter().map(|issue| render_issue(issue, None, cx)))
             })
             .when(self.0.is_empty(), |element| {
                 element.child("n/a".to_string())
@@ -132,8 +195,119 @@ impl Render for IssuesInner {
     }
 }
 
+/// Issues of every instance, most recently updated first
+pub struct UnifiedIssues {
+    sources: Vec<Entity<Issues>>,
+}
+
+impl UnifiedIssues {
+    pub fn new(cx: &mut Context<Self>, sources: Vec<Entity<Issues>>) -> Self {
+        for source in &sources {
+            cx.observe(source, |_this, _source, cx| cx.notify())
+                .detach();
+        }
+
+        Self { sources }
+    }
+
+    fn issues(&self, cx: &App) -> Vec<(String, Issue)> {
+        let mut issues = vec![];
+        for source in &self.sources {
+            let source = source.read(cx);
+            if let LoadState::Ready(inner) = source.inner.read(cx) {
+                issues.extend(
+                    inner
+                        .read(cx)
+                        .0
+                        .iter()
+                        .map(|issue| (source.host.clone(), issue.clone())),
+                );
+            }
+        }
+        issues.sort_by(|(_, a), (_, b)| b.updated_at.cmp(&a.updated_at));
+
+        issues
+    }
+
+    pub fn nav_items(&self, cx: &App) -> Vec<NavItem> {
+        self.issues(cx)
+            .into_iter()
+            .map(|(host, issue)| NavItem {
+                key: NavKey::new(NavKind::Issue, issue.web_url),
+                host,
+                id: issue.id,
+            })
+            .collect()
+    }
+}
+
+impl Render for UnifiedIssues {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let issues = self.issues(cx);
+        let mut loading = false;
+        let mut errors = vec![];
+        for source in &self.sources {
+            match source.read(cx).inner.read(cx) {
+                LoadState::Loading => loading = true,
+                LoadState::Ready(_) => {}
+                LoadState::Error(error) => errors.push(error.clone()),
+            }
+        }
+
+        div()
+            .child(
+                h_flex()
+                    .child(Icon::new(IconName::ArrowRight))
+                    .child(Label::new(format!("{} issues", ALL_INSTANCES)).text_xl()),
+            )
+            .when(loading, |element| element.child(Spinner::new()))
+            .children(errors)
+            .child(
+                v_flex()
+                    .paddings(Edges::all(px(5.)))
+                    .when(issues.is_empty() && !loading, |element| {
+                        element.child("n/a".to_string())
+                    })
+                    .children(
+                        issues
+                            .iter()
+                            .map(|(host, issue)| render_issue(issue, Some(host), cx)),
+                    ),
+            )
+    }
+}
+
+fn render_issue(issue: &Issue, host: Option<&str>, cx: &App) -> impl IntoElement {
+    let web_url = issue.web_url.clone();
+    let selected =
+        AppState::global(cx).is_selected(&NavKey::new(NavKind::Issue, issue.web_url.clone()));
+    let id = match host {
+        Some(host) => ElementId::NamedInteger(SharedString::new(host), issue.id),
+        None => ElementId::Integer(issue.id),
+    };
+
+    h_flex()
+        .id(id)
+        .when(selected, |element| element.bg(cx.theme().accent))
+        .when_some(host, |element, host| {
+            element
+                .child(badge(host.to_string(), cx))
+                .child(" ".to_string())
+        })
+        .child(
+            Button::new("id-link")
+                .link()
+                .label(format!("#{}", issue.iid))
+                .on_click(move |_, _, _| {
+                    open::that(web_url.clone()).unwrap_or_log();
+                }),
+        )
+        .child(" ".to_string())
+        .child(issue.title.clone())
+}
+
 async fn get_issues(
-    gitlab: AsyncGitlab,
+    gitlab: GitlabClient,
 ) -> Result<Vec<crate::state::gitlab::issue::Issue>, GitlabError> {
     Ok(MyIssues::new("assigned_to_me", Some("opened"))
         .query_async(&gitlab)
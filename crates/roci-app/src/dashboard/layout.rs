@@ -0,0 +1,354 @@
+use gpui::{prelude::FluentBuilder, *};
+use gpui_component::{
+    button::{Button, ButtonVariants},
+    h_flex,
+    label::Label,
+    menu::{DropdownMenu as _, PopupMenuItem},
+    notification::NotificationType,
+    v_flex, ActiveTheme, Icon, IconName, Sizable, WindowExt,
+};
+
+use crate::{
+    config::{
+        layout::{Layout, Section, SectionKind, ALL_INSTANCES},
+        status::StatusPreset,
+    },
+    dashboard::Dashboard,
+    state::AppState,
+};
+
+pub const COMPACT_CONTAINER_PADDING: Pixels = px(5.);
+pub const COMPACT_SECTION_MIN_WIDTH: Pixels = px(480.);
+
+/// Section being dragged, also used as drag preview
+#[derive(Clone)]
+pub struct DraggedSection(Section);
+
+impl Render for DraggedSection {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .px_2()
+            .rounded_md()
+            .border_1()
+            .border_color(cx.theme().border)
+            .bg(cx.theme().background)
+            .child(self.0.title())
+    }
+}
+
+impl Dashboard {
+    pub fn host_index(&self, host: &str, cx: &App) -> Option<usize> {
+        AppState::global(cx)
+            .config()
+            .gitlabs
+            .iter()
+            .position(|gitlab| gitlab.host == host)
+    }
+
+    /// All sections (including hidden ones) in user order
+    pub fn sections(&self, cx: &App) -> Vec<Section> {
+        let config = AppState::global(cx).config();
+        let hosts = if config.layout.unified {
+            vec![ALL_INSTANCES.to_string()]
+        } else {
+            config
+                .gitlabs
+                .iter()
+                .map(|gitlab| gitlab.host.clone())
+                .collect::<Vec<String>>()
+        };
+
+        config.layout.sections(&hosts)
+    }
+
+    fn section_view(&self, section: &Section, cx: &App) -> Option<AnyView> {
+        if section.host == ALL_INSTANCES {
+            return Some(match section.kind {
+                SectionKind::Todos => self.unified_todos.clone().into(),
+                SectionKind::Projects => self.unified_projects.clone().into(),
+                SectionKind::Issues => self.unified_issues.clone().into(),
+                SectionKind::Activity => self.activities.clone().into(),
+                SectionKind::StaleMergeRequests => self.unified_stale_merge_requests.clone().into(),
+            });
+        }
+        let index = self.host_index(&section.host, cx)?;
+
+        Some(match section.kind {
+            SectionKind::Todos => self.todos.read(cx).0.get(index)?.clone().into(),
+            SectionKind::Projects => self.projects.read(cx).0.get(index)?.clone().into(),
+            SectionKind::Issues => self.issues.read(cx).0.get(index)?.clone().into(),
+            SectionKind::Activity => self.activities.read(cx).0.get(index)?.clone().into(),
+            SectionKind::StaleMergeRequests => self
+                .stale_merge_requests
+                .read(cx)
+                .0
+                .get(index)?
+                .clone()
+                .into(),
+        })
+    }
+
+    fn update_layout(
+        &mut self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+        update: impl FnOnce(&mut Layout),
+    ) {
+        let mut sections = self.sections(cx);
+        let state = AppState::global_mut(cx);
+        let mut new_config = state.config().clone();
+        // Keep customization of sections not currently displayed (unified or not)
+        sections.extend(
+            new_config
+                .layout
+                .sections
+                .iter()
+                .filter(|section| !sections.iter().any(|s| s.is(&section.host, section.kind)))
+                .cloned()
+                .collect::<Vec<Section>>(),
+        );
+        new_config.layout.sections = sections;
+        update(&mut new_config.layout);
+
+        if let Err(error) = state.replace_config(new_config) {
+            window.push_notification(
+                (
+                    NotificationType::Error,
+                    SharedString::new(format!("Failed to write config on disk: {:#}", error)),
+                ),
+                cx,
+            );
+        }
+
+        cx.notify();
+    }
+
+    fn toggle_status_preset(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let state = AppState::global_mut(cx);
+        let mut new_config = state.config().clone();
+        new_config.status_styles.preset = match new_config.status_styles.preset {
+            StatusPreset::Default => StatusPreset::Colorblind,
+            StatusPreset::Colorblind => StatusPreset::Default,
+        };
+
+        if let Err(error) = state.replace_config(new_config) {
+            window.push_notification(
+                (
+                    NotificationType::Error,
+                    SharedString::new(format!("Failed to write config on disk: {:#}", error)),
+                ),
+                cx,
+            );
+        }
+
+        window.refresh();
+    }
+
+    fn update_section(
+        &mut self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+        target: &Section,
+        update: impl FnOnce(&mut Section),
+    ) {
+        self.update_layout(window, cx, |layout| {
+            if let Some(section) = layout
+                .sections
+                .iter_mut()
+                .find(|section| section.is(&target.host, target.kind))
+            {
+                update(section);
+            }
+        });
+    }
+
+    fn move_section(
+        &mut self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+        dragged: &Section,
+        target: &Section,
+    ) {
+        self.update_layout(window, cx, |layout| {
+            let position = |section: &Section| {
+                layout
+                    .sections
+                    .iter()
+                    .position(|section_| section_.is(&section.host, section.kind))
+            };
+            let (Some(from), Some(to)) = (position(dragged), position(target)) else {
+                return;
+            };
+
+            let section = layout.sections.remove(from);
+            layout.sections.insert(to, section);
+        });
+    }
+
+    pub fn render_layout_menu(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let view = cx.entity();
+
+        Button::new("title-bar-layout")
+            .outline()
+            .label("Layout")
+            .ghost()
+            .dropdown_menu(move |this, window, cx| {
+                let layout = &AppState::global(cx).config().layout;
+                let compact_label = if layout.compact {
+                    "Disable compact density"
+                } else {
+                    "Enable compact density"
+                };
+                let unified_label = if layout.unified {
+                    "Split sections per instance"
+                } else {
+                    "Merge instances sections"
+                };
+                let status_label = match AppState::global(cx).config().status_styles.preset {
+                    StatusPreset::Default => "Use colorblind friendly statuses",
+                    StatusPreset::Colorblind => "Use default statuses",
+                };
+                let hidden = view
+                    .read(cx)
+                    .sections(cx)
+                    .into_iter()
+                    .filter(|section| section.hidden)
+                    .collect::<Vec<Section>>();
+
+                let mut menu = this
+                    .item(
+                        PopupMenuItem::new(compact_label).on_click(window.listener_for(
+                            &view,
+                            |this, _event, window, cx| {
+                                this.update_layout(window, cx, |layout| {
+                                    layout.compact = !layout.compact
+                                });
+                            },
+                        )),
+                    )
+                    .item(
+                        PopupMenuItem::new(unified_label).on_click(window.listener_for(
+                            &view,
+                            |this, _event, window, cx| {
+                                this.update_layout(window, cx, |layout| {
+                                    layout.unified = !layout.unified
+                                });
+                            },
+                        )),
+                    )
+                    .item(
+                        PopupMenuItem::new(status_label).on_click(window.listener_for(
+                            &view,
+                            |this, _event, window, cx| {
+                                this.toggle_status_preset(window, cx);
+                            },
+                        )),
+                    )
+                    .item(
+                        PopupMenuItem::new("Reset layout").on_click(window.listener_for(
+                            &view,
+                            |this, _event, window, cx| {
+                                this.update_layout(window, cx, |layout| {
+                                    *layout = Layout::default()
+                                });
+                            },
+                        )),
+                    );
+
+                for section in hidden {
+                    menu = menu.item(
+                        PopupMenuItem::new(format!("Show {}", section.title())).on_click(
+                            window.listener_for(&view, move |this, _event, window, cx| {
+                                this.update_section(window, cx, &section, |section| {
+                                    section.hidden = false
+                                });
+                            }),
+                        ),
+                    );
+                }
+
+                menu
+            })
+    }
+
+    pub fn render_section(
+        &self,
+        section: Section,
+        cx: &mut Context<Self>,
+    ) -> Option<impl IntoElement> {
+        let view = self.section_view(&section, cx)?;
+        let compact = AppState::global(cx).config().layout.compact;
+        let collapse_icon = if section.collapsed {
+            IconName::ChevronRight
+        } else {
+            IconName::ChevronDown
+        };
+
+        Some(
+            h_flex()
+                .id(SharedString::new(section.title()))
+                .items_start()
+                .gap_1()
+                .when(compact, |element| {
+                    element.flex_1().min_w(COMPACT_SECTION_MIN_WIDTH)
+                })
+                .drag_over::<DraggedSection>(|style, _, _, cx| style.bg(cx.theme().accent))
+                .on_drop(cx.listener({
+                    let target = section.clone();
+
+                    move |this, dragged: &DraggedSection, window, cx| {
+                        this.move_section(window, cx, &dragged.0, &target);
+                    }
+                }))
+                .child(
+                    v_flex()
+                        .child(
+                            div()
+                                .id("drag-section")
+                                .cursor_grab()
+                                .child(Icon::new(IconName::Menu))
+                                .on_drag(
+                                    DraggedSection(section.clone()),
+                                    |dragged, _offset, _window, cx| cx.new(|_cx| dragged.clone()),
+                                ),
+                        )
+                        .child(
+                            Button::new("collapse-section")
+                                .icon(collapse_icon)
+                                .ghost()
+                                .small()
+                                .on_click(cx.listener({
+                                    let section = section.clone();
+
+                                    move |this, _, window, cx| {
+                                        this.update_section(window, cx, &section, |section| {
+                                            section.collapsed = !section.collapsed
+                                        });
+                                    }
+                                })),
+                        )
+                        .child(
+                            Button::new("hide-section")
+                                .icon(IconName::EyeOff)
+                                .ghost()
+                                .small()
+                                .tooltip("Hide (restore it from Layout menu)")
+                                .on_click(cx.listener({
+                                    let section = section.clone();
+
+                                    move |this, _, window, cx| {
+                                        this.update_section(window, cx, &section, |section| {
+                                            section.hidden = true
+                                        });
+                                    }
+                                })),
+                        ),
+                )
+                .child(if section.collapsed {
+                    Label::new(section.title()).text_xl().into_any_element()
+                } else {
+                    div().flex_1().child(view).into_any_element()
+                }),
+        )
+    }
+}
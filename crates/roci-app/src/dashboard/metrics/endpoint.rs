@@ -0,0 +1,85 @@
+use derive_more::Constructor;
+use gitlab::api::{Endpoint, Pageable, QueryParams};
+
+#[derive(Debug, Constructor)]
+pub struct MergedMergeRequests {
+    project_id: u64,
+    /// ISO 8601 date
+    updated_after: String,
+}
+
+impl Endpoint for MergedMergeRequests {
+    fn method(&self) -> reqwest::Method {
+        reqwest::Method::GET
+    }
+
+    fn endpoint(&self) -> std::borrow::Cow<'static, str> {
+        format!("projects/{}/merge_requests", self.project_id).into()
+    }
+
+    fn parameters(&self) -> QueryParams<'_> {
+        let mut params = QueryParams::default();
+        params.push("state", "merged");
+        params.push("updated_after", self.updated_after.as_str());
+        params.push("order_by", "updated_at");
+        params.push("sort", "desc");
+        params
+    }
+}
+
+impl Pageable for MergedMergeRequests {}
+
+#[derive(Debug, Constructor)]
+pub struct UpdatedDeployments {
+    project_id: u64,
+    /// ISO 8601 date
+    updated_after: String,
+}
+
+impl Endpoint for UpdatedDeployments {
+    fn method(&self) -> reqwest::Method {
+        reqwest::Method::GET
+    }
+
+    fn endpoint(&self) -> std::borrow::Cow<'static, str> {
+        format!("projects/{}/deployments", self.project_id).into()
+    }
+
+    fn parameters(&self) -> QueryParams<'_> {
+        let mut params = QueryParams::default();
+        // Required by `updated_after`
+        params.push("order_by", "updated_at");
+        params.push("sort", "desc");
+        params.push("updated_after", self.updated_after.as_str());
+        params
+    }
+}
+
+impl Pageable for UpdatedDeployments {}
+
+#[derive(Debug, Constructor)]
+pub struct UpdatedPipelines {
+    project_id: u64,
+    ref_: String,
+    /// ISO 8601 date
+    updated_after: String,
+}
+
+impl Endpoint for UpdatedPipelines {
+    fn method(&self) -> reqwest::Method {
+        reqwest::Method::GET
+    }
+
+    fn endpoint(&self) -> std::borrow::Cow<'static, str> {
+        format!("projects/{}/pipelines", self.project_id).into()
+    }
+
+    fn parameters(&self) -> QueryParams<'_> {
+        let mut params = QueryParams::default();
+        params.push("ref", self.ref_.as_str());
+        params.push("updated_after", self.updated_after.as_str());
+        params
+    }
+}
+
+impl Pageable for UpdatedPipelines {}
@@ -0,0 +1,400 @@
+use std::fs;
+
+use chrono::{TimeDelta, Utc};
+use futures::{future::join_all, join};
+use gitlab::api::{self, AsyncQuery};
+use gpui::{prelude::FluentBuilder, *};
+use gpui_component::{
+    button::{Button, ButtonVariants},
+    h_flex,
+    label::Label,
+    notification::NotificationType,
+    v_flex, ActiveTheme, StyledExt, WindowExt,
+};
+use roci_app_components::{error::WithButtonModalError, LoadState};
+
+use crate::{
+    config::gitlab_::Gitlab,
+    dashboard::{
+        error::GitlabError,
+        metrics::endpoint::{MergedMergeRequests, UpdatedDeployments, UpdatedPipelines},
+        project::get_project,
+        Dashboard,
+    },
+    state::{
+        gitlab::{
+            project::{deployment::Deployment, merge_request::MergeRequest, pipeline::Pipeline},
+            GitlabClient, Gitlabs,
+        },
+        metrics::{DoraMetrics, ProjectActivity},
+        AppState,
+    },
+    utils::duration,
+};
+
+mod endpoint;
+
+/// Selectable periods in days, data of the longest one is fetched once
+pub const METRICS_WINDOWS: [u32; 3] = [7, 30, 90];
+pub const DEFAULT_METRICS_WINDOW: u32 = 30;
+/// Deployments, merge requests and pipelines fetched at most per project
+pub const METRICS_ITEMS_COUNT: usize = 1000;
+
+const PROJECT_COLUMN_WIDTH: Pixels = px(260.);
+const COLUMN_WIDTH: Pixels = px(140.);
+
+pub struct Metrics {
+    inner: Entity<LoadState<MetricsInner, WithButtonModalError<GitlabError>>>,
+}
+
+impl Metrics {
+    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let gitlabs = AppState::global(cx).gitlabs();
+        let configs = AppState::global(cx).config().gitlabs.clone();
+
+        cx.spawn_in(window, async move |this, cx| {
+            let (projects, missing) = get_activities(&gitlabs, &configs).await;
+            let _ = this.update_in(cx, |this, _window, cx| {
+                let inner = cx.new(|_cx| MetricsInner::new(projects, missing));
+                this.inner = cx.new(|_cx| LoadState::Ready(inner));
+                cx.notify();
+            });
+        })
+        .detach();
+
+        Self {
+            inner: cx.new(|_cx| LoadState::Loading),
+        }
+    }
+}
+
+impl Render for Metrics {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        div().child(self.inner.clone())
+    }
+}
+
+pub struct MetricsInner {
+    days: u32,
+    projects: Vec<ProjectActivity>,
+    /// Projects that couldn't be loaded, left out of metrics
+    missing: Vec<String>,
+}
+
+impl MetricsInner {
+    fn new(projects: Vec<ProjectActivity>, missing: Vec<String>) -> Self {
+        Self {
+            days: DEFAULT_METRICS_WINDOW,
+            projects,
+            missing,
+        }
+    }
+
+    /// All projects first, then each project
+    fn rows(&self) -> Vec<(String, DoraMetrics)> {
+        let mut rows = vec![(
+            "All projects".to_string(),
+            DoraMetrics::of_projects(self.days, &self.projects),
+        )];
+        rows.extend(self.projects.iter().map(|project| {
+            (
+                format!("{} ({})", project.name, project.host),
+                DoraMetrics::of_projects(self.days, std::slice::from_ref(project)),
+            )
+        }));
+
+        rows
+    }
+
+    fn csv(&self) -> String {
+        let mut lines = vec![
+            "project,days,deployments,deployments_per_day,failed_deployments,\
+            change_failure_rate,time_to_merge_seconds,time_to_deploy_seconds,\
+            lead_time_seconds,time_to_restore_seconds"
+                .to_string(),
+        ];
+        lines.extend(self.rows().into_iter().map(|(name, metrics)| {
+            let optional = |value: Option<String>| value.unwrap_or_default();
+
+            format!(
+                "{},{},{},{:.3},{},{},{},{},{},{}",
+                csv_field(&name),
+                metrics.days,
+                metrics.deployments,
+                metrics.deployment_frequency(),
+                metrics.failed_deployments,
+                optional(
+                    metrics
+                        .change_failure_rate()
+                        .map(|rate| format!("{:.3}", rate))
+                ),
+                optional(metrics.time_to_merge.map(|value| value.to_string())),
+                optional(metrics.time_to_deploy.map(|value| value.to_string())),
+                optional(metrics.lead_time.map(|value| value.to_string())),
+                optional(metrics.time_to_restore.map(|value| value.to_string())),
+            )
+        }));
+
+        lines.join("\n") + "\n"
+    }
+
+    fn save_csv(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let csv = self.csv();
+        let directory = homedir::my_home().ok().flatten().unwrap_or_default();
+        let path = cx.prompt_for_new_path(&directory, Some("roci-metrics.csv"));
+
+        cx.spawn_in(window, async move |_this, cx| {
+            let Ok(Ok(Some(path))) = path.await else {
+                return;
+            };
+            let notification = match fs::write(&path, csv) {
+                Ok(()) => (
+                    NotificationType::Success,
+                    SharedString::new(format!("Metrics saved to {}", path.display())),
+                ),
+                Err(error) => (
+                    NotificationType::Error,
+                    SharedString::new(format!("Can't save metrics: {}", error)),
+                ),
+            };
+            let _ = cx.update(|window, cx| window.push_notification(notification, cx));
+        })
+        .detach();
+    }
+}
+
+impl Render for MetricsInner {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let optional = |value: Option<i64>| {
+            value
+                .map(|value| duration(value.max(0) as u64))
+                .unwrap_or_else(|| "n/a".to_string())
+        };
+
+        v_flex()
+            .gap_2()
+            .child(
+                h_flex()
+                    .gap_1()
+                    .children(METRICS_WINDOWS.iter().map(|days| {
+                        let days = *days;
+
+                        Button::new(("metrics-window", days as usize))
+                            .label(format!("{} days", days))
+                            .when(days == self.days, |button| button.primary())
+                            .on_click(cx.listener(move |this, _, _window, cx| {
+                                this.days = days;
+                                cx.notify();
+                            }))
+                    }))
+                    .child(div().flex_1())
+                    .child(
+                        Button::new("metrics-copy-csv")
+                            .label("Copy CSV")
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                cx.write_to_clipboard(ClipboardItem::new_string(this.csv()));
+                                window.push_notification(
+                                    SharedString::new("Metrics copied as CSV"),
+                                    cx,
+                                );
+                            })),
+                    )
+                    .child(Button::new("metrics-save-csv").label("Save CSV").on_click(
+                        cx.listener(|this, _, window, cx| {
+                            this.save_csv(window, cx);
+                        }),
+                    )),
+            )
+            .child(
+                Label::new(
+                    "Production deployments are the ones to environments named `prod*`, \
+                    durations are medians",
+                )
+                .text_color(cx.theme().muted_foreground),
+            )
+            .when(!self.missing.is_empty(), |element| {
+                element.child(
+                    Label::new(format!(
+                        "Not loaded (see logs): {}",
+                        self.missing.join(", ")
+                    ))
+                    .text_color(cx.theme().warning),
+                )
+            })
+            .child(
+                h_flex()
+                    .font_semibold()
+                    .child(div().w(PROJECT_COLUMN_WIDTH).child("Project"))
+                    .child(div().w(COLUMN_WIDTH).child("Deployments"))
+                    .child(div().w(COLUMN_WIDTH).child("Created → merged"))
+                    .child(div().w(COLUMN_WIDTH).child("Merged → deployed"))
+                    .child(div().w(COLUMN_WIDTH).child("Change failure rate"))
+                    .child(div().w(COLUMN_WIDTH).child("Time to restore")),
+            )
+            .children(self.rows().into_iter().map(|(name, metrics)| {
+                h_flex()
+                    .child(div().w(PROJECT_COLUMN_WIDTH).child(name))
+                    .child(div().w(COLUMN_WIDTH).child(format!(
+                        "{} ({:.2}/day)",
+                        metrics.deployments,
+                        metrics.deployment_frequency()
+                    )))
+                    .child(div().w(COLUMN_WIDTH).child(optional(metrics.time_to_merge)))
+                    .child(
+                        div()
+                            .w(COLUMN_WIDTH)
+                            .child(optional(metrics.time_to_deploy))
+                            .when_some(metrics.lead_time, |element, lead_time| {
+                                element.child(
+                                    Label::new(format!(
+                                        "{} in total",
+                                        duration(lead_time.max(0) as u64)
+                                    ))
+                                    .text_xs()
+                                    .text_color(cx.theme().muted_foreground),
+                                )
+                            }),
+                    )
+                    .child(
+                        div().w(COLUMN_WIDTH).child(
+                            metrics
+                                .change_failure_rate()
+                                .map(|rate| format!("{:.0}%", rate * 100.))
+                                .unwrap_or_else(|| "n/a".to_string()),
+                        ),
+                    )
+                    .child(
+                        div()
+                            .w(COLUMN_WIDTH)
+                            .child(optional(metrics.time_to_restore)),
+                    )
+            }))
+    }
+}
+
+impl Dashboard {
+    pub fn show_metrics_dialog(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let metrics = cx.new(|cx| Metrics::new(window, cx));
+
+        window.open_dialog(cx, move |dialog, _window, _cx| {
+            dialog
+                .title("Team metrics")
+                .width(px(1000.))
+                .child(metrics.clone())
+        });
+    }
+}
+
+/// Activity of every configured project over the longest window, with projects that couldn't
+/// be loaded (logged and left out of metrics)
+async fn get_activities(
+    gitlabs: &Gitlabs,
+    configs: &[Gitlab],
+) -> (Vec<ProjectActivity>, Vec<String>) {
+    let days = METRICS_WINDOWS.into_iter().max().unwrap_or_default();
+    let since = (Utc::now() - TimeDelta::days(days as i64)).to_rfc3339();
+
+    let mut projects = vec![];
+    let mut missing = vec![];
+    for (config, activities) in configs.iter().zip(
+        join_all(
+            configs
+                .iter()
+                .map(|config| get_instance_activities(gitlabs, config, &since)),
+        )
+        .await,
+    ) {
+        let activities = match activities {
+            Ok(activities) => activities,
+            Err(error) => {
+                tracing::warn!("Unable to load metrics on {}: {}", config.host, error);
+                missing.extend(
+                    config
+                        .project_ids
+                        .iter()
+                        .map(|project_id| format!("#{} ({})", project_id, config.host)),
+                );
+                continue;
+            }
+        };
+        for (project_id, activity) in config.project_ids.iter().zip(activities) {
+            match activity {
+                Ok(activity) => projects.push(activity),
+                Err(error) => {
+                    tracing::warn!(
+                        "Unable to load metrics of project {} on {}: {}",
+                        project_id,
+                        config.host,
+                        error
+                    );
+                    missing.push(format!("#{} ({})", project_id, config.host));
+                }
+            }
+        }
+    }
+
+    (projects, missing)
+}
+
+/// Activity of each project of `config`, in configured order
+async fn get_instance_activities(
+    gitlabs: &Gitlabs,
+    config: &Gitlab,
+    since: &str,
+) -> Result<Vec<Result<ProjectActivity, GitlabError>>, GitlabError> {
+    let gitlab = gitlabs.get(config).await?;
+
+    Ok(join_all(
+        config
+            .project_ids
+            .iter()
+            .map(|project_id| get_activity(&gitlab, &config.host, *project_id, since)),
+    )
+    .await)
+}
+
+async fn get_activity(
+    gitlab: &GitlabClient,
+    host: &str,
+    project_id: u64,
+    since: &str,
+) -> Result<ProjectActivity, GitlabError> {
+    let deployments = api::paged(
+        UpdatedDeployments::new(project_id, since.to_string()),
+        api::Pagination::Limit(METRICS_ITEMS_COUNT),
+    );
+    let merge_requests = api::paged(
+        MergedMergeRequests::new(project_id, since.to_string()),
+        api::Pagination::Limit(METRICS_ITEMS_COUNT),
+    );
+    let (project, deployments, merge_requests) = join!(
+        get_project(gitlab.clone(), project_id),
+        deployments.query_async(gitlab),
+        merge_requests.query_async(gitlab),
+    );
+    let (project, deployments, merge_requests): (_, Vec<Deployment>, Vec<MergeRequest>) =
+        (project?, deployments?, merge_requests?);
+    let pipelines: Vec<Pipeline> = api::paged(
+        UpdatedPipelines::new(project_id, project.default_branch, since.to_string()),
+        api::Pagination::Limit(METRICS_ITEMS_COUNT),
+    )
+    .query_async(gitlab)
+    .await?;
+
+    Ok(ProjectActivity {
+        host: host.to_string(),
+        name: project.name,
+        deployments,
+        merge_requests,
+        pipelines,
+    })
+}
+
+/// Quoted when needed
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
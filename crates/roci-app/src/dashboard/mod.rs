@@ -1,6 +1,4 @@
-use std::rc::Rc;
-
-use gpui::*;
+use gpui::{prelude::FluentBuilder, *};
 use gpui_component::{
     button::{Button, ButtonVariants},
     input::InputState,
@@ -13,27 +11,52 @@ use roci_app_components::{bool::BooleanState, list::List};
 use strum::IntoEnumIterator;
 
 use crate::{
+    actions::{
+        CopySelectedUrl, MarkSelectedTodoDone, OpenConfigure, OpenSearch, OpenSelected, Refresh,
+        SelectNext, SelectNextSection, SelectPrevious, SelectPreviousSection, ShowShortcuts,
+    },
     config::{
-        merge_request::ShowMergeRequest,
-        refresh::RefreshEvery,
-        theme::{load_theme, ThemeMode},
+        layout::ALL_INSTANCES, merge_request::ShowMergeRequest, project::ProjectLayout,
+        refresh::RefreshEvery, theme::ThemeMode,
     },
+    dashboard::theme::ThemeSelect,
+    popout::{self, PopOutKey},
     state::AppState,
 };
 
+mod activity;
+mod api;
 mod configure;
 mod error;
+mod export;
 mod issue;
+mod layout;
+mod metrics;
+mod navigation;
 mod project;
+mod prometheus;
+mod refresh;
+mod search;
+mod stale;
+mod theme;
 mod todo;
+mod tray;
+mod webhook;
 
 pub const CONTAINER_PADDING: Pixels = px(15.);
 
 pub struct Dashboard {
+    focus_handle: FocusHandle,
     notifications: Vec<(NotificationType, SharedString)>,
     projects: Entity<List<project::Projects>>,
     issues: Entity<List<issue::Issues>>,
     todos: Entity<List<todo::Todos>>,
+    activities: Entity<List<activity::Activities>>,
+    stale_merge_requests: Entity<List<stale::StaleMergeRequests>>,
+    unified_projects: Entity<project::UnifiedProjects>,
+    unified_issues: Entity<issue::UnifiedIssues>,
+    unified_todos: Entity<todo::UnifiedTodos>,
+    unified_stale_merge_requests: Entity<stale::UnifiedStaleMergeRequests>,
     //
     new_protocol: Entity<SelectState<Vec<String>>>,
     new_host: Entity<InputState>,
@@ -41,9 +64,19 @@ pub struct Dashboard {
     new_cert_insecure: Entity<BooleanState>,
     new_is_editing: Entity<BooleanState>,
     //
+    new_webhook_address: Entity<InputState>,
+    new_webhook_secret: Entity<InputState>,
+    new_webhook_enabled: Entity<BooleanState>,
+    //
     refresh_every: Entity<SelectState<Vec<RefreshEvery>>>,
     show_merge_request: Entity<SelectState<Vec<ShowMergeRequest>>>,
+    project_layout: Entity<SelectState<Vec<ProjectLayout>>>,
     theme_mode: Entity<SelectState<Vec<ThemeMode>>>,
+    dark_theme: ThemeSelect,
+    light_theme: ThemeSelect,
+    _appearance: Subscription,
+    _activation: Subscription,
+    _bounds: Subscription,
 }
 
 impl Dashboard {
@@ -78,6 +111,41 @@ impl Dashboard {
                     .collect(),
             )
         });
+        let activities = cx.new(|cx| {
+            List(
+                gitlabs
+                    .iter()
+                    .cloned()
+                    .map(|config| cx.new(|cx| activity::Activities::new(window, cx, config)))
+                    .collect(),
+            )
+        });
+        let stale_merge_requests = cx.new(|cx| {
+            List(
+                gitlabs
+                    .iter()
+                    .cloned()
+                    .map(|config| cx.new(|cx| stale::StaleMergeRequests::new(window, cx, config)))
+                    .collect(),
+            )
+        });
+
+        let unified_projects = {
+            let sources = projects.read(cx).0.clone();
+            cx.new(|cx| project::UnifiedProjects::new(cx, sources))
+        };
+        let unified_issues = {
+            let sources = issues.read(cx).0.clone();
+            cx.new(|cx| issue::UnifiedIssues::new(cx, sources))
+        };
+        let unified_todos = {
+            let sources = todos.read(cx).0.clone();
+            cx.new(|cx| todo::UnifiedTodos::new(cx, sources))
+        };
+        let unified_stale_merge_requests = {
+            let sources = stale_merge_requests.read(cx).0.clone();
+            cx.new(|cx| stale::UnifiedStaleMergeRequests::new(cx, sources))
+        };
 
         let new_protocol = cx.new(|cx| {
             SelectState::new(
@@ -91,14 +159,16 @@ impl Dashboard {
         let new_auth_key = cx.new(|cx| InputState::new(window, cx));
         let new_cert_insecure = cx.new(|_cx| BooleanState::new(false));
         let new_is_editing = cx.new(|_cx| BooleanState::new(false));
+        let new_webhook_address = cx.new(|cx| InputState::new(window, cx));
+        let new_webhook_secret = cx.new(|cx| InputState::new(window, cx).masked(true));
+        let new_webhook_enabled = cx.new(|_cx| BooleanState::new(false));
 
-        let refresh_every_index = RefreshEvery::iter()
-            .collect::<Vec<RefreshEvery>>()
+        let refresh_every_index = RefreshEvery::presets()
             .iter()
             .position(|v| v == &config.refresh_every);
         let refresh_every = cx.new(|cx| {
             SelectState::new(
-                RefreshEvery::iter().collect(),
+                RefreshEvery::presets(),
                 refresh_every_index.map(|v| IndexPath::new(v)),
                 window,
                 cx,
@@ -126,6 +196,21 @@ impl Dashboard {
         )
         .detach();
 
+        let project_layout_index = ProjectLayout::iter()
+            .collect::<Vec<ProjectLayout>>()
+            .iter()
+            .position(|v| v == &config.project_layout);
+        let project_layout = cx.new(|cx| {
+            SelectState::new(
+                ProjectLayout::iter().collect(),
+                project_layout_index.map(|v| IndexPath::new(v)),
+                window,
+                cx,
+            )
+        });
+        cx.subscribe_in(&project_layout, window, Self::on_select_project_layout)
+            .detach();
+
         let theme_mode_index = ThemeMode::iter()
             .collect::<Vec<ThemeMode>>()
             .iter()
@@ -140,12 +225,40 @@ impl Dashboard {
         });
         cx.subscribe_in(&theme_mode, window, Self::on_select_theme_mode)
             .detach();
+        let (dark_theme, light_theme) = Self::new_theme_selects(window, cx, &config);
+        // Follow OS dark/light changes while running
+        let _appearance = cx.observe_window_appearance(window, |this, window, cx| {
+            if AppState::global(cx).config().theme_mode == ThemeMode::System {
+                this.apply_theme(window, cx);
+            }
+        });
+        refresh::watch_visibility(window, cx);
+        // Window minimized or covered is back
+        let _activation = cx.observe_window_activation(window, |_this, window, cx| {
+            if window.is_window_active() {
+                AppState::global_mut(cx).refreshes_mut().set_hidden(false);
+            }
+        });
+
+        let _bounds = cx.observe_window_bounds(window, |_this, window, cx| {
+            popout::save_bounds(popout::MAIN_WINDOW, window, cx);
+        });
+
+        let focus_handle = cx.focus_handle();
+        window.focus(&focus_handle);
 
         Self {
+            focus_handle,
             notifications: vec![],
             projects,
             issues,
             todos,
+            activities,
+            stale_merge_requests,
+            unified_projects,
+            unified_issues,
+            unified_todos,
+            unified_stale_merge_requests,
             //
             new_protocol,
             new_host,
@@ -153,9 +266,19 @@ impl Dashboard {
             new_cert_insecure,
             new_is_editing,
             //
+            new_webhook_address,
+            new_webhook_secret,
+            new_webhook_enabled,
+            //
             refresh_every,
             show_merge_request,
+            project_layout,
             theme_mode,
+            dark_theme,
+            light_theme,
+            _appearance,
+            _activation,
+            _bounds,
         }
     }
 
@@ -169,6 +292,44 @@ impl Dashboard {
 
     fn refresh_all(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         *self = Self::new(window, cx);
+        self.rebind_pop_outs(cx);
+    }
+
+    /// Pop outs show rebuilt entities, former ones would keep being fetched on their own
+    fn rebind_pop_outs(&self, cx: &mut Context<Self>) {
+        for key in popout::opened(cx) {
+            let content = match &key {
+                PopOutKey::Project(host, project_id) => {
+                    let projects = self
+                        .projects
+                        .read(cx)
+                        .0
+                        .iter()
+                        .find(|projects| projects.read(cx).host() == host)
+                        .cloned();
+                    projects
+                        .map(|projects| project::Projects::pop_out_view(cx, projects, *project_id))
+                }
+                PopOutKey::Todos(host) if host == ALL_INSTANCES => {
+                    Some(self.unified_todos.clone().into())
+                }
+                PopOutKey::Todos(host) => self
+                    .todos
+                    .read(cx)
+                    .0
+                    .iter()
+                    .find(|todos| todos.read(cx).host() == host)
+                    .map(|todos| todos.clone().into()),
+                // Fetched by the pop out itself
+                PopOutKey::PipelineLog(_) => continue,
+            };
+
+            match content {
+                Some(content) => popout::rebind(cx, &key, content),
+                // Instance removed from configuration
+                None => popout::close(cx, &key),
+            }
+        }
     }
 
     fn on_select_refresh_every(
@@ -227,40 +388,20 @@ impl Dashboard {
         self.refresh_all(window, cx);
     }
 
-    fn on_select_theme_mode(
+    fn on_select_project_layout(
         &mut self,
-        _: &Entity<SelectState<Vec<ThemeMode>>>,
-        event: &SelectEvent<Vec<ThemeMode>>,
+        _: &Entity<SelectState<Vec<ProjectLayout>>>,
+        event: &SelectEvent<Vec<ProjectLayout>>,
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        let SelectEvent::Confirm(Some(theme_mode)) = event else {
+        let SelectEvent::Confirm(Some(project_layout)) = event else {
             return;
         };
         let state = AppState::global_mut(cx);
         let mut new_config = state.config().clone();
-        new_config.theme_mode = theme_mode.clone();
-        let (theme, theme_error) = match load_theme(*theme_mode) {
-            Ok(value) => value,
-            Err(error) => {
-                window.push_notification(
-                    (
-                        NotificationType::Error,
-                        SharedString::new(format!("Failed to apply theme: {:#}", error)),
-                    ),
-                    cx,
-                );
-                return;
-            }
-        };
-        Theme::global_mut(cx).apply_config(&Rc::new(theme));
-
-        if let Some(error) = theme_error {
-            window.push_notification(error.into_notification(), cx);
-            return;
-        }
+        new_config.project_layout = project_layout.clone();
 
-        let state = AppState::global_mut(cx);
         if let Err(error) = state.replace_config(new_config.clone()) {
             window.push_notification(
                 (
@@ -271,6 +412,8 @@ impl Dashboard {
             );
             return;
         }
+
+        self.refresh_all(window, cx);
     }
 }
 
@@ -278,6 +421,14 @@ impl Render for Dashboard {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let view = cx.entity();
         let refresh_every = self.refresh_every.clone();
+        let compact = AppState::global(cx).config().layout.compact;
+        let theme_mode = AppState::global(cx).config().theme_mode;
+        let sections = self
+            .sections(cx)
+            .into_iter()
+            .filter(|section| !section.hidden)
+            .filter_map(|section| self.render_section(section, cx))
+            .collect::<Vec<_>>();
 
         while let Some((notification_type, notification_message)) = self.notifications.pop() {
             window.push_notification(
@@ -288,15 +439,52 @@ impl Render for Dashboard {
 
         v_flex()
             .size_full()
+            .track_focus(&self.focus_handle)
+            .key_context("Dashboard")
+            .on_action(cx.listener(|this, _: &OpenSearch, window, cx| {
+                this.show_search_dialog(window, cx);
+            }))
+            .on_action(cx.listener(|this, _: &Refresh, window, cx| {
+                this.refresh_all(window, cx);
+            }))
+            .on_action(cx.listener(|this, _: &SelectNext, _window, cx| {
+                this.select_sibling(cx, true);
+            }))
+            .on_action(cx.listener(|this, _: &SelectPrevious, _window, cx| {
+                this.select_sibling(cx, false);
+            }))
+            .on_action(cx.listener(|this, _: &SelectNextSection, _window, cx| {
+                this.select_section(cx, true);
+            }))
+            .on_action(cx.listener(|this, _: &SelectPreviousSection, _window, cx| {
+                this.select_section(cx, false);
+            }))
+            .on_action(cx.listener(|this, _: &OpenSelected, _window, cx| {
+                this.open_selected(cx);
+            }))
+            .on_action(cx.listener(|this, _: &CopySelectedUrl, window, cx| {
+                this.copy_selected_url(window, cx);
+            }))
+            .on_action(cx.listener(|this, _: &MarkSelectedTodoDone, window, cx| {
+                this.mark_selected_todo_done(window, cx);
+            }))
+            .on_action(cx.listener(|this, _: &OpenConfigure, window, cx| {
+                this.show_configure_dialog(window, cx);
+            }))
+            .on_action(cx.listener(|this, _: &ShowShortcuts, window, cx| {
+                this.show_shortcuts_dialog(window, cx);
+            }))
             .child(
-                TitleBar::new().child(
-                    h_flex().w_full().pr_2().justify_between().child(
-                        h_flex()
-                            .child(Icon::new(crate::assets::Icon::Spaceship))
-                            .child(" ")
-                            .child("Roci: your work dashboard spaceship"),
+                TitleBar::new()
+                    .on_close_window(|_, window, cx| Self::close_window(window, cx))
+                    .child(
+                        h_flex().w_full().pr_2().justify_between().child(
+                            h_flex()
+                                .child(Icon::new(crate::assets::Icon::Spaceship))
+                                .child(" ")
+                                .child("Roci: your work dashboard spaceship"),
+                        ),
                     ),
-                ),
             )
             .child(
                 v_flex()
@@ -310,20 +498,103 @@ impl Render for Dashboard {
                                     .dropdown_menu({
                                         let view = view.clone();
 
-                                        move |this, window, _cx| {
+                                        move |this, window, cx| {
+                                            let tray = &AppState::global(cx).config().tray;
+                                            let tray_label = if tray.enabled {
+                                                "Disable tray icon (on next start)"
+                                            } else {
+                                                "Enable tray icon (on next start)"
+                                            };
+                                            let keep_running_label = if tray.keep_running {
+                                                "Quit when window is closed"
+                                            } else {
+                                                "Keep running in tray when window is closed"
+                                            };
+
                                             this.item(
-                                                PopupMenuItem::new("Configure access").on_click(
-                                                    window.listener_for(
+                                                PopupMenuItem::new("Configure access (Ctrl+,)")
+                                                    .on_click(window.listener_for(
                                                         &view,
                                                         |this, _event, window, cx| {
                                                             this.show_configure_dialog(window, cx);
                                                         },
+                                                    )),
+                                            )
+                                            .item(PopupMenuItem::new("Search (Ctrl+K)").on_click(
+                                                window.listener_for(
+                                                    &view,
+                                                    |this, _event, window, cx| {
+                                                        this.show_search_dialog(window, cx);
+                                                    },
+                                                ),
+                                            ))
+                                            .item(PopupMenuItem::new("Team metrics").on_click(
+                                                window.listener_for(
+                                                    &view,
+                                                    |this, _event, window, cx| {
+                                                        this.show_metrics_dialog(window, cx);
+                                                    },
+                                                ),
+                                            ))
+                                            .item(PopupMenuItem::new("Export report").on_click(
+                                                window.listener_for(
+                                                    &view,
+                                                    |this, _event, window, cx| {
+                                                        this.show_export_dialog(window, cx);
+                                                    },
+                                                ),
+                                            ))
+                                            .item(
+                                                PopupMenuItem::new("Keyboard shortcuts (?)")
+                                                    .on_click(window.listener_for(
+                                                        &view,
+                                                        |this, _event, window, cx| {
+                                                            this.show_shortcuts_dialog(window, cx);
+                                                        },
+                                                    )),
+                                            )
+                                            .separator()
+                                            .item(PopupMenuItem::new(tray_label).on_click(
+                                                window.listener_for(
+                                                    &view,
+                                                    |this, _event, window, cx| {
+                                                        this.update_tray_config(
+                                                            window,
+                                                            cx,
+                                                            |tray| tray.enabled = !tray.enabled,
+                                                        );
+                                                    },
+                                                ),
+                                            ))
+                                            .item(PopupMenuItem::new("Webhook receiver").on_click(
+                                                window.listener_for(
+                                                    &view,
+                                                    |this, _event, window, cx| {
+                                                        this.show_webhook_dialog(window, cx);
+                                                    },
+                                                ),
+                                            ))
+                                            .item(
+                                                PopupMenuItem::new(keep_running_label).on_click(
+                                                    window.listener_for(
+                                                        &view,
+                                                        |this, _event, window, cx| {
+                                                            this.update_tray_config(
+                                                                window,
+                                                                cx,
+                                                                |tray| {
+                                                                    tray.keep_running =
+                                                                        !tray.keep_running
+                                                                },
+                                                            );
+                                                        },
                                                     ),
                                                 ),
                                             )
                                         }
                                     }),
                             )
+                            .child(self.render_layout_menu(cx))
                             .child(
                                 div().child(
                                     h_flex()
@@ -344,7 +615,14 @@ impl Render for Dashboard {
                                         )
                                         .child(Select::new(&refresh_every))
                                         .child(Select::new(&self.show_merge_request))
-                                        .child(Select::new(&self.theme_mode)),
+                                        .child(Select::new(&self.project_layout))
+                                        .child(Select::new(&self.theme_mode))
+                                        .when(theme_mode != ThemeMode::Light, |element| {
+                                            element.child(Select::new(&self.dark_theme))
+                                        })
+                                        .when(theme_mode != ThemeMode::Dark, |element| {
+                                            element.child(Select::new(&self.light_theme))
+                                        }),
                                 ),
                             ),
                     )
@@ -353,10 +631,19 @@ impl Render for Dashboard {
                         div()
                             .flex_1()
                             .overflow_hidden()
-                            .paddings(Edges::all(CONTAINER_PADDING))
-                            .child(self.todos.clone())
-                            .child(self.projects.clone())
-                            .child(self.issues.clone()),
+                            .when(!compact, |element| {
+                                element.paddings(Edges::all(CONTAINER_PADDING))
+                            })
+                            .when(compact, |element| {
+                                element
+                                    .paddings(Edges::all(layout::COMPACT_CONTAINER_PADDING))
+                                    .text_sm()
+                                    .flex()
+                                    .flex_wrap()
+                                    .items_start()
+                                    .gap_2()
+                            })
+                            .children(sections),
                     ),
             )
             .children(Root::render_dialog_layer(window, cx))
@@ -0,0 +1,180 @@
+use gpui::*;
+use gpui_component::{h_flex, label::Label, v_flex, StyledExt, WindowExt};
+use tracing_unwrap::ResultExt;
+
+use crate::{
+    actions::SHORTCUTS,
+    config::layout::{Section, SectionKind, ALL_INSTANCES},
+    dashboard::Dashboard,
+    state::{
+        navigation::{NavItem, NavKey, NavKind},
+        AppState,
+    },
+};
+
+impl Dashboard {
+    /// Navigable items with their section index, in the dashboard display order
+    fn nav_items(&self, cx: &App) -> Vec<(usize, NavItem)> {
+        self.sections(cx)
+            .iter()
+            .filter(|section| !section.hidden && !section.collapsed)
+            .enumerate()
+            .flat_map(|(index, section)| {
+                self.section_nav_items(section, cx)
+                    .into_iter()
+                    .map(move |item| (index, item))
+            })
+            .collect()
+    }
+
+    fn section_nav_items(&self, section: &Section, cx: &App) -> Vec<NavItem> {
+        if section.host == ALL_INSTANCES {
+            return match section.kind {
+                SectionKind::Todos => self.unified_todos.read(cx).nav_items(cx),
+                SectionKind::Projects => self.unified_projects.read(cx).nav_items(cx),
+                SectionKind::Issues => self.unified_issues.read(cx).nav_items(cx),
+                SectionKind::Activity => vec![],
+                SectionKind::StaleMergeRequests => {
+                    self.unified_stale_merge_requests.read(cx).nav_items(cx)
+                }
+            };
+        }
+        let Some(index) = self.host_index(&section.host, cx) else {
+            return vec![];
+        };
+
+        match section.kind {
+            SectionKind::Todos => self
+                .todos
+                .read(cx)
+                .0
+                .get(index)
+                .map(|todos| todos.read(cx).nav_items(cx)),
+            SectionKind::Projects => self
+                .projects
+                .read(cx)
+                .0
+                .get(index)
+                .map(|projects| projects.read(cx).nav_items(cx)),
+            SectionKind::Issues => self
+                .issues
+                .read(cx)
+                .0
+                .get(index)
+                .map(|issues| issues.read(cx).nav_items(cx)),
+            SectionKind::Activity => None,
+            SectionKind::StaleMergeRequests => self
+                .stale_merge_requests
+                .read(cx)
+                .0
+                .get(index)
+                .map(|stale_merge_requests| stale_merge_requests.read(cx).nav_items(cx)),
+        }
+        .unwrap_or_default()
+    }
+
+    fn selected_item(&self, cx: &App) -> Option<(usize, NavItem)> {
+        let selected = AppState::global(cx).selected()?;
+        self.nav_items(cx)
+            .into_iter()
+            .find(|(_, item)| &item.key == selected)
+    }
+
+    fn select(&mut self, cx: &mut Context<Self>, key: Option<NavKey>) {
+        AppState::global_mut(cx).select(key);
+        cx.notify();
+    }
+
+    pub fn select_sibling(&mut self, cx: &mut Context<Self>, forward: bool) {
+        let items = self.nav_items(cx);
+        if items.is_empty() {
+            return;
+        }
+
+        let current = AppState::global(cx)
+            .selected()
+            .and_then(|selected| items.iter().position(|(_, item)| &item.key == selected));
+        let index = match (current, forward) {
+            (None, true) => 0,
+            (None, false) => items.len() - 1,
+            (Some(index), true) => (index + 1) % items.len(),
+            (Some(index), false) => (index + items.len() - 1) % items.len(),
+        };
+
+        self.select(cx, Some(items[index].1.key.clone()));
+    }
+
+    pub fn select_section(&mut self, cx: &mut Context<Self>, forward: bool) {
+        let items = self.nav_items(cx);
+        let current = self.selected_item(cx).map(|(section, _)| section);
+
+        let target = if forward {
+            items
+                .iter()
+                .find(|(section, _)| current.is_none_or(|current| *section > current))
+        } else {
+            items
+                .iter()
+                .map(|(section, _)| *section)
+                .filter(|section| current.is_none_or(|current| *section < current))
+                .max()
+                .and_then(|section| items.iter().find(|(section_, _)| *section_ == section))
+        };
+
+        if let Some((_, item)) = target {
+            self.select(cx, Some(item.key.clone()));
+        }
+    }
+
+    pub fn open_selected(&mut self, cx: &mut Context<Self>) {
+        if let Some((_, item)) = self.selected_item(cx) {
+            open::that(item.key.url).unwrap_or_log();
+        }
+    }
+
+    pub fn copy_selected_url(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some((_, item)) = self.selected_item(cx) {
+            cx.write_to_clipboard(ClipboardItem::new_string(item.key.url));
+            window.push_notification("URL copied", cx);
+        }
+    }
+
+    pub fn mark_selected_todo_done(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some((_, item)) = self.selected_item(cx) else {
+            return;
+        };
+        if item.key.kind != NavKind::Todo {
+            window.push_notification("Selected item is not a todo", cx);
+            return;
+        }
+
+        let todos = self
+            .todos
+            .read(cx)
+            .0
+            .iter()
+            .find(|todos| todos.read(cx).host() == item.host)
+            .cloned();
+        if let Some(todos) = todos {
+            self.select_sibling(cx, true);
+            todos.update(cx, |todos, cx| todos.mark_done(window, cx, item.id));
+        }
+    }
+
+    pub fn show_shortcuts_dialog(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        window.open_dialog(cx, |dialog, _window, _cx| {
+            dialog
+                .title("Keyboard shortcuts")
+                .child(
+                    v_flex()
+                        .gap_1()
+                        .children(SHORTCUTS.iter().map(|(keys, description)| {
+                            h_flex()
+                                .gap_3()
+                                .child(div().w(px(140.)).child(Label::new(*keys).font_semibold()))
+                                .child(*description)
+                        })),
+                )
+        });
+    }
+}
@@ -0,0 +1,88 @@
+use derive_more::Constructor;
+use gitlab::api::{Endpoint, Pageable, QueryParams};
+
+#[derive(Debug, Constructor)]
+pub struct ProjectEnvironments {
+    project_id: u64,
+}
+
+impl Endpoint for ProjectEnvironments {
+    fn method(&self) -> reqwest::Method {
+        reqwest::Method::GET
+    }
+
+    fn endpoint(&self) -> std::borrow::Cow<'static, str> {
+        format!("projects/{}/environments", self.project_id).into()
+    }
+
+    fn parameters(&self) -> QueryParams<'_> {
+        let mut params = QueryParams::default();
+        params.push("states", "available");
+        params
+    }
+}
+
+impl Pageable for ProjectEnvironments {}
+
+/// Deployments of an environment, most recent first
+#[derive(Debug, Constructor)]
+pub struct ProjectDeployments {
+    project_id: u64,
+    environment: String,
+}
+
+impl Endpoint for ProjectDeployments {
+    fn method(&self) -> reqwest::Method {
+        reqwest::Method::GET
+    }
+
+    fn endpoint(&self) -> std::borrow::Cow<'static, str> {
+        format!("projects/{}/deployments", self.project_id).into()
+    }
+
+    fn parameters(&self) -> QueryParams<'_> {
+        let mut params = QueryParams::default();
+        params.push("environment", &self.environment);
+        params.push("order_by", "id");
+        params.push("sort", "desc");
+        params
+    }
+}
+
+impl Pageable for ProjectDeployments {}
+
+#[derive(Debug, Constructor)]
+pub struct StopEnvironment {
+    project_id: u64,
+    environment_id: u64,
+}
+
+impl Endpoint for StopEnvironment {
+    fn method(&self) -> reqwest::Method {
+        reqwest::Method::POST
+    }
+
+    fn endpoint(&self) -> std::borrow::Cow<'static, str> {
+        format!(
+            "projects/{}/environments/{}/stop",
+            self.project_id, self.environment_id
+        )
+        .into()
+    }
+}
+
+#[derive(Debug, Constructor)]
+pub struct RetryJob {
+    project_id: u64,
+    job_id: u64,
+}
+
+impl Endpoint for RetryJob {
+    fn method(&self) -> reqwest::Method {
+        reqwest::Method::POST
+    }
+
+    fn endpoint(&self) -> std::borrow::Cow<'static, str> {
+        format!("projects/{}/jobs/{}/retry", self.project_id, self.job_id).into()
+    }
+}
@@ -0,0 +1,338 @@
+use derive_more::Constructor;
+use futures::future::join_all;
+use gitlab::api::{self, AsyncQuery, Endpoint};
+use gpui::{prelude::FluentBuilder, *};
+use gpui_component::{
+    button::{Button, ButtonVariants},
+    h_flex,
+    label::Label,
+    menu::{DropdownMenu as _, PopupMenuItem},
+    notification::NotificationType,
+    ActiveTheme, Icon, IconName, WindowExt,
+};
+use roci_app_components::{
+    confirm::open_confirm_dialog, error::WithButtonModalError, with_button_error, LoadState,
+};
+use tracing_unwrap::ResultExt;
+
+use crate::{
+    dashboard::{
+        error::GitlabError,
+        project::deployment::endpoint::{
+            ProjectDeployments, ProjectEnvironments, RetryJob, StopEnvironment,
+        },
+    },
+    state::gitlab::{
+        project::deployment::{Deployment, DeploymentStatus, Environment, EnvironmentDeployments},
+        GitlabClient,
+    },
+    utils::short_datetime,
+};
+
+mod endpoint;
+
+/// Last deployments fetched per environment, enough to find rollback candidates
+pub const DEPLOYMENTS_COUNT: usize = 20;
+pub const ROLLBACK_CANDIDATES_COUNT: usize = 3;
+
+pub struct Deployments {
+    inner: Entity<LoadState<DeploymentsInner, WithButtonModalError<GitlabError>>>,
+    gitlab: GitlabClient,
+    project_id: u64,
+}
+
+impl Deployments {
+    pub fn new(
+        window: &mut Window,
+        cx: &mut Context<Self>,
+        gitlab: GitlabClient,
+        project_id: u64,
+    ) -> Self {
+        let this = Self {
+            inner: cx.new(|_cx| LoadState::Loading),
+            gitlab,
+            project_id,
+        };
+        this.load(window, cx);
+
+        this
+    }
+
+    fn load(&self, window: &mut Window, cx: &mut Context<Self>) {
+        let gitlab = self.gitlab.clone();
+        let project_id = self.project_id;
+
+        cx.spawn_in(window, async move |this, cx| {
+            match get_deployments(gitlab, project_id).await {
+                Ok(environments) => {
+                    let _ = this.update_in(cx, |this, _window, cx| {
+                        let view = cx.entity();
+                        let environments = cx.new(|_cx| DeploymentsInner::new(view, environments));
+                        this.inner = cx.new(|_cx| LoadState::Ready(environments));
+                        cx.notify();
+                    });
+                }
+                Err(error) => {
+                    let _ = this.update_in(cx, |this, _window, cx| {
+                        this.inner = cx.new(|cx| {
+                            with_button_error!(
+                                cx,
+                                "Load error".into(),
+                                format!("Error during load deployments"),
+                                error.into()
+                            )
+                        });
+                        cx.notify();
+                    });
+                }
+            }
+        })
+        .detach();
+    }
+
+    fn stop(&mut self, window: &mut Window, cx: &mut Context<Self>, environment: Environment) {
+        let view = cx.entity();
+
+        open_confirm_dialog(
+            window,
+            cx,
+            "Stop environment",
+            format!("Stop environment {} ?", environment.name),
+            move |window, cx| {
+                let environment = environment.clone();
+
+                view.update(cx, |this, cx| {
+                    let endpoint = StopEnvironment::new(this.project_id, environment.id);
+                    this.run(
+                        window,
+                        cx,
+                        endpoint,
+                        format!("Environment {} stopping", environment.name),
+                        "Can't stop environment",
+                    );
+                });
+            },
+        );
+    }
+
+    fn redeploy(&mut self, window: &mut Window, cx: &mut Context<Self>, deployment: Deployment) {
+        let Some(deployable) = deployment.deployable.clone() else {
+            return;
+        };
+        let view = cx.entity();
+
+        open_confirm_dialog(
+            window,
+            cx,
+            "Deploy",
+            format!(
+                "Deploy {} ({}) on {} ?",
+                deployment.ref_,
+                deployment.short_sha(),
+                deployment.environment.name
+            ),
+            move |window, cx| {
+                let environment = deployment.environment.name.clone();
+
+                view.update(cx, |this, cx| {
+                    let endpoint = RetryJob::new(this.project_id, deployable.id);
+                    this.run(
+                        window,
+                        cx,
+                        endpoint,
+                        format!("Deployment on {} started", environment),
+                        "Can't start deployment",
+                    );
+                });
+            },
+        );
+    }
+
+    fn run<E: Endpoint + Sync + 'static>(
+        &mut self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+        endpoint: E,
+        success: String,
+        failure: &'static str,
+    ) {
+        let gitlab = self.gitlab.clone();
+
+        cx.spawn_in(window, async move |this, cx| {
+            let result = api::ignore(endpoint).query_async(&gitlab).await;
+            let _ = this.update_in(cx, |this, window, cx| match result {
+                Ok(_) => {
+                    window.push_notification(SharedString::new(success), cx);
+                    this.load(window, cx);
+                }
+                Err(error) => window.push_notification(
+                    (
+                        NotificationType::Error,
+                        SharedString::new(format!("{}: {}", failure, error)),
+                    ),
+                    cx,
+                ),
+            });
+        })
+        .detach();
+    }
+}
+
+impl Render for Deployments {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        div().child(self.inner.clone())
+    }
+}
+
+#[derive(Constructor)]
+pub struct DeploymentsInner {
+    view: Entity<Deployments>,
+    environments: Vec<EnvironmentDeployments>,
+}
+
+impl Render for DeploymentsInner {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let view = self.view.clone();
+
+        h_flex()
+            .when(self.environments.is_empty(), |_cx| {
+                div().child(Label::new("n/a"))
+            })
+            .id("deployments")
+            .children(self.environments.iter().map(|environment| {
+                let last = environment.last();
+                let (icon, color): (IconName, Hsla) = match last.map(|d| d.status) {
+                    Some(DeploymentStatus::Success) => (IconName::CircleCheck, cx.theme().green),
+                    Some(DeploymentStatus::Failed) => (IconName::CircleX, cx.theme().red),
+                    Some(
+                        DeploymentStatus::Created
+                        | DeploymentStatus::Running
+                        | DeploymentStatus::Blocked,
+                    ) => (IconName::Ellipsis, cx.theme().cyan),
+                    Some(DeploymentStatus::Canceled | DeploymentStatus::Skipped) | None => {
+                        (IconName::Dash, cx.theme().muted_foreground)
+                    }
+                };
+                let tooltip = match last {
+                    Some(deployment) => format!(
+                        "{}: {} ({}) by {} at {}",
+                        deployment.status,
+                        deployment.ref_,
+                        deployment.short_sha(),
+                        deployment.user.name,
+                        short_datetime(&deployment.created_at)
+                    ),
+                    None => "Never deployed".to_string(),
+                };
+                let url = environment.environment.external_url.clone().or(last
+                    .and_then(|deployment| deployment.deployable.as_ref())
+                    .map(|deployable| deployable.web_url.clone()));
+
+                div()
+                    .id(ElementId::Integer(environment.environment.id))
+                    .child(
+                        Button::new("environment")
+                            .icon(Icon::new(icon).text_color(color))
+                            .label(environment.environment.name.clone())
+                            .link()
+                            .tooltip(tooltip)
+                            .dropdown_menu({
+                                let view = view.clone();
+                                let environment = environment.clone();
+
+                                move |this, window, _cx| {
+                                    let mut menu = this;
+
+                                    if let Some(url) = url.clone() {
+                                        menu = menu.item(PopupMenuItem::new("Open").on_click(
+                                            move |_, _, _| {
+                                                open::that(url.clone()).unwrap_or_log();
+                                            },
+                                        ));
+                                    }
+
+                                    menu =
+                                        menu.item(PopupMenuItem::new("Stop environment").on_click(
+                                            window.listener_for(&view, {
+                                                let environment = environment.environment.clone();
+
+                                                move |this, _event, window, cx| {
+                                                    this.stop(window, cx, environment.clone());
+                                                }
+                                            }),
+                                        ));
+
+                                    if let Some(last) = environment
+                                        .last()
+                                        .filter(|deployment| deployment.deployable.is_some())
+                                    {
+                                        menu = menu.item(PopupMenuItem::new("Re-deploy").on_click(
+                                            window.listener_for(&view, {
+                                                let last = last.clone();
+
+                                                move |this, _event, window, cx| {
+                                                    this.redeploy(window, cx, last.clone());
+                                                }
+                                            }),
+                                        ));
+                                    }
+
+                                    for candidate in environment
+                                        .rollback_candidates()
+                                        .take(ROLLBACK_CANDIDATES_COUNT)
+                                    {
+                                        let label = format!(
+                                            "Rollback to {} ({}, {})",
+                                            candidate.ref_,
+                                            candidate.short_sha(),
+                                            short_datetime(&candidate.created_at)
+                                        );
+                                        menu = menu.item(PopupMenuItem::new(label).on_click(
+                                            window.listener_for(&view, {
+                                                let candidate = candidate.clone();
+
+                                                move |this, _event, window, cx| {
+                                                    this.redeploy(window, cx, candidate.clone());
+                                                }
+                                            }),
+                                        ));
+                                    }
+
+                                    menu
+                                }
+                            }),
+                    )
+            }))
+    }
+}
+
+async fn get_deployments(
+    gitlab: GitlabClient,
+    project_id: u64,
+) -> Result<Vec<EnvironmentDeployments>, GitlabError> {
+    let environments: Vec<Environment> =
+        api::paged(ProjectEnvironments::new(project_id), api::Pagination::All)
+            .query_async(&gitlab)
+            .await?;
+
+    // Per environment, a single page would be filled by the most active ones
+    join_all(environments.into_iter().map(|environment| {
+        let gitlab = &gitlab;
+        async move {
+            let deployments: Vec<Deployment> = api::paged(
+                ProjectDeployments::new(project_id, environment.name.clone()),
+                api::Pagination::Limit(DEPLOYMENTS_COUNT),
+            )
+            .query_async(gitlab)
+            .await?;
+
+            Ok(EnvironmentDeployments {
+                environment,
+                deployments,
+            })
+        }
+    }))
+    .await
+    .into_iter()
+    .collect()
+}
@@ -0,0 +1,173 @@
+use derive_more::Constructor;
+use gitlab::api::{BodyError, Endpoint, FormParams, Pageable};
+
+#[derive(Debug, Constructor)]
+pub struct MergeRequestDiscussions {
+    project_id: u64,
+    merge_request_iid: u64,
+}
+
+impl Endpoint for MergeRequestDiscussions {
+    fn method(&self) -> reqwest::Method {
+        reqwest::Method::GET
+    }
+
+    fn endpoint(&self) -> std::borrow::Cow<'static, str> {
+        format!(
+            "projects/{}/merge_requests/{}/discussions",
+            self.project_id, self.merge_request_iid
+        )
+        .into()
+    }
+}
+
+impl Pageable for MergeRequestDiscussions {}
+
+#[derive(Debug, Constructor)]
+pub struct MergeRequestDiffs {
+    project_id: u64,
+    merge_request_iid: u64,
+}
+
+impl Endpoint for MergeRequestDiffs {
+    fn method(&self) -> reqwest::Method {
+        reqwest::Method::GET
+    }
+
+    fn endpoint(&self) -> std::borrow::Cow<'static, str> {
+        format!(
+            "projects/{}/merge_requests/{}/diffs",
+            self.project_id, self.merge_request_iid
+        )
+        .into()
+    }
+}
+
+impl Pageable for MergeRequestDiffs {}
+
+#[derive(Debug, Constructor)]
+pub struct SingleMergeRequest {
+    project_id: u64,
+    merge_request_iid: u64,
+}
+
+impl Endpoint for SingleMergeRequest {
+    fn method(&self) -> reqwest::Method {
+        reqwest::Method::GET
+    }
+
+    fn endpoint(&self) -> std::borrow::Cow<'static, str> {
+        format!(
+            "projects/{}/merge_requests/{}",
+            self.project_id, self.merge_request_iid
+        )
+        .into()
+    }
+}
+
+#[derive(Debug, Constructor)]
+pub struct ResolveDiscussion {
+    project_id: u64,
+    merge_request_iid: u64,
+    discussion_id: String,
+    resolved: bool,
+}
+
+impl Endpoint for ResolveDiscussion {
+    fn method(&self) -> reqwest::Method {
+        reqwest::Method::PUT
+    }
+
+    fn endpoint(&self) -> std::borrow::Cow<'static, str> {
+        format!(
+            "projects/{}/merge_requests/{}/discussions/{}",
+            self.project_id, self.merge_request_iid, self.discussion_id
+        )
+        .into()
+    }
+
+    fn body(&self) -> Result<Option<(&'static str, Vec<u8>)>, BodyError> {
+        let mut params = FormParams::default();
+        params.push("resolved", self.resolved);
+        params.into_body()
+    }
+}
+
+#[derive(Debug, Constructor)]
+pub struct ReplyToDiscussion {
+    project_id: u64,
+    merge_request_iid: u64,
+    discussion_id: String,
+    body: String,
+}
+
+impl Endpoint for ReplyToDiscussion {
+    fn method(&self) -> reqwest::Method {
+        reqwest::Method::POST
+    }
+
+    fn endpoint(&self) -> std::borrow::Cow<'static, str> {
+        format!(
+            "projects/{}/merge_requests/{}/discussions/{}/notes",
+            self.project_id, self.merge_request_iid, self.discussion_id
+        )
+        .into()
+    }
+
+    fn body(&self) -> Result<Option<(&'static str, Vec<u8>)>, BodyError> {
+        let mut params = FormParams::default();
+        params.push("body", self.body.as_str());
+        params.into_body()
+    }
+}
+
+/// Position of a new inline comment, see gitlab "position API"
+#[derive(Debug, Clone, Constructor)]
+pub struct NewPosition {
+    base_sha: String,
+    start_sha: String,
+    head_sha: String,
+    old_path: String,
+    new_path: String,
+    /// Unset for an added line
+    old_line: Option<u64>,
+    new_line: u64,
+}
+
+#[derive(Debug, Constructor)]
+pub struct CreateDiscussion {
+    project_id: u64,
+    merge_request_iid: u64,
+    body: String,
+    position: Option<NewPosition>,
+}
+
+impl Endpoint for CreateDiscussion {
+    fn method(&self) -> reqwest::Method {
+        reqwest::Method::POST
+    }
+
+    fn endpoint(&self) -> std::borrow::Cow<'static, str> {
+        format!(
+            "projects/{}/merge_requests/{}/discussions",
+            self.project_id, self.merge_request_iid
+        )
+        .into()
+    }
+
+    fn body(&self) -> Result<Option<(&'static str, Vec<u8>)>, BodyError> {
+        let mut params = FormParams::default();
+        params.push("body", self.body.as_str());
+        if let Some(position) = &self.position {
+            params.push("position[position_type]", "text");
+            params.push("position[base_sha]", position.base_sha.as_str());
+            params.push("position[start_sha]", position.start_sha.as_str());
+            params.push("position[head_sha]", position.head_sha.as_str());
+            params.push("position[old_path]", position.old_path.as_str());
+            params.push("position[new_path]", position.new_path.as_str());
+            params.push_opt("position[old_line]", position.old_line);
+            params.push("position[new_line]", position.new_line);
+        }
+        params.into_body()
+    }
+}
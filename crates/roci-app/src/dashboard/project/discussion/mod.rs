@@ -0,0 +1,443 @@
+use std::collections::BTreeMap;
+
+use gitlab::api::{self, AsyncQuery, Endpoint};
+use gpui::{prelude::FluentBuilder, *};
+use gpui_component::{
+    button::{Button, ButtonVariants},
+    form::{field, h_form},
+    h_flex,
+    input::{Input, InputState},
+    label::Label,
+    notification::NotificationType,
+    v_flex, ActiveTheme, Icon, IconName, StyledExt, WindowExt,
+};
+use roci_app_components::{error::WithButtonModalError, with_button_error, LoadState};
+
+use crate::{
+    dashboard::{
+        error::GitlabError,
+        project::discussion::endpoint::{
+            CreateDiscussion, MergeRequestDiffs, MergeRequestDiscussions, NewPosition,
+            ReplyToDiscussion, ResolveDiscussion, SingleMergeRequest,
+        },
+    },
+    state::gitlab::{
+        project::{
+            discussion::{Discussion, FileDiff},
+            merge_request::MergeRequest,
+        },
+        GitlabClient,
+    },
+};
+
+mod endpoint;
+
+pub struct Discussions {
+    inner: Entity<LoadState<DiscussionsInner, WithButtonModalError<GitlabError>>>,
+    gitlab: GitlabClient,
+    project_id: u64,
+    merge_request_iid: u64,
+    new_path: Entity<InputState>,
+    new_line: Entity<InputState>,
+    new_body: Entity<InputState>,
+}
+
+impl Discussions {
+    pub fn new(
+        window: &mut Window,
+        cx: &mut Context<Self>,
+        gitlab: GitlabClient,
+        project_id: u64,
+        merge_request_iid: u64,
+    ) -> Self {
+        let this = Self {
+            inner: cx.new(|_cx| LoadState::Loading),
+            gitlab,
+            project_id,
+            merge_request_iid,
+            new_path: cx.new(|cx| InputState::new(window, cx).placeholder("src/main.rs")),
+            new_line: cx.new(|cx| InputState::new(window, cx).placeholder("42")),
+            new_body: cx.new(|cx| InputState::new(window, cx)),
+        };
+        this.load(window, cx);
+
+        this
+    }
+
+    fn load(&self, window: &mut Window, cx: &mut Context<Self>) {
+        let gitlab = self.gitlab.clone();
+        let project_id = self.project_id;
+        let merge_request_iid = self.merge_request_iid;
+
+        cx.spawn_in(window, async move |this, cx| {
+            match get_discussions(&gitlab, project_id, merge_request_iid).await {
+                Ok(discussions) => {
+                    let _ = this.update_in(cx, |this, window, cx| {
+                        let view = cx.entity();
+                        let discussions =
+                            cx.new(|cx| DiscussionsInner::new(window, cx, view, discussions));
+                        this.inner = cx.new(|_cx| LoadState::Ready(discussions));
+                        cx.notify();
+                    });
+                }
+                Err(error) => {
+                    let _ = this.update_in(cx, |this, _window, cx| {
+                        this.inner = cx.new(|cx| {
+                            with_button_error!(
+                                cx,
+                                "Load error".into(),
+                                format!("Error during load discussions"),
+                                error.into()
+                            )
+                        });
+                        cx.notify();
+                    });
+                }
+            }
+        })
+        .detach();
+    }
+
+    fn resolve(
+        &mut self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+        discussion_id: String,
+        resolved: bool,
+    ) {
+        let endpoint = ResolveDiscussion::new(
+            self.project_id,
+            self.merge_request_iid,
+            discussion_id,
+            resolved,
+        );
+        self.submit(window, cx, endpoint, "Can't update discussion");
+    }
+
+    fn reply(
+        &mut self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+        discussion_id: String,
+        body: String,
+    ) {
+        if body.is_empty() {
+            window.push_notification("Please fill reply.", cx);
+            return;
+        }
+
+        let endpoint =
+            ReplyToDiscussion::new(self.project_id, self.merge_request_iid, discussion_id, body);
+        self.submit(window, cx, endpoint, "Can't reply to discussion");
+    }
+
+    fn submit<E: Endpoint + Sync + 'static>(
+        &mut self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+        endpoint: E,
+        failure: &'static str,
+    ) {
+        let gitlab = self.gitlab.clone();
+
+        cx.spawn_in(window, async move |this, cx| {
+            let result = api::ignore(endpoint).query_async(&gitlab).await;
+            let _ = this.update_in(cx, |this, window, cx| match result {
+                Ok(_) => this.load(window, cx),
+                Err(error) => window.push_notification(
+                    (
+                        NotificationType::Error,
+                        SharedString::new(format!("{}: {}", failure, error)),
+                    ),
+                    cx,
+                ),
+            });
+        })
+        .detach();
+    }
+
+    fn comment(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let path = self.new_path.read(cx).value().trim().to_string();
+        let line = self.new_line.read(cx).value().trim().parse::<u64>().ok();
+        let body = self.new_body.read(cx).value().to_string();
+
+        if body.is_empty() {
+            window.push_notification("Please fill comment.", cx);
+            return;
+        }
+        let location = match (path.is_empty(), line) {
+            (true, _) => None,
+            (false, Some(line)) => Some((path, line)),
+            (false, None) => {
+                window.push_notification("Please fill a valid line number.", cx);
+                return;
+            }
+        };
+
+        let gitlab = self.gitlab.clone();
+        let project_id = self.project_id;
+        let merge_request_iid = self.merge_request_iid;
+
+        cx.spawn_in(window, async move |this, cx| {
+            let result =
+                create_discussion(gitlab, project_id, merge_request_iid, body, location).await;
+            let _ = this.update_in(cx, |this, window, cx| match result {
+                Ok(_) => {
+                    this.reset_new_comment(window, cx);
+                    this.load(window, cx);
+                }
+                Err(error) => window.push_notification(
+                    (
+                        NotificationType::Error,
+                        SharedString::new(format!("Can't create discussion: {}", error)),
+                    ),
+                    cx,
+                ),
+            });
+        })
+        .detach();
+    }
+
+    fn reset_new_comment(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        for input in [&self.new_path, &self.new_line, &self.new_body] {
+            input.update(cx, |this, cx| {
+                this.set_value(SharedString::new(""), window, cx);
+            });
+        }
+    }
+}
+
+impl Render for Discussions {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .gap_3()
+            .child(self.inner.clone())
+            .child(Label::new("New comment").text_lg())
+            .child(
+                h_form()
+                    .child(
+                        field()
+                            .label("File (empty for a general comment)")
+                            .child(Input::new(&self.new_path)),
+                    )
+                    .child(field().label("Line").child(Input::new(&self.new_line)))
+                    .child(
+                        field()
+                            .label("Comment")
+                            .child(Input::new(&self.new_body))
+                            .required(true),
+                    ),
+            )
+            .child(
+                field().label_indent(false).child(
+                    Button::new("comment")
+                        .primary()
+                        .label("Comment")
+                        .on_click(cx.listener(|this, _, window, cx| this.comment(window, cx))),
+                ),
+            )
+    }
+}
+
+pub struct DiscussionsInner {
+    view: Entity<Discussions>,
+    discussions: Vec<Discussion>,
+    replies: Vec<Entity<InputState>>,
+}
+
+impl DiscussionsInner {
+    fn new(
+        window: &mut Window,
+        cx: &mut Context<Self>,
+        view: Entity<Discussions>,
+        discussions: Vec<Discussion>,
+    ) -> Self {
+        let discussions = discussions
+            .into_iter()
+            .filter(|discussion| discussion.notes.iter().any(|note| !note.system))
+            .collect::<Vec<Discussion>>();
+        let replies = discussions
+            .iter()
+            .map(|_| cx.new(|cx| InputState::new(window, cx).placeholder("Reply")))
+            .collect();
+
+        Self {
+            view,
+            discussions,
+            replies,
+        }
+    }
+
+    fn render_discussion(&self, index: usize, cx: &App) -> impl IntoElement {
+        let discussion = &self.discussions[index];
+        let reply = self.replies[index].clone();
+        let unresolved = discussion.is_unresolved();
+
+        v_flex()
+            .id(ElementId::Name(SharedString::new(discussion.id.clone())))
+            .pl_3()
+            .children(
+                discussion
+                    .notes
+                    .iter()
+                    .filter(|note| !note.system)
+                    .map(|note| {
+                        h_flex()
+                            .gap_1()
+                            .child(Label::new(note.author.name.clone()).font_semibold())
+                            .child(note.body.clone())
+                    }),
+            )
+            .child(
+                h_flex()
+                    .gap_1()
+                    .when(unresolved, |element| {
+                        element.child(
+                            Icon::new(IconName::TriangleAlert).text_color(cx.theme().warning),
+                        )
+                    })
+                    .when(discussion.is_resolvable(), |element| {
+                        let label = if unresolved {
+                            "Resolve thread"
+                        } else {
+                            "Unresolve thread"
+                        };
+
+                        element.child(Button::new("resolve").link().label(label).on_click({
+                            let view = self.view.clone();
+                            let discussion_id = discussion.id.clone();
+
+                            move |_, window, cx| {
+                                view.update(cx, |this, cx| {
+                                    this.resolve(window, cx, discussion_id.clone(), unresolved);
+                                });
+                            }
+                        }))
+                    })
+                    .child(Input::new(&reply))
+                    .child(Button::new("reply").label("Reply").on_click({
+                        let view = self.view.clone();
+                        let discussion_id = discussion.id.clone();
+
+                        move |_, window, cx| {
+                            let body = reply.read(cx).value().to_string();
+                            view.update(cx, |this, cx| {
+                                this.reply(window, cx, discussion_id.clone(), body);
+                            });
+                        }
+                    })),
+            )
+    }
+}
+
+impl Render for DiscussionsInner {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let mut groups: BTreeMap<Option<(String, Option<u64>)>, Vec<usize>> = BTreeMap::new();
+        for (index, discussion) in self.discussions.iter().enumerate() {
+            groups.entry(discussion.location()).or_default().push(index);
+        }
+
+        v_flex()
+            .gap_3()
+            .when(self.discussions.is_empty(), |element| {
+                element.child("n/a".to_string())
+            })
+            .children(groups.into_iter().map(|(location, indexes)| {
+                let title = match location {
+                    None => "General".to_string(),
+                    Some((path, Some(line))) => format!("{}:{}", path, line),
+                    Some((path, None)) => path,
+                };
+
+                v_flex()
+                    .gap_1()
+                    .child(
+                        h_flex()
+                            .child(Icon::new(IconName::File))
+                            .child(" ")
+                            .child(Label::new(title).font_semibold()),
+                    )
+                    .children(
+                        indexes
+                            .into_iter()
+                            .map(|index| self.render_discussion(index, cx))
+                            .collect::<Vec<_>>(),
+                    )
+            }))
+    }
+}
+
+pub async fn get_discussions(
+    gitlab: &GitlabClient,
+    project_id: u64,
+    merge_request_iid: u64,
+) -> Result<Vec<Discussion>, GitlabError> {
+    Ok(api::paged(
+        MergeRequestDiscussions::new(project_id, merge_request_iid),
+        api::Pagination::All,
+    )
+    .query_async(gitlab)
+    .await?)
+}
+
+async fn create_discussion(
+    gitlab: GitlabClient,
+    project_id: u64,
+    merge_request_iid: u64,
+    body: String,
+    location: Option<(String, u64)>,
+) -> Result<(), GitlabError> {
+    let position = match location {
+        Some((path, line)) => {
+            let (merge_request, diffs) = futures::join!(
+                async {
+                    let merge_request: Result<MergeRequest, _> =
+                        SingleMergeRequest::new(project_id, merge_request_iid)
+                            .query_async(&gitlab)
+                            .await;
+                    merge_request
+                },
+                async {
+                    let diffs: Result<Vec<FileDiff>, _> = api::paged(
+                        MergeRequestDiffs::new(project_id, merge_request_iid),
+                        api::Pagination::All,
+                    )
+                    .query_async(&gitlab)
+                    .await;
+                    diffs
+                }
+            );
+            let diff_refs = merge_request?
+                .diff_refs
+                .ok_or(GitlabError::MissingDiffRefs)?;
+            // Old path differs for a renamed file, old line is needed for an unchanged one
+            let diff = diffs?
+                .into_iter()
+                .find(|diff| diff.new_path == path)
+                .ok_or(GitlabError::FileNotChanged(path))?;
+            let line = diff.line(line);
+
+            Some(NewPosition::new(
+                diff_refs.base_sha,
+                diff_refs.start_sha,
+                diff_refs.head_sha,
+                diff.old_path,
+                diff.new_path,
+                line.old_line,
+                line.new_line,
+            ))
+        }
+        None => None,
+    };
+
+    api::ignore(CreateDiscussion::new(
+        project_id,
+        merge_request_iid,
+        body,
+        position,
+    ))
+    .query_async(&gitlab)
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,32 @@
+use derive_more::Constructor;
+use gitlab::api::{Endpoint, Pageable, QueryParams};
+
+/// Jobs of a pipeline, last attempt only unless `include_retried`
+#[derive(Debug, Constructor)]
+pub struct PipelineJobs {
+    project_id: u64,
+    pipeline_id: u64,
+    include_retried: bool,
+}
+
+impl Endpoint for PipelineJobs {
+    fn method(&self) -> reqwest::Method {
+        reqwest::Method::GET
+    }
+
+    fn endpoint(&self) -> std::borrow::Cow<'static, str> {
+        format!(
+            "projects/{}/pipelines/{}/jobs",
+            self.project_id, self.pipeline_id
+        )
+        .into()
+    }
+
+    fn parameters(&self) -> QueryParams<'_> {
+        let mut params = QueryParams::default();
+        params.push("include_retried", self.include_retried);
+        params
+    }
+}
+
+impl Pageable for PipelineJobs {}
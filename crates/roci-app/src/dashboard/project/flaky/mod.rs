@@ -0,0 +1,197 @@
+use futures::future::join_all;
+use gitlab::api::{self, projects, AsyncQuery};
+use gpui::{prelude::FluentBuilder, *};
+use gpui_component::{
+    button::{Button, ButtonVariants},
+    h_flex,
+    label::Label,
+    v_flex, ActiveTheme, IconName, StyledExt,
+};
+use roci_app_components::{error::WithButtonModalError, with_button_error, LoadState};
+use tracing_unwrap::ResultExt;
+
+use crate::{
+    dashboard::{error::GitlabError, project::endpoint::PipelineJobs},
+    state::gitlab::{
+        project::{
+            job::{FlakyJob, Job},
+            pipeline::Pipeline,
+        },
+        GitlabClient,
+    },
+    utils::short_datetime,
+};
+
+/// Last default branch pipelines whose jobs are analysed
+pub const FLAKY_PIPELINES_COUNT: usize = 30;
+/// Failures linked per flaky job
+pub const FLAKY_EXAMPLES_COUNT: usize = 3;
+
+pub struct FlakyJobs {
+    inner: Entity<LoadState<FlakyJobsInner, WithButtonModalError<GitlabError>>>,
+}
+
+impl FlakyJobs {
+    pub fn new(
+        window: &mut Window,
+        cx: &mut Context<Self>,
+        gitlab: GitlabClient,
+        project_id: u64,
+        default_branch: String,
+    ) -> Self {
+        cx.spawn_in(window, async move |this, cx| {
+            match get_jobs(gitlab, project_id, default_branch).await {
+                Ok((pipelines, jobs)) => {
+                    let _ = this.update_in(cx, |this, _window, cx| {
+                        let flaky_jobs = FlakyJob::rank(&jobs, FLAKY_EXAMPLES_COUNT);
+                        let inner = cx.new(|_cx| FlakyJobsInner::new(pipelines, flaky_jobs));
+                        this.inner = cx.new(|_cx| LoadState::Ready(inner));
+                        cx.notify();
+                    });
+                }
+                Err(error) => {
+                    let _ = this.update_in(cx, |this, _window, cx| {
+                        this.inner = cx.new(|cx| {
+                            with_button_error!(
+                                cx,
+                                "Load error".into(),
+                                format!("Error during load jobs"),
+                                error.into()
+                            )
+                        });
+                        cx.notify();
+                    });
+                }
+            }
+        })
+        .detach();
+
+        Self {
+            inner: cx.new(|_cx| LoadState::Loading),
+        }
+    }
+}
+
+impl Render for FlakyJobs {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        div().child(self.inner.clone())
+    }
+}
+
+pub struct FlakyJobsInner {
+    /// Analysed pipelines count
+    pipelines: usize,
+    flaky_jobs: Vec<FlakyJob>,
+}
+
+impl FlakyJobsInner {
+    fn new(pipelines: usize, flaky_jobs: Vec<FlakyJob>) -> Self {
+        Self {
+            pipelines,
+            flaky_jobs,
+        }
+    }
+}
+
+impl Render for FlakyJobsInner {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .gap_2()
+            .child(
+                Label::new(format!(
+                    "Jobs of last {} default branch pipelines, a job is flaky when it failed and \
+                    succeeded on a same commit or failed between two successful pipelines",
+                    self.pipelines
+                ))
+                .text_color(cx.theme().muted_foreground),
+            )
+            .when(self.flaky_jobs.is_empty(), |element| {
+                element.child("No flaky job found".to_string())
+            })
+            .children(self.flaky_jobs.iter().map(|flaky_job| {
+                v_flex()
+                    .id(ElementId::Name(SharedString::new(format!(
+                        "flaky-{}",
+                        flaky_job.name
+                    ))))
+                    .child(
+                        h_flex()
+                            .gap_1()
+                            .child(Label::new(flaky_job.name.clone()).font_semibold())
+                            .child(
+                                Label::new(format!("({})", flaky_job.stage))
+                                    .text_color(cx.theme().muted_foreground),
+                            )
+                            .child(format!(
+                                "{} failures in {} runs, {} commits with retries of another \
+                                outcome, {} isolated failures",
+                                flaky_job.failures,
+                                flaky_job.runs,
+                                flaky_job.retried_flips,
+                                flaky_job.isolated_failures
+                            )),
+                    )
+                    .child(
+                        h_flex()
+                            .gap_1()
+                            .children(flaky_job.examples.iter().map(|job: &Job| {
+                                let web_url = job.web_url.clone();
+
+                                Button::new(ElementId::Integer(job.id))
+                                    .link()
+                                    .icon(IconName::ExternalLink)
+                                    .label(format!(
+                                        "{} ({})",
+                                        job.id,
+                                        short_datetime(&job.created_at)
+                                    ))
+                                    .on_click(move |_, _, _| {
+                                        open::that(web_url.clone()).unwrap_or_log();
+                                    })
+                            })),
+                    )
+            }))
+    }
+}
+
+/// Analysed pipelines count and their jobs
+async fn get_jobs(
+    gitlab: GitlabClient,
+    project_id: u64,
+    default_branch: String,
+) -> Result<(usize, Vec<Job>), GitlabError> {
+    let pipelines_endpoint = projects::pipelines::Pipelines::builder()
+        .project(project_id)
+        .ref_(default_branch)
+        .build()?;
+    let pipelines: Vec<Pipeline> = api::paged(
+        pipelines_endpoint,
+        api::Pagination::Limit(FLAKY_PIPELINES_COUNT),
+    )
+    .query_async(&gitlab)
+    .await?;
+
+    let jobs = join_all(
+        pipelines
+            .iter()
+            .map(|pipeline| get_pipeline_jobs(&gitlab, project_id, pipeline.id)),
+    )
+    .await
+    .into_iter()
+    .collect::<Result<Vec<Vec<Job>>, GitlabError>>()?;
+
+    Ok((pipelines.len(), jobs.into_iter().flatten().collect()))
+}
+
+async fn get_pipeline_jobs(
+    gitlab: &GitlabClient,
+    project_id: u64,
+    pipeline_id: u64,
+) -> Result<Vec<Job>, GitlabError> {
+    Ok(api::paged(
+        PipelineJobs::new(project_id, pipeline_id, true),
+        api::Pagination::All,
+    )
+    .query_async(gitlab)
+    .await?)
+}
@@ -0,0 +1,27 @@
+use derive_more::Constructor;
+use gitlab::api::{BodyError, Endpoint, UrlBase};
+use serde_json::json;
+
+#[derive(Debug, Constructor)]
+pub struct GraphQl {
+    query: String,
+}
+
+impl Endpoint for GraphQl {
+    fn method(&self) -> reqwest::Method {
+        reqwest::Method::POST
+    }
+
+    fn endpoint(&self) -> std::borrow::Cow<'static, str> {
+        "api/graphql".into()
+    }
+
+    fn url_base(&self) -> UrlBase {
+        UrlBase::Instance
+    }
+
+    fn body(&self) -> Result<Option<(&'static str, Vec<u8>)>, BodyError> {
+        let body = serde_json::to_vec(&json!({ "query": self.query }))?;
+        Ok(Some(("application/json", body)))
+    }
+}
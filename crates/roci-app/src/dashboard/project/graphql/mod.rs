@@ -0,0 +1,223 @@
+use std::collections::HashMap;
+
+use gitlab::api::AsyncQuery;
+use serde::de::DeserializeOwned;
+
+use crate::{
+    config::merge_request::ShowMergeRequest,
+    dashboard::{
+        error::GitlabError,
+        project::{graphql::endpoint::GraphQl, pipeline::PIPELINES_COUNT},
+    },
+    state::gitlab::{
+        graphql::{ProjectDetailsNode, ProjectNode, ProjectsData, Response},
+        project::{merge_request::MergeRequestContainer, pipeline::Pipeline, Project},
+        GitlabClient,
+    },
+};
+
+mod endpoint;
+
+/// Projects asked in one query (maximum page size)
+pub const PROJECTS_PER_QUERY: usize = 100;
+/// Projects whose pipelines and merge requests are asked in one query, low enough to stay
+/// under Gitlab query complexity limit
+pub const DETAILS_PER_QUERY: usize = 5;
+/// Opened merge requests counted per project in table layout
+pub const TABLE_MERGE_REQUESTS: usize = 100;
+/// Last merge requests shown per project in list layout
+pub const LIST_MERGE_REQUESTS: usize = 25;
+/// Discussions checked for unresolved threads per merge request
+pub const DISCUSSIONS_PER_MERGE_REQUEST: usize = 100;
+
+const PROJECT_FIELDS: &str =
+    "id name fullPath webUrl lastActivityAt namespace { name fullPath } repository { rootRef }";
+const PIPELINE_FIELDS: &str =
+    "id iid status path createdAt ref startedAt finishedAt duration queuedDuration";
+const MERGE_REQUEST_FIELDS: &str =
+    "id iid title state webUrl createdAt updatedAt mergedAt detailedMergeStatus draft \
+    description diffRefs { baseSha headSha startSha }";
+
+/// What is fetched for each project
+#[derive(Debug, Clone)]
+pub enum Batch {
+    /// Last default branch pipeline and opened merge requests
+    Table,
+    /// Last default branch push pipelines and last merge requests with their discussions
+    List(ShowMergeRequest),
+}
+
+/// Same models as REST fetching
+pub struct ProjectData {
+    pub project: Project,
+    pub pipelines: Vec<Pipeline>,
+    pub merge_requests: Vec<MergeRequestContainer>,
+}
+
+/// Fetch given projects with a few GraphQL queries, projects not found (or not accessible)
+/// are missing from result
+pub async fn get_projects(
+    gitlab: &GitlabClient,
+    instance_url: &str,
+    project_ids: &[u64],
+    batch: &Batch,
+) -> Result<HashMap<u64, ProjectData>, GitlabError> {
+    let mut username = None;
+    let mut projects = vec![];
+    for chunk in project_ids.chunks(PROJECTS_PER_QUERY) {
+        let data: ProjectsData = query(gitlab, projects_query(chunk)).await?;
+        username = username.or(data.current_user.map(|user| user.username));
+        projects.extend(
+            data.projects
+                .nodes
+                .into_iter()
+                .filter_map(|project| Some((project.project_id()?, project))),
+        );
+    }
+
+    let mut projects_ = HashMap::new();
+    while !projects.is_empty() {
+        let chunk = projects
+            .drain(..projects.len().min(DETAILS_PER_QUERY))
+            .collect::<Vec<(u64, ProjectNode)>>();
+        let mut details: HashMap<String, Option<ProjectDetailsNode>> =
+            query(gitlab, details_query(&chunk, batch, username.as_deref())).await?;
+
+        for (project_id, project) in chunk {
+            let (pipelines, merge_requests) = details
+                .remove(&alias(project_id))
+                .flatten()
+                .map(|details| (details.pipelines, details.merge_requests))
+                .unwrap_or_default();
+
+            let mut pipelines = pipelines
+                .map(|pipelines| pipelines.nodes)
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|pipeline| pipeline.into_pipeline(project_id, instance_url))
+                .collect::<Vec<Pipeline>>();
+            pipelines.reverse();
+            let mut merge_requests = merge_requests
+                .map(|merge_requests| merge_requests.nodes)
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|merge_request| merge_request.into_container(project_id, instance_url))
+                .collect::<Vec<MergeRequestContainer>>();
+            merge_requests.reverse();
+
+            projects_.insert(
+                project_id,
+                ProjectData {
+                    project: project.into_project(),
+                    pipelines,
+                    merge_requests,
+                },
+            );
+        }
+    }
+
+    Ok(projects_)
+}
+
+async fn query<T: DeserializeOwned + Send + 'static>(
+    gitlab: &GitlabClient,
+    query: String,
+) -> Result<T, GitlabError> {
+    let response: Response<T> = GraphQl::new(query).query_async(gitlab).await?;
+    let messages = response
+        .errors
+        .iter()
+        .map(|error| error.message.clone())
+        .collect::<Vec<String>>()
+        .join(", ");
+
+    match response.data {
+        Some(data) => {
+            // Partial errors (eg. a project not accessible) leave other data usable
+            if !messages.is_empty() {
+                tracing::warn!("GraphQL errors: {}", messages);
+            }
+            Ok(data)
+        }
+        None => Err(GitlabError::GraphQl(messages)),
+    }
+}
+
+fn projects_query(project_ids: &[u64]) -> String {
+    let ids = project_ids
+        .iter()
+        .map(|project_id| string(&format!("gid://gitlab/Project/{}", project_id)))
+        .collect::<Vec<String>>()
+        .join(", ");
+
+    format!(
+        "{{ currentUser {{ username }} projects(ids: [{}], first: {}) {{ nodes {{ {} }} }} }}",
+        ids, PROJECTS_PER_QUERY, PROJECT_FIELDS
+    )
+}
+
+fn details_query(projects: &[(u64, ProjectNode)], batch: &Batch, username: Option<&str>) -> String {
+    let fields = projects
+        .iter()
+        .map(|(project_id, project)| {
+            let ref_ = string(&project.default_branch());
+            let (pipelines, merge_requests, discussions) = match batch {
+                Batch::Table => (
+                    format!("pipelines(ref: {}, first: 1)", ref_),
+                    format!(
+                        "mergeRequests(state: opened, first: {})",
+                        TABLE_MERGE_REQUESTS
+                    ),
+                    String::new(),
+                ),
+                Batch::List(show) => {
+                    let author = match (show, username) {
+                        (ShowMergeRequest::OnlyMine, Some(username)) => {
+                            format!(", authorUsername: {}", string(username))
+                        }
+                        _ => String::new(),
+                    };
+                    (
+                        format!(
+                            "pipelines(ref: {}, source: \"push\", first: {})",
+                            ref_, PIPELINES_COUNT
+                        ),
+                        format!(
+                            "mergeRequests(first: {}, sort: CREATED_DESC{})",
+                            LIST_MERGE_REQUESTS, author
+                        ),
+                        format!(
+                            "discussions(first: {}) {{ nodes {{ resolvable resolved }} }}",
+                            DISCUSSIONS_PER_MERGE_REQUEST
+                        ),
+                    )
+                }
+            };
+
+            format!(
+                "{}: project(fullPath: {}) {{ {} {{ nodes {{ {} }} }} \
+                {} {{ nodes {{ {} headPipeline {{ {} }} {} }} }} }}",
+                alias(*project_id),
+                string(&project.full_path),
+                pipelines,
+                PIPELINE_FIELDS,
+                merge_requests,
+                MERGE_REQUEST_FIELDS,
+                PIPELINE_FIELDS,
+                discussions
+            )
+        })
+        .collect::<Vec<String>>()
+        .join(" ");
+
+    format!("{{ {} }}", fields)
+}
+
+fn alias(project_id: u64) -> String {
+    format!("p{}", project_id)
+}
+
+/// GraphQL string literal (JSON escaping is compatible)
+fn string(value: &str) -> String {
+    serde_json::Value::from(value).to_string()
+}
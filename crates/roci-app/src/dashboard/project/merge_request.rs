@@ -1,19 +1,28 @@
+use derive_more::Constructor;
+use futures::future::join_all;
 use gitlab::api::{self, AsyncQuery};
-use gitlab::AsyncGitlab;
-use gpui::*;
+use gpui::{prelude::FluentBuilder, *};
 use gpui_component::button::{Button, ButtonVariants};
-use gpui_component::{h_flex, ActiveTheme, Icon, IconName};
+use gpui_component::label::Label;
+use gpui_component::{h_flex, ActiveTheme, WindowExt};
 use roci_app_components::error::WithButtonModalError;
 use roci_app_components::{with_button_error, LoadState};
 use tracing_unwrap::ResultExt;
 
 use crate::config::merge_request::ShowMergeRequest;
+use crate::config::status::{Severity, StatusKind};
 use crate::dashboard::error::GitlabError;
+use crate::dashboard::project::discussion::{get_discussions, Discussions};
+use crate::dashboard::project::pipeline::status_style;
+use crate::state::gitlab::project::merge_request::MergeRequest;
 use crate::state::gitlab::project::merge_request::MergeRequestContainer;
-use crate::state::gitlab::project::merge_request::{MergeRequestState, MERGE_STATUS_MERGEABLE};
+use crate::state::gitlab::project::merge_request::MergeRequestState;
 use crate::state::gitlab::project::pipeline::Pipeline;
 use crate::state::gitlab::user::User;
+use crate::state::gitlab::GitlabClient;
+use crate::state::navigation::{NavItem, NavKey, NavKind};
 use crate::state::AppState;
+use crate::utils::{age, days_since};
 
 pub struct MergeRequests(Entity<LoadState<MergeRequestsInner, WithButtonModalError<GitlabError>>>);
 
@@ -21,19 +30,25 @@ impl MergeRequests {
     pub fn new(
         window: &mut Window,
         cx: &mut Context<Self>,
-        gitlab: AsyncGitlab,
+        gitlab: GitlabClient,
         project_id: u64,
     ) -> Self {
         let show = AppState::global(cx).config().show_merge_request.clone();
 
         cx.spawn_in(
             window,
-            async move |merge_requests, cx| match get_merge_requests(gitlab, project_id, show).await
+            async move |merge_requests, cx| match get_merge_requests(
+                gitlab.clone(),
+                project_id,
+                show,
+            )
+            .await
             {
                 Ok(merge_requests_) => {
                     let _ = merge_requests.update_in(cx, |merge_requests, _window, cx| {
-                        let merge_requests_ =
-                            LoadState::Ready(cx.new(|_cx| MergeRequestsInner(merge_requests_)));
+                        let merge_requests_ = LoadState::Ready(
+                            cx.new(|_cx| MergeRequestsInner::new(gitlab, merge_requests_)),
+                        );
                         let merge_requests_ = cx.new(|_cx| merge_requests_);
                         merge_requests.0 = merge_requests_;
                     });
@@ -56,6 +71,58 @@ impl MergeRequests {
 
         Self(cx.new(|_cx| LoadState::Loading))
     }
+
+    /// Merge requests fetched by a batch
+    pub fn loaded(
+        cx: &mut Context<Self>,
+        gitlab: GitlabClient,
+        merge_requests: Vec<MergeRequestContainer>,
+    ) -> Self {
+        let merge_requests =
+            LoadState::Ready(cx.new(|_cx| MergeRequestsInner::new(gitlab, merge_requests)));
+
+        Self(cx.new(|_cx| merge_requests))
+    }
+
+    pub fn nav_items(&self, host: &str, cx: &App) -> Vec<NavItem> {
+        let LoadState::Ready(merge_requests) = self.0.read(cx) else {
+            return vec![];
+        };
+
+        merge_requests
+            .read(cx)
+            .merge_requests
+            .iter()
+            .filter(|merge_request| matches!(merge_request.state, MergeRequestState::Opened))
+            .map(|merge_request| NavItem {
+                key: NavKey::new(NavKind::MergeRequest, merge_request.web_url.clone()),
+                host: host.to_string(),
+                id: merge_request.id,
+            })
+            .collect()
+    }
+
+    /// Detailed merge status of each opened merge request
+    pub fn statuses(&self, cx: &App) -> Vec<String> {
+        self.opened(cx)
+            .into_iter()
+            .map(|merge_request| merge_request.detailed_merge_status)
+            .collect()
+    }
+
+    pub fn opened(&self, cx: &App) -> Vec<MergeRequest> {
+        let LoadState::Ready(merge_requests) = self.0.read(cx) else {
+            return vec![];
+        };
+
+        merge_requests
+            .read(cx)
+            .merge_requests
+            .iter()
+            .filter(|merge_request| matches!(merge_request.state, MergeRequestState::Opened))
+            .map(|merge_request| (**merge_request).clone())
+            .collect()
+    }
 }
 
 impl Render for MergeRequests {
@@ -64,52 +131,130 @@ impl Render for MergeRequests {
     }
 }
 
-pub struct MergeRequestsInner(Vec<MergeRequestContainer>);
+#[derive(Constructor)]
+pub struct MergeRequestsInner {
+    gitlab: GitlabClient,
+    merge_requests: Vec<MergeRequestContainer>,
+}
 
 impl Render for MergeRequestsInner {
     fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let state = AppState::global(cx);
+        let fade_after_days = state.config().merge_request_aging.fade_after_days as i64;
+
         h_flex()
             .id("merge_requests")
-            .children(self.0.iter().filter_map(|merge_request| {
-                let forced_color = merge_request
+            .children(self.merge_requests.iter().filter_map(|merge_request| {
+                let pipeline_failing = merge_request
                     .last_pipeline
                     .as_ref()
-                    .filter(|p| p.status.is_error())
-                    .map(|_| cx.theme().red);
-                let icon = match merge_request.state {
-                    MergeRequestState::Opened => {
-                        if &merge_request.detailed_merge_status == MERGE_STATUS_MERGEABLE {
-                            Some((
-                                IconName::LayoutDashboard,
-                                forced_color.unwrap_or(cx.theme().green),
-                            ))
-                        } else {
-                            Some((
-                                IconName::LayoutDashboard,
-                                forced_color.unwrap_or(cx.theme().cyan),
-                            ))
-                        }
-                    }
+                    .is_some_and(|p| status_style(p.status, cx).severity == Severity::Error);
+                let style = match merge_request.state {
+                    MergeRequestState::Opened if pipeline_failing => Some(
+                        state
+                            .config()
+                            .status_styles
+                            .style(StatusKind::FailingPipeline),
+                    ),
+                    MergeRequestState::Opened => Some(state.config().status_styles.style(
+                        StatusKind::of_merge_request(&merge_request.detailed_merge_status),
+                    )),
                     _ => None,
                 };
                 let web_url = merge_request.web_url.clone();
+                let old = matches!(merge_request.state, MergeRequestState::Opened)
+                    && days_since(&merge_request.created_at)
+                        .is_some_and(|days| days >= fade_after_days);
+                let selected = state.is_selected(&NavKey::new(
+                    NavKind::MergeRequest,
+                    merge_request.web_url.clone(),
+                ));
+                let unresolved = merge_request.unresolved_discussions;
+                let unresolved_color = if unresolved.is_some_and(|unresolved| unresolved > 0) {
+                    cx.theme().warning
+                } else {
+                    cx.theme().muted_foreground
+                };
 
-                if let Some((icon, color)) = icon {
+                if let Some(style) = style {
                     Some(
-                        div().id(ElementId::Integer(merge_request.id)).child(
-                            div().child(
-                                Button::new("merge_request-icon")
-                                    .icon(Icon::new(icon).text_color(color))
-                                    .link()
-                                    .tooltip(format!(
-                                        "{} ({})",
-                                        merge_request.title, merge_request.detailed_merge_status
-                                    ))
-                                    .on_click(move |_, _, _| {
-                                        open::that(web_url.clone()).unwrap_or_log();
-                                    }),
+                        div()
+                            .id(ElementId::Integer(merge_request.id))
+                            .when(selected, |element| element.bg(cx.theme().accent))
+                            .child(
+                                h_flex()
+                                    .child(
+                                        div().when(old, |element| element.opacity(0.5)).child(
+                                            Button::new("merge_request-icon")
+                                                .icon(style.icon(cx))
+                                                .link()
+                                                .tooltip(format!(
+                                                    "{} ({}, opened {} ago)",
+                                                    merge_request.title,
+                                                    merge_request.detailed_merge_status,
+                                                    age(&merge_request.created_at)
+                                                ))
+                                                .on_click(move |_, _, _| {
+                                                    open::that(web_url.clone()).unwrap_or_log();
+                                                }),
+                                        ),
+                                    )
+                                    .when(old, |element| {
+                                        element.child(
+                                            Label::new(age(&merge_request.created_at))
+                                                .text_xs()
+                                                .text_color(cx.theme().muted_foreground),
+                                        )
+                                    })
+                                    .child(
+                                        Button::new("merge_request-discussions")
+                                            .link()
+                                            .child(
+                                                Label::new(
+                                                    unresolved
+                                                        .map(|unresolved| unresolved.to_string())
+                                                        .unwrap_or("?".to_string()),
+                                                )
+                                                .text_xs()
+                                                .text_color(unresolved_color),
+                                            )
+                                            .tooltip(match unresolved {
+                                                Some(unresolved) => {
+                                                    format!("{} unresolved threads", unresolved)
+                                                }
+                                                None => "Unknown unresolved threads".to_string(),
+                                            })
+                                            .on_click({
+                                                let gitlab = self.gitlab.clone();
+                                                let title = merge_request.title.clone();
+                                                let project_id = merge_request.project_id;
+                                                let merge_request_iid = merge_request.iid;
+
+                                                move |_, window, cx| {
+                                                    let discussions = cx.new(|cx| {
+                                                        Discussions::new(
+                                                            window,
+                                                            cx,
+                                                            gitlab.clone(),
+                                                            project_id,
+                                                            merge_request_iid,
+                                                        )
+                                                    });
+                                                    let title = title.clone();
+
+                                                    window.open_dialog(
+                                                        cx,
+                                                        move |dialog, _window, _cx| {
+                                                            dialog
+                                                                .title(title.clone())
+                                                                .width(px(900.))
+                                                                .child(discussions.clone())
+                                                        },
+                                                    );
+                                                }
+                                            }),
+                                    ),
                             ),
-                        ),
                     )
                 } else {
                     None
@@ -119,7 +264,7 @@ impl Render for MergeRequestsInner {
 }
 
 async fn get_merge_requests(
-    gitlab: AsyncGitlab,
+    gitlab: GitlabClient,
     project_id: u64,
     show: ShowMergeRequest,
 ) -> Result<Vec<MergeRequestContainer>, GitlabError> {
@@ -140,17 +285,43 @@ async fn get_merge_requests(
             .await?;
     merge_requests.reverse();
 
-    let mut merge_requests_ = vec![];
-    for merge_request in merge_requests.into_iter() {
-        let last_pipeline = get_last_pipeline(&gitlab, project_id, merge_request.iid).await?;
-        merge_requests_.push(MergeRequestContainer::new(merge_request, last_pipeline));
-    }
+    join_all(merge_requests.into_iter().map(|merge_request| {
+        let gitlab = &gitlab;
+        async move {
+            let (last_pipeline, discussions) = futures::join!(
+                get_last_pipeline(gitlab, project_id, merge_request.iid),
+                get_discussions(gitlab, project_id, merge_request.iid)
+            );
+            // Unknown count rather than a failing block (eg. forbidden discussions)
+            let unresolved_discussions = discussions
+                .inspect_err(|error| {
+                    tracing::warn!(
+                        "Unable to get discussions of merge request !{} of project {}: {}",
+                        merge_request.iid,
+                        project_id,
+                        error
+                    )
+                })
+                .ok()
+                .map(|discussions| {
+                    discussions
+                        .iter()
+                        .filter(|discussion| discussion.is_unresolved())
+                        .count()
+                });
 
-    Ok(merge_requests_)
+            last_pipeline.map(|last_pipeline| {
+                MergeRequestContainer::new(merge_request, last_pipeline, unresolved_discussions)
+            })
+        }
+    }))
+    .await
+    .into_iter()
+    .collect()
 }
 
-async fn get_last_pipeline(
-    gitlab: &AsyncGitlab,
+pub async fn get_last_pipeline(
+    gitlab: &GitlabClient,
     project_id: u64,
     merge_request_iid: u64,
 ) -> Result<Option<Pipeline>, GitlabError> {
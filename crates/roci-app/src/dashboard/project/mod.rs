@@ -1,6 +1,8 @@
+use std::collections::HashMap;
+
 use derive_more::Constructor;
-use gitlab::{api::AsyncQuery, AsyncGitlab};
-use gpui::*;
+use gitlab::api::AsyncQuery;
+use gpui::{prelude::FluentBuilder, *};
 use gpui_component::{
     button::{Button, ButtonVariants},
     form::{field, v_form},
@@ -8,26 +10,67 @@ use gpui_component::{
     input::{Input, InputState},
     label::Label,
     notification::NotificationType,
-    Icon, IconName, WindowExt,
+    switch::Switch,
+    v_flex, ActiveTheme, Icon, IconName, WindowExt,
+};
+use roci_app_components::{
+    badge::badge, bool::BooleanState, error::WithButtonModalError, list::List, with_button_error,
+    LoadState,
 };
-use roci_app_components::{error::WithButtonModalError, list::List, with_button_error, LoadState};
 use tracing_unwrap::ResultExt;
 
 use crate::{
+    api::snapshot::ProjectSnapshot,
+    config::{
+        gitlab_::Fetcher, layout::ALL_INSTANCES, project::ProjectLayout, refresh::RefreshEvery,
+    },
     dashboard::{
         error::GitlabError,
-        project::{merge_request::MergeRequests, pipeline::Pipelines},
+        project::{
+            deployment::Deployments,
+            flaky::FlakyJobs,
+            graphql::{get_projects, Batch, ProjectData},
+            merge_request::MergeRequests,
+            pipeline::Pipelines,
+            pipeline_log::PipelineLog,
+            release::Releases,
+            table::ProjectsTable,
+            window::ProjectPopOut,
+        },
+        refresh::wait_refresh,
+    },
+    popout::{self, PopOutKey},
+    state::{
+        gitlab::{project::pipeline::Pipeline, GitlabClient},
+        navigation::{NavItem, NavKey, NavKind},
+        refresh::RefreshKey,
+        AppState,
     },
-    state::AppState,
+    tray::TrayItem,
+    utils::project_path,
 };
 
+mod deployment;
+mod discussion;
+mod endpoint;
+mod flaky;
+mod graphql;
 mod merge_request;
 mod pipeline;
+mod pipeline_log;
+mod release;
+mod table;
+mod window;
 
 pub struct Projects {
     inner: Entity<LoadState<List<Project>, WithButtonModalError<GitlabError>>>,
+    table: Option<Entity<ProjectsTable>>,
     config: crate::config::gitlab_::Gitlab,
     new_project_ids: Entity<InputState>,
+    new_refresh_every: Entity<InputState>,
+    new_project_refresh_every: Entity<InputState>,
+    new_graphql: Entity<BooleanState>,
+    _batch: Option<Task<()>>,
 }
 
 impl Projects {
@@ -42,17 +85,44 @@ impl Projects {
             .map(|v| v.to_string())
             .collect::<Vec<String>>()
             .join(",");
+        let refresh_every = match &config.refresh_every {
+            Some(refresh_every) => refresh_every.duration().as_secs().to_string(),
+            None => String::new(),
+        };
+        let mut project_refresh_every = config
+            .project_refresh_every
+            .iter()
+            .map(|(project_id, refresh_every)| {
+                format!("{}={}", project_id, refresh_every.duration().as_secs())
+            })
+            .collect::<Vec<String>>();
+        project_refresh_every.sort();
+        let project_refresh_every = project_refresh_every.join(",");
+        let graphql = config.fetcher == Fetcher::GraphQl;
 
         Self::init(window, cx, config.clone());
 
         Self {
             inner: cx.new(|_cx| LoadState::Loading),
+            table: None,
             config,
             new_project_ids: cx.new(|cx| {
                 let mut value = InputState::new(window, cx);
                 value.set_value(SharedString::new(project_ids), window, cx);
                 value
             }),
+            new_refresh_every: cx.new(|cx| {
+                let mut value = InputState::new(window, cx).placeholder("Default");
+                value.set_value(SharedString::new(refresh_every), window, cx);
+                value
+            }),
+            new_project_refresh_every: cx.new(|cx| {
+                let mut value = InputState::new(window, cx);
+                value.set_value(SharedString::new(project_refresh_every), window, cx);
+                value
+            }),
+            new_graphql: cx.new(|_cx| BooleanState::new(graphql)),
+            _batch: None,
         }
     }
 
@@ -60,11 +130,43 @@ impl Projects {
         let host = config.host.clone();
         let gitlabs = AppState::global(cx).gitlabs();
         let instance_url = format!("{}{}", config.protocol(), host);
+        let layout = AppState::global(cx).config().project_layout.clone();
 
         cx.spawn_in(window, async move |projects, cx| {
             match gitlabs.get(&config).await {
+                Ok(gitlab) if layout == ProjectLayout::Table => {
+                    let _ = projects.update_in(cx, |projects, window, cx| {
+                        let project_ids = config.project_ids.clone();
+                        projects._batch = None;
+                        projects.table = Some(cx.new(|cx| {
+                            ProjectsTable::new(
+                                window,
+                                cx,
+                                host.clone(),
+                                instance_url.clone(),
+                                gitlab,
+                                project_ids,
+                                config.fetcher,
+                            )
+                        }));
+
+                        cx.notify();
+                    });
+                }
+                Ok(gitlab) if config.fetcher == Fetcher::GraphQl => {
+                    let _ = projects.update_in(cx, |projects, window, cx| {
+                        projects._batch = Some(Self::batch(
+                            window,
+                            cx,
+                            config.clone(),
+                            gitlab,
+                            instance_url.clone(),
+                        ));
+                    });
+                }
                 Ok(gitlab) => {
                     let _ = projects.update_in(cx, |projects, window, cx| {
+                        projects._batch = None;
                         let projects_ =
                             Self::projects(window, cx, config, gitlab, instance_url.clone());
                         let projects_ = cx.new(|_cx| projects_);
@@ -92,13 +194,103 @@ impl Projects {
         .detach();
     }
 
+    /// Load every listed project with a few GraphQL queries, again on each refresh
+    fn batch(
+        window: &mut Window,
+        cx: &mut Context<Self>,
+        config: crate::config::gitlab_::Gitlab,
+        gitlab: GitlabClient,
+        instance_url: String,
+    ) -> Task<()> {
+        let refresh_key = RefreshKey::Projects(config.host.clone());
+        let batch = Batch::List(AppState::global(cx).config().show_merge_request.clone());
+
+        cx.spawn_in(window, async move |projects, cx| loop {
+            let result = get_projects(&gitlab, &instance_url, &config.project_ids, &batch).await;
+
+            let updated = projects.update_in(cx, |projects, window, cx| {
+                match result {
+                    Ok(mut data) => {
+                        AppState::record_pipelines(
+                            cx,
+                            config.host.clone(),
+                            data.values()
+                                .flat_map(|data| data.pipelines.clone())
+                                .collect(),
+                        );
+                        AppState::global_mut(cx).refreshes_mut().loaded(
+                            &refresh_key,
+                            fingerprint(&data),
+                            data.values().any(|data| {
+                                data.pipelines
+                                    .iter()
+                                    .any(|pipeline| pipeline.status.is_running())
+                            }),
+                        );
+                        let parent = cx.entity().downgrade();
+                        let projects_ = config
+                            .project_ids
+                            .iter()
+                            .map(|project_id| {
+                                cx.new(|cx| match data.remove(project_id) {
+                                    Some(data) => Project::loaded(
+                                        window,
+                                        cx,
+                                        parent.clone(),
+                                        &config.host,
+                                        &instance_url,
+                                        gitlab.clone(),
+                                        *project_id,
+                                        data,
+                                    ),
+                                    None => Project::failed(
+                                        cx,
+                                        *project_id,
+                                        GitlabError::GraphQl(format!(
+                                            "Project {} not found",
+                                            project_id
+                                        )),
+                                    ),
+                                })
+                            })
+                            .collect::<Vec<Entity<Project>>>();
+                        let projects_ = cx.new(|_cx| List(projects_));
+                        projects.inner = cx.new(|_cx| LoadState::Ready(projects_));
+                    }
+                    Err(error) => {
+                        AppState::global_mut(cx)
+                            .refreshes_mut()
+                            .failed(&refresh_key);
+                        projects.inner = cx.new(|cx| {
+                            with_button_error!(
+                                cx,
+                                "Load error".into(),
+                                format!("Error during load projects of {}", config.host),
+                                error.into()
+                            )
+                        });
+                    }
+                }
+
+                cx.notify();
+            });
+            if updated.is_err() {
+                break;
+            }
+
+            wait_refresh(cx, &refresh_key).await;
+        })
+    }
+
     fn projects(
         window: &mut Window,
         cx: &mut Context<Self>,
         config: crate::config::gitlab_::Gitlab,
-        gitlab: AsyncGitlab,
+        gitlab: GitlabClient,
         instance_url: String,
     ) -> List<Project> {
+        let parent = cx.entity().downgrade();
+
         List(
             config
                 .project_ids
@@ -108,6 +300,8 @@ impl Projects {
                         Project::new(
                             window,
                             cx,
+                            parent.clone(),
+                            config.host.clone(),
                             instance_url.clone(),
                             gitlab.clone(),
                             *project_id,
@@ -118,29 +312,205 @@ impl Projects {
         )
     }
 
+    pub fn nav_items(&self, cx: &App) -> Vec<NavItem> {
+        if let Some(table) = &self.table {
+            return table.read(cx).nav_items(&self.config.host, cx);
+        }
+        let LoadState::Ready(projects) = self.inner.read(cx) else {
+            return vec![];
+        };
+
+        projects
+            .read(cx)
+            .0
+            .iter()
+            .flat_map(|project| project.read(cx).nav_items(&self.config.host, cx))
+            .collect()
+    }
+
+    /// Update listed project pipelines with a pipeline received by webhook
+    pub fn apply_pipeline(
+        &mut self,
+        host: &str,
+        project_id: u64,
+        ref_: &str,
+        pipeline: &Pipeline,
+        cx: &mut Context<Self>,
+    ) {
+        if self.config.host != host {
+            return;
+        }
+        let LoadState::Ready(projects) = self.inner.read(cx) else {
+            return;
+        };
+
+        let pipelines = projects
+            .read(cx)
+            .0
+            .iter()
+            .filter_map(|project| {
+                let LoadState::Ready(project) = project.read(cx).0.read(cx) else {
+                    return None;
+                };
+                let project = project.read(cx);
+                (project.project_id == project_id && project.default_branch == ref_)
+                    .then(|| project.pipelines.clone())
+            })
+            .collect::<Vec<Entity<Pipelines>>>();
+
+        for pipelines in pipelines {
+            pipelines.update(cx, |pipelines, cx| pipelines.apply(pipeline.clone(), cx));
+        }
+    }
+
+    pub fn failing_pipelines(&self, cx: &App) -> Vec<TrayItem> {
+        if let Some(table) = &self.table {
+            return table.read(cx).failing_pipelines(cx);
+        }
+        let LoadState::Ready(projects) = self.inner.read(cx) else {
+            return vec![];
+        };
+
+        projects
+            .read(cx)
+            .0
+            .iter()
+            .filter_map(|project| project.read(cx).failing_pipeline(cx))
+            .collect()
+    }
+
+    /// Last default branch pipeline of each project with its full path
+    pub fn last_pipelines(&self, cx: &App) -> Vec<(String, Pipeline)> {
+        if let Some(table) = &self.table {
+            return table.read(cx).last_pipelines(cx);
+        }
+        let LoadState::Ready(projects) = self.inner.read(cx) else {
+            return vec![];
+        };
+
+        projects
+            .read(cx)
+            .0
+            .iter()
+            .filter_map(|project| project.read(cx).last_pipeline(cx))
+            .collect()
+    }
+
+    /// Detailed merge status of each displayed opened merge request
+    pub fn merge_request_statuses(&self, cx: &App) -> Vec<String> {
+        if let Some(table) = &self.table {
+            return table.read(cx).merge_request_statuses(cx);
+        }
+        let LoadState::Ready(projects) = self.inner.read(cx) else {
+            return vec![];
+        };
+
+        projects
+            .read(cx)
+            .0
+            .iter()
+            .flat_map(|project| project.read(cx).merge_request_statuses(cx))
+            .collect()
+    }
+
+    /// Displayed projects with their last pipeline and opened merge requests
+    pub fn snapshots(&self, cx: &App) -> Vec<ProjectSnapshot> {
+        if let Some(table) = &self.table {
+            return table.read(cx).snapshots(cx);
+        }
+        let LoadState::Ready(projects) = self.inner.read(cx) else {
+            return vec![];
+        };
+
+        projects
+            .read(cx)
+            .0
+            .iter()
+            .filter_map(|project| project.read(cx).snapshot(cx))
+            .collect()
+    }
+
+    pub fn host(&self) -> &str {
+        &self.config.host
+    }
+
+    /// Pop out view of `project_id`, following `projects` batches
+    pub fn pop_out_view(cx: &mut App, projects: Entity<Self>, project_id: u64) -> AnyView {
+        cx.new(|cx| ProjectPopOut::new(cx, projects, project_id))
+            .into()
+    }
+
+    /// Project of card layout, projects are listed in configured order
+    fn project(&self, project_id: u64, cx: &App) -> Option<Entity<Project>> {
+        let LoadState::Ready(projects) = self.inner.read(cx) else {
+            return None;
+        };
+        let index = self
+            .config
+            .project_ids
+            .iter()
+            .position(|project_id_| *project_id_ == project_id)?;
+
+        projects.read(cx).0.get(index).cloned()
+    }
+
     fn show_project_ids_dialog(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         let view = cx.entity().clone();
         let project_ids = self.new_project_ids.clone();
+        let refresh_every = self.new_refresh_every.clone();
+        let project_refresh_every = self.new_project_refresh_every.clone();
+        let graphql = self.new_graphql.clone();
+
+        window.open_dialog(cx, move |dialog, _window, cx| {
+            let graphql_ = graphql.read(cx);
 
-        window.open_dialog(cx, move |dialog, _window, _cx| {
             dialog
                 .title("Configure projects")
                 .child(
-                    v_form().child(
-                        field()
-                            .label("Fill projects ids separated by comma (eg. `1,42,756`)")
-                            .child(Input::new(&project_ids)),
-                    ),
+                    v_form()
+                        .child(
+                            field()
+                                .label("Fill projects ids separated by comma (eg. `1,42,756`)")
+                                .child(Input::new(&project_ids)),
+                        )
+                        .child(
+                            field()
+                                .label("Refresh every (seconds, empty for global setting)")
+                                .child(Input::new(&refresh_every)),
+                        )
+                        .child(
+                            field()
+                                .label("Projects refresh in seconds (eg. `42=60,756=3600`)")
+                                .child(Input::new(&project_refresh_every)),
+                        )
+                        .child(
+                            field().label("Batched GraphQL fetching").child(
+                                Switch::new("graphql").checked(graphql_.get()).on_click({
+                                    let graphql = graphql.clone();
+                                    move |_, _window, cx| {
+                                        graphql.update(cx, |this, _cx| {
+                                            this.toggle();
+                                        });
+                                    }
+                                }),
+                            ),
+                        ),
                 )
                 .footer({
                     let view = view.clone();
                     let project_ids = project_ids.clone();
+                    let refresh_every = refresh_every.clone();
+                    let project_refresh_every = project_refresh_every.clone();
+                    let graphql = graphql.clone();
 
                     move |_, _, _, _cx| {
                         vec![
                             Button::new("confirm").primary().label("Confirm").on_click({
                                 let view = view.clone();
                                 let project_ids = project_ids.clone();
+                                let refresh_every = refresh_every.clone();
+                                let project_refresh_every = project_refresh_every.clone();
+                                let graphql = graphql.clone();
 
                                 move |_, window, cx| {
                                     view.update(cx, |view, cx| {
@@ -158,6 +528,25 @@ impl Projects {
                                                 .into_iter()
                                                 .filter_map(|raw| raw.parse::<u64>().ok())
                                                 .collect::<Vec<u64>>();
+                                            gitlab.refresh_every =
+                                                RefreshEvery::parse(&refresh_every.read(cx).value());
+                                            gitlab.project_refresh_every = project_refresh_every
+                                                .read(cx)
+                                                .value()
+                                                .split(",")
+                                                .filter_map(|raw| {
+                                                    let (project_id, seconds) =
+                                                        raw.split_once("=")?;
+                                                    Some((
+                                                        project_id.trim().parse::<u64>().ok()?,
+                                                        RefreshEvery::parse(seconds)?,
+                                                    ))
+                                                })
+                                                .collect();
+                                            gitlab.fetcher = match graphql.read(cx).get() {
+                                                true => Fetcher::GraphQl,
+                                                false => Fetcher::Rest,
+                                            };
                                             let gitlab_ = gitlab.clone();
 
                                             if let Err(error) =
@@ -167,7 +556,7 @@ impl Projects {
                                                     (
                                                         NotificationType::Error,
                                                         SharedString::new(format!(
-                                                            "Error during project ids list save: {}",
+                                                            "Error during projects configuration save: {}",
                                                             error.to_string()
                                                         )),
                                                     ),
@@ -175,7 +564,7 @@ impl Projects {
                                                 );
                                             } else {
                                                 window.push_notification(
-                                                    SharedString::new("Project ids list saved"),
+                                                    SharedString::new("Projects configuration saved"),
                                                     cx,
                                                 );
 
@@ -219,7 +608,118 @@ impl Render for Projects {
                             })),
                     ),
             )
-            .child(self.inner.clone())
+            .when_some(self.table.clone(), |element, table| element.child(table))
+            .when(self.table.is_none(), |element| {
+                element.child(self.inner.clone())
+            })
+    }
+}
+
+/// Projects of every instance, most recently active first
+pub struct UnifiedProjects {
+    sources: Vec<Entity<Projects>>,
+}
+
+impl UnifiedProjects {
+    pub fn new(cx: &mut Context<Self>, sources: Vec<Entity<Projects>>) -> Self {
+        for source in &sources {
+            cx.observe(source, |_this, _source, cx| cx.notify())
+                .detach();
+        }
+
+        Self { sources }
+    }
+
+    /// Listed projects with their host (table layout ones excluded)
+    fn projects(&self, cx: &App) -> Vec<(String, Entity<Project>)> {
+        let mut projects = vec![];
+        for source in &self.sources {
+            let source = source.read(cx);
+            if let (None, LoadState::Ready(list)) = (&source.table, source.inner.read(cx)) {
+                projects.extend(
+                    list.read(cx)
+                        .0
+                        .iter()
+                        .map(|project| (source.config.host.clone(), project.clone())),
+                );
+            }
+        }
+        projects.sort_by_cached_key(|(_, project)| {
+            std::cmp::Reverse(project.read(cx).last_activity_at(cx))
+        });
+
+        projects
+    }
+
+    pub fn nav_items(&self, cx: &App) -> Vec<NavItem> {
+        let mut items = vec![];
+        for source in &self.sources {
+            if let Some(table) = &source.read(cx).table {
+                items.extend(table.read(cx).nav_items(&source.read(cx).config.host, cx));
+            }
+        }
+        for (host, project) in self.projects(cx) {
+            items.extend(project.read(cx).nav_items(&host, cx));
+        }
+
+        items
+    }
+}
+
+impl Render for UnifiedProjects {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let projects = self.projects(cx);
+
+        div()
+            .child(
+                h_flex()
+                    .child(Icon::new(IconName::ArrowRight))
+                    .child(Label::new(format!("{} projects", ALL_INSTANCES)).text_xl())
+                    .children(self.sources.iter().map(|source| {
+                        let host = source.read(cx).config.host.clone();
+                        let source = source.clone();
+
+                        h_flex()
+                            .id(SharedString::new(host.clone()))
+                            .pl_2()
+                            .child(badge(host, cx))
+                            .child(
+                                Button::new("configure-projects")
+                                    .icon(IconName::Settings)
+                                    .link()
+                                    .on_click(move |_, window, cx| {
+                                        source.update(cx, |this, cx| {
+                                            this.show_project_ids_dialog(window, cx)
+                                        });
+                                    }),
+                            )
+                    })),
+            )
+            .children(self.sources.iter().filter_map(|source| {
+                let source = source.read(cx);
+
+                match (&source.table, source.inner.read(cx)) {
+                    (Some(table), _) => Some(
+                        v_flex()
+                            .child(badge(source.config.host.clone(), cx))
+                            .child(table.clone())
+                            .into_any_element(),
+                    ),
+                    (None, LoadState::Ready(_)) => None,
+                    (None, _) => Some(
+                        h_flex()
+                            .gap_1()
+                            .child(badge(source.config.host.clone(), cx))
+                            .child(source.inner.clone())
+                            .into_any_element(),
+                    ),
+                }
+            }))
+            .children(
+                projects
+                    .into_iter()
+                    .map(|(host, project)| h_flex().gap_1().child(badge(host, cx)).child(project)),
+            )
     }
 }
 
@@ -229,32 +729,62 @@ impl Project {
     fn new(
         window: &mut Window,
         cx: &mut Context<Self>,
+        parent: WeakEntity<Projects>,
+        host: String,
         instance_url: String,
-        gitlab: AsyncGitlab,
+        gitlab: GitlabClient,
         project_id: u64,
     ) -> Self {
         let gitlab_ = gitlab.clone();
+        let parent_ = parent.clone();
         let project_url = format!("{}/projects/{}", instance_url, project_id);
+        let refresh_key = RefreshKey::Project(host.clone(), project_id);
 
         cx.spawn_in(window, async move |project, cx| {
             match get_project(gitlab_.clone(), project_id).await {
                 Ok(project_) => {
                     let _ = project.update_in(cx, |project, window, cx| {
                         let name = project_.name.into();
-                        let pipelines =
-                            cx.new(|cx| Pipelines::new(window, cx, gitlab_.clone(), project_id));
+                        let default_branch = project_.default_branch;
+                        let last_activity_at = project_.last_activity_at;
+                        let pipelines = cx.new(|cx| {
+                            Pipelines::new(
+                                window,
+                                cx,
+                                gitlab_.clone(),
+                                project_id,
+                                default_branch.clone(),
+                                refresh_key,
+                            )
+                        });
                         let merge_requests = cx
                             .new(|cx| MergeRequests::new(window, cx, gitlab_.clone(), project_id));
+                        let deployments =
+                            cx.new(|cx| Deployments::new(window, cx, gitlab_.clone(), project_id));
 
                         project.0 = cx.new(|cx| {
                             LoadState::Ready(cx.new(|_cx| {
-                                ProjectInner::new(name, project_url, pipelines, merge_requests)
+                                ProjectInner::new(
+                                    parent_,
+                                    name,
+                                    project_url,
+                                    gitlab_.clone(),
+                                    project_id,
+                                    default_branch,
+                                    last_activity_at,
+                                    pipelines,
+                                    merge_requests,
+                                    deployments,
+                                )
                             }))
                         });
                     });
                 }
                 Err(error) => {
                     let _ = project.update_in(cx, |project, _window, cx| {
+                        AppState::global_mut(cx)
+                            .refreshes_mut()
+                            .failed(&refresh_key);
                         project.0 = cx.new(|cx| {
                             with_button_error!(
                                 cx,
@@ -271,11 +801,19 @@ impl Project {
 
         let gitlab_ = gitlab.clone();
         let instance_url = instance_url.clone();
-        let interval = AppState::global(cx).config().refresh_every.duration();
+        let refresh_key = RefreshKey::Project(host.clone(), project_id);
         cx.spawn_in(window, async move |project, cx| {
-            Timer::after(interval).await;
+            wait_refresh(cx, &refresh_key).await;
             let _ = project.update_in(cx, |project, window, cx| {
-                *project = Self::new(window, cx, instance_url, gitlab_.clone(), project_id);
+                *project = Self::new(
+                    window,
+                    cx,
+                    parent,
+                    host,
+                    instance_url,
+                    gitlab_.clone(),
+                    project_id,
+                );
                 cx.notify();
             });
         })
@@ -283,6 +821,112 @@ impl Project {
 
         Self(cx.new(|_cx| LoadState::Loading))
     }
+
+    /// Project fetched by a batch, refreshed along with it
+    #[allow(clippy::too_many_arguments)]
+    fn loaded(
+        window: &mut Window,
+        cx: &mut Context<Self>,
+        parent: WeakEntity<Projects>,
+        host: &str,
+        instance_url: &str,
+        gitlab: GitlabClient,
+        project_id: u64,
+        data: ProjectData,
+    ) -> Self {
+        let project_url = format!("{}/projects/{}", instance_url, project_id);
+        let pipelines = cx.new(|cx| Pipelines::loaded(cx, host, data.pipelines));
+        let merge_requests =
+            cx.new(|cx| MergeRequests::loaded(cx, gitlab.clone(), data.merge_requests));
+        let deployments = cx.new(|cx| Deployments::new(window, cx, gitlab.clone(), project_id));
+
+        Self(cx.new(|cx| {
+            LoadState::Ready(cx.new(|_cx| {
+                ProjectInner::new(
+                    parent,
+                    data.project.name.into(),
+                    project_url,
+                    gitlab,
+                    project_id,
+                    data.project.default_branch,
+                    data.project.last_activity_at,
+                    pipelines,
+                    merge_requests,
+                    deployments,
+                )
+            }))
+        }))
+    }
+
+    fn failed(cx: &mut Context<Self>, project_id: u64, error: GitlabError) -> Self {
+        Self(cx.new(|cx| {
+            with_button_error!(
+                cx,
+                "Load error".into(),
+                format!("Error during load project {}", project_id),
+                error.into()
+            )
+        }))
+    }
+
+    fn nav_items(&self, host: &str, cx: &App) -> Vec<NavItem> {
+        let LoadState::Ready(project) = self.0.read(cx) else {
+            return vec![];
+        };
+
+        project.read(cx).nav_items(host, cx)
+    }
+
+    fn last_activity_at(&self, cx: &App) -> Option<String> {
+        let LoadState::Ready(project) = self.0.read(cx) else {
+            return None;
+        };
+
+        Some(project.read(cx).last_activity_at.clone())
+    }
+
+    fn failing_pipeline(&self, cx: &App) -> Option<TrayItem> {
+        let LoadState::Ready(project) = self.0.read(cx) else {
+            return None;
+        };
+
+        project.read(cx).failing_pipeline(cx)
+    }
+
+    fn last_pipeline(&self, cx: &App) -> Option<(String, Pipeline)> {
+        let LoadState::Ready(project) = self.0.read(cx) else {
+            return None;
+        };
+        let project = project.read(cx);
+
+        project
+            .pipelines
+            .read(cx)
+            .last(cx)
+            .map(|pipeline| (project_path(&pipeline.web_url).to_string(), pipeline))
+    }
+
+    fn snapshot(&self, cx: &App) -> Option<ProjectSnapshot> {
+        let LoadState::Ready(project) = self.0.read(cx) else {
+            return None;
+        };
+        let project = project.read(cx);
+
+        Some(ProjectSnapshot::new(
+            project.project_id,
+            &project.project_url,
+            project.pipelines.read(cx).last(cx).as_ref(),
+            &project.merge_requests.read(cx).opened(cx),
+        ))
+    }
+
+    fn merge_request_statuses(&self, cx: &App) -> Vec<String> {
+        let LoadState::Ready(project) = self.0.read(cx) else {
+            return vec![];
+        };
+
+        project.read(cx).merge_requests.read(cx).statuses(cx)
+    }
 }
 
 impl Render for Project {
@@ -293,15 +937,120 @@ impl Render for Project {
 
 #[derive(Constructor)]
 pub struct ProjectInner {
+    /// Projects of the instance, looked up again by popped out windows
+    parent: WeakEntity<Projects>,
     name: SharedString,
     project_url: String,
+    gitlab: GitlabClient,
+    project_id: u64,
+    default_branch: String,
+    last_activity_at: String,
     pipelines: Entity<Pipelines>,
     merge_requests: Entity<MergeRequests>,
+    deployments: Entity<Deployments>,
+}
+
+impl ProjectInner {
+    fn nav_items(&self, host: &str, cx: &App) -> Vec<NavItem> {
+        let mut items = vec![NavItem {
+            key: NavKey::new(NavKind::Project, self.project_url.clone()),
+            host: host.to_string(),
+            id: self.project_id,
+        }];
+        items.extend(self.merge_requests.read(cx).nav_items(host, cx));
+        items
+    }
+
+    fn failing_pipeline(&self, cx: &App) -> Option<TrayItem> {
+        self.pipelines
+            .read(cx)
+            .last(cx)
+            .filter(|pipeline| pipeline.status.is_error())
+            .map(|pipeline| TrayItem::new(self.name.to_string(), pipeline.web_url))
+    }
+
+    fn show_releases_dialog(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let title = format!("{} releases", self.name);
+        let releases = cx.new(|cx| {
+            Releases::new(
+                window,
+                cx,
+                self.gitlab.clone(),
+                self.project_id,
+                self.default_branch.clone(),
+            )
+        });
+
+        window.open_dialog(cx, move |dialog, _window, _cx| {
+            dialog
+                .title(title.clone())
+                .width(px(900.))
+                .child(releases.clone())
+        });
+    }
+
+    fn show_flaky_jobs_dialog(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let title = format!("{} flaky jobs", self.name);
+        let flaky_jobs = cx.new(|cx| {
+            FlakyJobs::new(
+                window,
+                cx,
+                self.gitlab.clone(),
+                self.project_id,
+                self.default_branch.clone(),
+            )
+        });
+
+        window.open_dialog(cx, move |dialog, _window, _cx| {
+            dialog
+                .title(title.clone())
+                .width(px(900.))
+                .child(flaky_jobs.clone())
+        });
+    }
+
+    fn pop_out(&mut self, cx: &mut Context<Self>) {
+        let Some(projects) = self.parent.upgrade() else {
+            return;
+        };
+        let host = projects.read(cx).host().to_string();
+        let project_id = self.project_id;
+        let view = Projects::pop_out_view(cx, projects, project_id);
+
+        popout::open(
+            cx,
+            PopOutKey::Project(host, project_id),
+            self.name.clone(),
+            view,
+        );
+    }
+
+    fn pop_out_pipeline_log(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(pipeline) = self.pipelines.read(cx).last(cx) else {
+            window.push_notification("No pipeline yet", cx);
+            return;
+        };
+        let title = format!("{} pipeline {} log", self.name, pipeline.iid);
+        let gitlab = self.gitlab.clone();
+        let project_id = self.project_id;
+        let log = cx.new(|cx| PipelineLog::new(cx, gitlab, project_id, pipeline.id));
+
+        popout::open(
+            cx,
+            PopOutKey::PipelineLog(pipeline.web_url),
+            SharedString::new(title),
+            log.into(),
+        );
+    }
 }
 
 impl Render for ProjectInner {
     fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let selected = AppState::global(cx)
+            .is_selected(&NavKey::new(NavKind::Project, self.project_url.clone()));
+
         h_flex()
+            .when(selected, |element| element.bg(cx.theme().accent))
             .child(
                 Button::new(self.name.clone())
                     .label(self.name.clone())
@@ -310,15 +1059,84 @@ impl Render for ProjectInner {
                         open::that(this.project_url.clone()).unwrap_or_log()
                     })),
             )
+            .child(
+                Button::new("releases")
+                    .icon(IconName::BookOpen)
+                    .link()
+                    .tooltip("Releases and tags")
+                    .on_click(
+                        cx.listener(|this, _, window, cx| this.show_releases_dialog(window, cx)),
+                    ),
+            )
+            .child(
+                Button::new("flaky-jobs")
+                    .icon(IconName::TriangleAlert)
+                    .link()
+                    .tooltip("Flaky jobs")
+                    .on_click(
+                        cx.listener(|this, _, window, cx| this.show_flaky_jobs_dialog(window, cx)),
+                    ),
+            )
+            .child(
+                Button::new("pipeline-log")
+                    .icon(IconName::File)
+                    .link()
+                    .tooltip("Last pipeline log in a new window")
+                    .on_click(
+                        cx.listener(|this, _, window, cx| this.pop_out_pipeline_log(window, cx)),
+                    ),
+            )
+            .child(
+                Button::new("pop-out-project")
+                    .icon(IconName::PanelRightOpen)
+                    .link()
+                    .tooltip("Open in a new window")
+                    .on_click(cx.listener(|this, _, _window, cx| this.pop_out(cx))),
+            )
             .child(" | ".to_string())
             .child(self.pipelines.clone())
             .child(" | ".to_string())
             .child(self.merge_requests.clone())
+            .child(" | ".to_string())
+            .child(self.deployments.clone())
     }
 }
 
-async fn get_project(
-    gitlab: AsyncGitlab,
+/// Changes when a pipeline or a merge request of batched projects changes
+fn fingerprint(data: &HashMap<u64, ProjectData>) -> String {
+    let mut project_ids = data.keys().collect::<Vec<&u64>>();
+    project_ids.sort();
+
+    project_ids
+        .into_iter()
+        .map(|project_id| {
+            let data = &data[project_id];
+            let merge_requests = data
+                .merge_requests
+                .iter()
+                .map(|merge_request| {
+                    format!(
+                        "{}:{}:{:?}",
+                        merge_request.id,
+                        merge_request.detailed_merge_status,
+                        merge_request.unresolved_discussions
+                    )
+                })
+                .collect::<Vec<String>>()
+                .join(",");
+            format!(
+                "{}[{}][{}]",
+                project_id,
+                pipeline::fingerprint(&data.pipelines),
+                merge_requests
+            )
+        })
+        .collect::<Vec<String>>()
+        .join(";")
+}
+
+pub async fn get_project(
+    gitlab: GitlabClient,
     project_id: u64,
 ) -> Result<crate::state::gitlab::project::Project, GitlabError> {
     Ok(gitlab::api::projects::Project::builder()
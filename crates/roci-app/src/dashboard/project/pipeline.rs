@@ -1,18 +1,33 @@
+use std::{collections::HashMap, sync::Arc};
+
+use chrono::{DateTime, TimeDelta, Utc};
+use futures::future::join_all;
 use gitlab::api::{self, projects, AsyncQuery};
-use gitlab::AsyncGitlab;
 use gpui::prelude::FluentBuilder;
 use gpui::*;
 use gpui_component::button::{Button, ButtonVariants};
+use gpui_component::h_flex;
 use gpui_component::label::Label;
-use gpui_component::{h_flex, ActiveTheme, Icon, IconName};
+use gpui_component::tooltip::Tooltip;
 use roci_app_components::error::WithButtonModalError;
+use roci_app_components::sparkline::{Sparkline, SparklinePoint};
 use roci_app_components::{with_button_error, LoadState};
 use tracing_unwrap::ResultExt;
 
+use crate::config::status::StatusStyle;
 use crate::dashboard::error::GitlabError;
 use crate::state::gitlab::project::pipeline::{Pipeline, PipelineStatus};
+use crate::state::gitlab::GitlabClient;
+use crate::state::history::{History, PipelineStats};
+use crate::state::refresh::RefreshKey;
+use crate::state::AppState;
+use crate::utils::{duration, short_datetime};
 
 pub const PIPELINES_COUNT: usize = 10;
+/// Finished pipelines shown in duration sparkline and success rate
+pub const TREND_COUNT: usize = 30;
+/// Pipelines older are not taken from history for sparkline
+pub const TREND_DAYS: i64 = 30;
 
 pub struct Pipelines(Entity<LoadState<PipelinesInner, WithButtonModalError<GitlabError>>>);
 
@@ -20,20 +35,56 @@ impl Pipelines {
     pub fn new(
         window: &mut Window,
         cx: &mut Context<Self>,
-        gitlab: AsyncGitlab,
+        gitlab: GitlabClient,
         project_id: u64,
+        default_branch: String,
+        refresh_key: RefreshKey,
     ) -> Self {
+        let history = AppState::global(cx).history();
+        let executor = cx.background_executor().clone();
+
         cx.spawn_in(window, async move |pipelines, cx| {
-            match get_pipelines(gitlab, project_id).await {
+            match get_pipelines(
+                gitlab,
+                project_id,
+                &default_branch,
+                refresh_key.host(),
+                history,
+                executor,
+            )
+            .await
+            {
                 Ok(pipelines_) => {
+                    let Ok(trend) =
+                        cx.update(|_window, cx| read_trend(cx, refresh_key.host(), &pipelines_))
+                    else {
+                        return;
+                    };
+                    let trend = trend.await;
                     let _ = pipelines.update_in(cx, |pipelines, _window, cx| {
-                        let pipelines_ = LoadState::Ready(cx.new(|_cx| PipelinesInner(pipelines_)));
+                        AppState::record_pipelines(
+                            cx,
+                            refresh_key.host().to_string(),
+                            pipelines_.clone(),
+                        );
+                        AppState::global_mut(cx).refreshes_mut().loaded(
+                            &refresh_key,
+                            fingerprint(&pipelines_),
+                            pipelines_
+                                .iter()
+                                .any(|pipeline| pipeline.status.is_running()),
+                        );
+                        let pipelines_ = PipelinesInner::new(pipelines_, trend);
+                        let pipelines_ = LoadState::Ready(cx.new(|_cx| pipelines_));
                         let pipelines_ = cx.new(|_cx| pipelines_);
                         pipelines.0 = pipelines_;
                     });
                 }
                 Err(error) => {
                     let _ = pipelines.update_in(cx, |project, _window, cx| {
+                        AppState::global_mut(cx)
+                            .refreshes_mut()
+                            .failed(&refresh_key);
                         project.0 = cx.new(|cx| {
                             with_button_error!(
                                 cx,
@@ -50,6 +101,59 @@ impl Pipelines {
 
         Self(cx.new(|_cx| LoadState::Loading))
     }
+
+    /// Pipelines fetched by a batch, history trend is added once read
+    pub fn loaded(cx: &mut Context<Self>, host: &str, pipelines: Vec<Pipeline>) -> Self {
+        let trend = read_trend(cx, host, &pipelines);
+        let pipelines = PipelinesInner::new(pipelines, vec![]);
+        let pipelines = cx.new(|_cx| pipelines);
+
+        let pipelines_ = pipelines.downgrade();
+        cx.spawn(async move |_this, cx| {
+            let mut trend = trend.await;
+            let _ = pipelines_.update(cx, |pipelines, cx| {
+                merge(&mut trend, &pipelines.trend);
+                pipelines.trend = trend;
+                cx.notify();
+            });
+        })
+        .detach();
+
+        Self(cx.new(|_cx| LoadState::Ready(pipelines)))
+    }
+
+    /// Add or update a pipeline received by webhook
+    pub fn apply(&mut self, pipeline: Pipeline, cx: &mut Context<Self>) {
+        let LoadState::Ready(pipelines) = self.0.read(cx) else {
+            return;
+        };
+
+        pipelines.clone().update(cx, |pipelines, cx| {
+            merge(&mut pipelines.trend, std::slice::from_ref(&pipeline));
+            match pipelines
+                .pipelines
+                .iter_mut()
+                .find(|pipeline_| pipeline_.id == pipeline.id)
+            {
+                Some(pipeline_) => *pipeline_ = pipeline,
+                None => {
+                    pipelines.pipelines.push(pipeline);
+                    if pipelines.pipelines.len() > PIPELINES_COUNT {
+                        pipelines.pipelines.remove(0);
+                    }
+                }
+            }
+            cx.notify();
+        });
+    }
+
+    pub fn last(&self, cx: &App) -> Option<Pipeline> {
+        let LoadState::Ready(pipelines) = self.0.read(cx) else {
+            return None;
+        };
+
+        pipelines.read(cx).pipelines.last().cloned()
+    }
 }
 
 impl Render for Pipelines {
@@ -58,33 +162,84 @@ impl Render for Pipelines {
     }
 }
 
-pub struct PipelinesInner(Vec<Pipeline>);
+pub struct PipelinesInner {
+    pipelines: Vec<Pipeline>,
+    /// Last finished pipelines of the same ref, oldest first
+    trend: Vec<Pipeline>,
+}
+
+impl PipelinesInner {
+    /// `trend` as read from history, given pipelines may not be recorded yet
+    fn new(pipelines: Vec<Pipeline>, mut trend: Vec<Pipeline>) -> Self {
+        merge(&mut trend, &pipelines);
+
+        Self { pipelines, trend }
+    }
+
+    fn sparkline(&self, cx: &App) -> impl IntoElement {
+        // Pipelines without known duration would be plotted as instant ones
+        let points = self
+            .trend
+            .iter()
+            .filter_map(|pipeline| {
+                let seconds = pipeline.duration?;
+                let tooltip = format!(
+                    "{} ({}), {}, {}",
+                    pipeline.iid,
+                    short_datetime(&pipeline.created_at),
+                    pipeline.status,
+                    duration(seconds)
+                );
+
+                Some(
+                    SparklinePoint::new(seconds as f64, tooltip)
+                        .color(status_style(pipeline.status, cx).color.hsla(cx)),
+                )
+            })
+            .collect::<Vec<SparklinePoint>>();
+        let stats = PipelineStats::from_pipelines(&self.trend);
+        let tooltip = SharedString::new(format!(
+            "{} succeeded and {} failed of last {} pipelines{}",
+            stats.succeeded,
+            stats.failed,
+            self.trend.len(),
+            stats
+                .average_duration
+                .map(|average| format!(", {} on average", duration(average as u64)))
+                .unwrap_or_default()
+        ));
+
+        h_flex()
+            .gap_1()
+            .when(!points.is_empty(), |this| {
+                this.child(Sparkline::new("pipelines-durations", points))
+            })
+            .when_some(stats.success_rate(), |this, success_rate| {
+                this.child(
+                    div()
+                        .id("pipelines-success-rate")
+                        .child(Label::new(format!("{:.0}%", success_rate * 100.)).text_xs())
+                        .tooltip(move |window, cx| Tooltip::new(tooltip.clone()).build(window, cx)),
+                )
+            })
+    }
+}
 
 impl Render for PipelinesInner {
     fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         h_flex()
-            .when(self.0.is_empty(), |_cx| div().child(Label::new("n/a")))
+            .when(self.pipelines.is_empty(), |_cx| {
+                div().child(Label::new("n/a"))
+            })
             .id("pipelines")
-            .children(self.0.iter().map(|pipeline| {
-                let (icon, color): (IconName, Hsla) = match pipeline.status {
-                    PipelineStatus::Running
-                    | PipelineStatus::Pending
-                    | PipelineStatus::Created
-                    | PipelineStatus::Preparing
-                    | PipelineStatus::Manual
-                    | PipelineStatus::Scheduled
-                    | PipelineStatus::WaitingForResource => (IconName::Ellipsis, cx.theme().cyan),
-                    PipelineStatus::Success => (IconName::CircleCheck, cx.theme().green),
-                    PipelineStatus::Failed | PipelineStatus::Canceled | PipelineStatus::Skipped => {
-                        (IconName::CircleX, cx.theme().red)
-                    }
-                };
+            .children(self.pipelines.iter().map(|pipeline| {
+                let style = status_style(pipeline.status, cx);
                 let web_url = pipeline.web_url.clone();
 
                 div().id(ElementId::Integer(pipeline.id)).child(
                     div().child(
                         Button::new("pipeline-icon")
-                            .icon(Icon::new(icon).text_color(color))
+                            .icon(style.icon(cx))
                             .link()
                             .tooltip(format!(
                                 "{} ({}), {}",
@@ -96,20 +251,88 @@ impl Render for PipelinesInner {
                     ),
                 )
             }))
+            .when(!self.trend.is_empty(), |this| {
+                this.child(div().pl_2().child(self.sparkline(cx)))
+            })
     }
 }
 
-async fn get_pipelines(gitlab: AsyncGitlab, project_id: u64) -> Result<Vec<Pipeline>, GitlabError> {
-    let project_: crate::state::gitlab::project::Project = projects::Project::builder()
-        .project(project_id)
-        .build()?
-        .query_async(&gitlab)
-        .await?;
+/// Add or update finished `pipelines` in `trend`, keeping last `TREND_COUNT` ones
+fn merge(trend: &mut Vec<Pipeline>, pipelines: &[Pipeline]) {
+    for pipeline in pipelines {
+        match trend
+            .iter_mut()
+            .find(|pipeline_| pipeline_.id == pipeline.id)
+        {
+            // Keep details of history when given pipeline is from a list
+            Some(pipeline_) if !pipeline.lacks_details() => *pipeline_ = pipeline.clone(),
+            Some(pipeline_) => pipeline_.status = pipeline.status,
+            None => trend.push(pipeline.clone()),
+        }
+    }
+
+    trend.retain(|pipeline| {
+        matches!(
+            pipeline.status,
+            PipelineStatus::Success | PipelineStatus::Failed
+        )
+    });
+    trend.sort_by_key(|pipeline| DateTime::parse_from_rfc3339(&pipeline.created_at).ok());
+    if trend.len() > TREND_COUNT {
+        trend.drain(..trend.len() - TREND_COUNT);
+    }
+}
+
+pub fn status_style(status: PipelineStatus, cx: &App) -> StatusStyle {
+    AppState::global(cx)
+        .config()
+        .status_styles
+        .style(status.into())
+}
+
+pub fn fingerprint(pipelines: &[Pipeline]) -> String {
+    pipelines
+        .iter()
+        .map(|pipeline| format!("{}:{}", pipeline.id, pipeline.status))
+        .collect::<Vec<String>>()
+        .join(",")
+}
+
+/// Last finished pipelines of the ref of `pipelines` from history, read in
+/// background as database queries are blocking
+fn read_trend(cx: &App, host: &str, pipelines: &[Pipeline]) -> Task<Vec<Pipeline>> {
+    let (Some(history), Some(last)) = (AppState::global(cx).history(), pipelines.last()) else {
+        return Task::ready(vec![]);
+    };
+    let host = host.to_string();
+    let project_id = last.project_id;
+    let ref_ = last.ref_.clone();
+
+    cx.background_spawn(async move {
+        history
+            .pipelines(
+                &host,
+                project_id,
+                Some(&ref_),
+                Utc::now() - TimeDelta::days(TREND_DAYS),
+            )
+            .inspect_err(|error| tracing::warn!("Unable to read pipelines history: {}", error))
+            .unwrap_or_default()
+    })
+}
 
+async fn get_pipelines(
+    gitlab: GitlabClient,
+    project_id: u64,
+    default_branch: &str,
+    host: &str,
+    history: Option<Arc<History>>,
+    executor: BackgroundExecutor,
+) -> Result<Vec<Pipeline>, GitlabError> {
     let pipelines_endpoint = projects::pipelines::Pipelines::builder()
         .project(project_id)
         .source(projects::pipelines::PipelineSource::Push)
-        .ref_(&project_.default_branch)
+        .ref_(default_branch)
         .build()?;
     let mut pipelines: Vec<crate::state::gitlab::project::pipeline::Pipeline> =
         api::paged(pipelines_endpoint, api::Pagination::Limit(PIPELINES_COUNT))
@@ -117,5 +340,80 @@ async fn get_pipelines(gitlab: AsyncGitlab, project_id: u64) -> Result<Vec<Pipel
             .await?;
     pipelines.reverse();
 
+    // Durations are only given by single pipeline endpoint, history (if enabled) avoids
+    // asking again for already known pipelines
+    let pipeline_ids = pipelines
+        .iter()
+        .filter(|pipeline| pipeline.lacks_details())
+        .map(|pipeline| pipeline.id)
+        .collect::<Vec<u64>>();
+    let mut known = match history {
+        Some(history) => {
+            let host = host.to_string();
+            let pipeline_ids = pipeline_ids.clone();
+            executor
+                .spawn(async move {
+                    history
+                        .details(&host, &pipeline_ids)
+                        .inspect_err(|error| {
+                            tracing::warn!("Unable to read pipelines history: {}", error)
+                        })
+                        .unwrap_or_else(|_| HashMap::new())
+                })
+                .await
+        }
+        None => HashMap::new(),
+    };
+
+    let fetched = join_all(
+        pipeline_ids
+            .iter()
+            .filter(|pipeline_id| !known.contains_key(pipeline_id))
+            .map(|pipeline_id| async {
+                (
+                    *pipeline_id,
+                    get_pipeline(&gitlab, project_id, *pipeline_id).await,
+                )
+            }),
+    )
+    .await;
+    for (pipeline_id, details) in fetched {
+        // Listed pipeline is kept as is, without duration
+        match details {
+            Ok(details) => {
+                known.insert(pipeline_id, details);
+            }
+            Err(error) => {
+                tracing::warn!(
+                    "Unable to get details of pipeline {}: {}",
+                    pipeline_id,
+                    error
+                )
+            }
+        }
+    }
+
+    for pipeline in pipelines.iter_mut() {
+        if let Some(details) = known.remove(&pipeline.id) {
+            pipeline.started_at = details.started_at;
+            pipeline.finished_at = details.finished_at;
+            pipeline.duration = details.duration;
+            pipeline.queued_duration = details.queued_duration;
+        }
+    }
+
     Ok(pipelines)
 }
+
+async fn get_pipeline(
+    gitlab: &GitlabClient,
+    project_id: u64,
+    pipeline_id: u64,
+) -> Result<Pipeline, GitlabError> {
+    let endpoint = projects::pipelines::Pipeline::builder()
+        .project(project_id)
+        .pipeline(pipeline_id)
+        .build()?;
+
+    Ok(endpoint.query_async(gitlab).await?)
+}
@@ -0,0 +1,19 @@
+use derive_more::Constructor;
+use gitlab::api::Endpoint;
+
+/// Raw log of a job
+#[derive(Debug, Constructor)]
+pub struct JobLog {
+    project_id: u64,
+    job_id: u64,
+}
+
+impl Endpoint for JobLog {
+    fn method(&self) -> reqwest::Method {
+        reqwest::Method::GET
+    }
+
+    fn endpoint(&self) -> std::borrow::Cow<'static, str> {
+        format!("projects/{}/jobs/{}/trace", self.project_id, self.job_id).into()
+    }
+}
@@ -0,0 +1,235 @@
+use std::time::Duration;
+
+use gitlab::api::{self, AsyncQuery};
+use gpui::{prelude::FluentBuilder, *};
+use gpui_component::{
+    button::{Button, ButtonVariants},
+    h_flex,
+    label::Label,
+    spinner::Spinner,
+    v_flex, ActiveTheme,
+};
+use roci_app_components::error::{ErrorDetails, WithButtonModalError};
+use tracing_unwrap::ResultExt;
+
+use crate::{
+    dashboard::{
+        error::GitlabError,
+        project::{endpoint::PipelineJobs, pipeline_log::endpoint::JobLog},
+    },
+    state::gitlab::{
+        project::job::{log_lines, Job, JobStatus},
+        GitlabClient,
+    },
+};
+
+mod endpoint;
+
+/// Last lines of the log displayed, whole log is on Gitlab
+pub const LOG_LINES_COUNT: usize = 1000;
+/// Log of a running job is fetched again
+pub const LOG_REFRESH_EVERY: Duration = Duration::from_secs(10);
+/// Whole log is downloaded on each refresh, a larger one is not refreshed anymore
+pub const LOG_REFRESH_MAX_BYTES: usize = 2 * 1024 * 1024;
+/// Resolved by the platform, falls back to default font
+pub const LOG_FONT_FAMILY: &str = "monospace";
+
+/// Log of a pipeline job, the first failed one unless another one is chosen
+pub struct PipelineLog {
+    gitlab: GitlabClient,
+    project_id: u64,
+    pipeline_id: u64,
+    jobs: Vec<Job>,
+    selected: Option<u64>,
+    lines: Vec<SharedString>,
+    error: Option<Entity<WithButtonModalError<GitlabError>>>,
+    loading: bool,
+    /// Running job log stopped being refreshed because of its size
+    too_large: bool,
+    _load: Option<Task<()>>,
+}
+
+impl PipelineLog {
+    pub fn new(
+        cx: &mut Context<Self>,
+        gitlab: GitlabClient,
+        project_id: u64,
+        pipeline_id: u64,
+    ) -> Self {
+        let mut log = Self {
+            gitlab,
+            project_id,
+            pipeline_id,
+            jobs: vec![],
+            selected: None,
+            lines: vec![],
+            error: None,
+            loading: true,
+            too_large: false,
+            _load: None,
+        };
+        log.load(cx);
+
+        log
+    }
+
+    fn select(&mut self, cx: &mut Context<Self>, job_id: u64) {
+        self.selected = Some(job_id);
+        self.load(cx);
+    }
+
+    /// Fetch jobs and log of selected one, again while it is running
+    fn load(&mut self, cx: &mut Context<Self>) {
+        let gitlab = self.gitlab.clone();
+        let project_id = self.project_id;
+        let pipeline_id = self.pipeline_id;
+        let mut selected = self.selected;
+        self.loading = true;
+        cx.notify();
+
+        self._load = Some(cx.spawn(async move |this, cx| loop {
+            let result = get_log(&gitlab, project_id, pipeline_id, selected).await;
+            let mut running = false;
+
+            let updated = this.update(cx, |this, cx| {
+                this.loading = false;
+                match result {
+                    Ok((jobs, job_id, log)) => {
+                        let job_running = jobs
+                            .iter()
+                            .any(|job| Some(job.id) == job_id && job.status.is_running());
+                        this.too_large = job_running && log.size > LOG_REFRESH_MAX_BYTES;
+                        running = job_running && !this.too_large;
+                        selected = job_id;
+                        this.jobs = jobs;
+                        this.selected = job_id;
+                        this.lines = log.lines.into_iter().map(SharedString::new).collect();
+                        this.error = None;
+                    }
+                    Err(error) => {
+                        let details = cx.new(|_cx| {
+                            ErrorDetails::new(
+                                format!("Error during load pipeline {} log", pipeline_id),
+                                Some(error),
+                            )
+                        });
+                        this.error =
+                            Some(cx.new(|_cx| {
+                                WithButtonModalError::new("Load error".into(), details)
+                            }));
+                    }
+                }
+                cx.notify();
+            });
+            if updated.is_err() || !running {
+                break;
+            }
+
+            Timer::after(LOG_REFRESH_EVERY).await;
+        }));
+    }
+}
+
+impl Render for PipelineLog {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let web_url = self
+            .jobs
+            .iter()
+            .find(|job| Some(job.id) == self.selected)
+            .map(|job| job.web_url.clone());
+
+        v_flex()
+            .gap_2()
+            .child(
+                h_flex()
+                    .flex_wrap()
+                    .gap_1()
+                    .children(self.jobs.iter().map(|job| {
+                        let job_id = job.id;
+
+                        Button::new(ElementId::Integer(job.id))
+                            .label(format!("{} ({})", job.name, job.status))
+                            .when(Some(job.id) == self.selected, |button| button.primary())
+                            .when(Some(job.id) != self.selected, |button| button.ghost())
+                            .on_click(
+                                cx.listener(move |this, _, _window, cx| this.select(cx, job_id)),
+                            )
+                    }))
+                    .when_some(web_url, |element, web_url| {
+                        element.child(
+                            Button::new("open-job")
+                                .link()
+                                .label("Open in browser")
+                                .on_click(move |_, _, _| {
+                                    open::that(web_url.clone()).unwrap_or_log();
+                                }),
+                        )
+                    }),
+            )
+            .when(self.loading, |element| element.child(Spinner::new()))
+            .when(self.too_large, |element| {
+                element.child(
+                    Label::new("Log too large to be refreshed, select the job again to reload it")
+                        .text_color(cx.theme().muted_foreground),
+                )
+            })
+            .children(self.error.clone())
+            .when(!self.loading && self.lines.is_empty(), |element| {
+                element.child(Label::new("Empty log").text_color(cx.theme().muted_foreground))
+            })
+            .child(
+                v_flex().font_family(LOG_FONT_FAMILY).text_xs().children(
+                    self.lines
+                        .iter()
+                        .map(|line| div().whitespace_nowrap().child(line.clone())),
+                ),
+            )
+    }
+}
+
+/// Last lines of a job log
+#[derive(Debug, Default)]
+struct JobLogLines {
+    lines: Vec<String>,
+    /// Bytes of the whole log
+    size: usize,
+}
+
+/// Pipeline jobs, displayed job (`selected`, else first failed or last one) and its log lines
+async fn get_log(
+    gitlab: &GitlabClient,
+    project_id: u64,
+    pipeline_id: u64,
+    selected: Option<u64>,
+) -> Result<(Vec<Job>, Option<u64>, JobLogLines), GitlabError> {
+    let mut jobs: Vec<Job> = api::paged(
+        PipelineJobs::new(project_id, pipeline_id, false),
+        api::Pagination::All,
+    )
+    .query_async(gitlab)
+    .await?;
+    jobs.sort_by_key(|job| job.id);
+
+    let job_id = selected
+        .filter(|job_id| jobs.iter().any(|job| job.id == *job_id))
+        .or_else(|| {
+            jobs.iter()
+                .find(|job| job.status == JobStatus::Failed && !job.allow_failure)
+                .or_else(|| jobs.iter().rev().find(|job| job.status.is_running()))
+                .or(jobs.last())
+                .map(|job| job.id)
+        });
+    let Some(job_id) = job_id else {
+        return Ok((jobs, None, JobLogLines::default()));
+    };
+
+    let log = api::raw(JobLog::new(project_id, job_id))
+        .query_async(gitlab)
+        .await?;
+    let log = JobLogLines {
+        lines: log_lines(&String::from_utf8_lossy(&log), LOG_LINES_COUNT),
+        size: log.len(),
+    };
+
+    Ok((jobs, Some(job_id), log))
+}
@@ -0,0 +1,100 @@
+use derive_more::Constructor;
+use gitlab::api::{BodyError, Endpoint, FormParams, Pageable, QueryParams};
+
+#[derive(Debug, Constructor)]
+pub struct ProjectReleases {
+    project_id: u64,
+}
+
+impl Endpoint for ProjectReleases {
+    fn method(&self) -> reqwest::Method {
+        reqwest::Method::GET
+    }
+
+    fn endpoint(&self) -> std::borrow::Cow<'static, str> {
+        format!("projects/{}/releases", self.project_id).into()
+    }
+
+    fn parameters(&self) -> QueryParams<'_> {
+        let mut params = QueryParams::default();
+        params.push("order_by", "released_at");
+        params.push("sort", "desc");
+        params
+    }
+}
+
+impl Pageable for ProjectReleases {}
+
+#[derive(Debug, Constructor)]
+pub struct ProjectTags {
+    project_id: u64,
+}
+
+impl Endpoint for ProjectTags {
+    fn method(&self) -> reqwest::Method {
+        reqwest::Method::GET
+    }
+
+    fn endpoint(&self) -> std::borrow::Cow<'static, str> {
+        format!("projects/{}/repository/tags", self.project_id).into()
+    }
+
+    fn parameters(&self) -> QueryParams<'_> {
+        let mut params = QueryParams::default();
+        params.push("order_by", "updated");
+        params.push("sort", "desc");
+        params
+    }
+}
+
+impl Pageable for ProjectTags {}
+
+#[derive(Debug, Constructor)]
+pub struct Compare {
+    project_id: u64,
+    from: String,
+    to: String,
+}
+
+impl Endpoint for Compare {
+    fn method(&self) -> reqwest::Method {
+        reqwest::Method::GET
+    }
+
+    fn endpoint(&self) -> std::borrow::Cow<'static, str> {
+        format!("projects/{}/repository/compare", self.project_id).into()
+    }
+
+    fn parameters(&self) -> QueryParams<'_> {
+        let mut params = QueryParams::default();
+        params.push("from", self.from.as_str());
+        params.push("to", self.to.as_str());
+        params
+    }
+}
+
+#[derive(Debug, Constructor)]
+pub struct CreateRelease {
+    project_id: u64,
+    tag_name: String,
+    name: String,
+    description: String,
+}
+
+impl Endpoint for CreateRelease {
+    fn method(&self) -> reqwest::Method {
+        reqwest::Method::POST
+    }
+
+    fn endpoint(&self) -> std::borrow::Cow<'static, str> {
+        format!("projects/{}/releases", self.project_id).into()
+    }
+
+    fn body(&self) -> Result<Option<(&'static str, Vec<u8>)>, BodyError> {
+        let mut params = FormParams::default();
+        params.push("tag_name", self.tag_name.as_str());
+        params.push("name", self.name.as_str());
+        params.push("description", self.description.as_str());
+        params.into_body()
+    }
+}
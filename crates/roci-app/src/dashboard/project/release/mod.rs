@@ -0,0 +1,333 @@
+use derive_more::Constructor;
+use gitlab::api::{self, AsyncQuery};
+use gpui::{prelude::FluentBuilder, *};
+use gpui_component::{
+    button::{Button, ButtonVariants},
+    form::{field, h_form},
+    h_flex,
+    input::{Input, InputState},
+    label::Label,
+    notification::NotificationType,
+    text::TextView,
+    v_flex, Icon, IconName, StyledExt, WindowExt,
+};
+use roci_app_components::{error::WithButtonModalError, with_button_error, LoadState};
+use tracing_unwrap::ResultExt;
+
+use crate::{
+    dashboard::{
+        error::GitlabError,
+        project::release::endpoint::{CreateRelease, ProjectReleases, ProjectTags},
+    },
+    state::gitlab::{
+        project::release::{Compare, Release, Tag},
+        GitlabClient,
+    },
+    utils::short_datetime,
+};
+
+mod endpoint;
+
+pub const RELEASES_COUNT: usize = 5;
+pub const TAGS_COUNT: usize = 10;
+
+pub struct Releases {
+    inner: Entity<LoadState<ReleasesInner, WithButtonModalError<GitlabError>>>,
+    gitlab: GitlabClient,
+    project_id: u64,
+    default_branch: String,
+    new_name: Entity<InputState>,
+    new_notes: Entity<InputState>,
+}
+
+impl Releases {
+    pub fn new(
+        window: &mut Window,
+        cx: &mut Context<Self>,
+        gitlab: GitlabClient,
+        project_id: u64,
+        default_branch: String,
+    ) -> Self {
+        let this = Self {
+            inner: cx.new(|_cx| LoadState::Loading),
+            gitlab,
+            project_id,
+            default_branch,
+            new_name: cx.new(|cx| InputState::new(window, cx).placeholder("Tag name if empty")),
+            new_notes: cx.new(|cx| InputState::new(window, cx)),
+        };
+        this.load(window, cx);
+
+        this
+    }
+
+    fn load(&self, window: &mut Window, cx: &mut Context<Self>) {
+        let gitlab = self.gitlab.clone();
+        let project_id = self.project_id;
+        let default_branch = self.default_branch.clone();
+
+        cx.spawn_in(window, async move |this, cx| {
+            match get_releases(gitlab, project_id, default_branch).await {
+                Ok((releases, tags, since_last_release)) => {
+                    let _ = this.update_in(cx, |this, _window, cx| {
+                        let view = cx.entity();
+                        let inner = cx.new(|_cx| {
+                            ReleasesInner::new(view, releases, tags, since_last_release)
+                        });
+                        this.inner = cx.new(|_cx| LoadState::Ready(inner));
+                        cx.notify();
+                    });
+                }
+                Err(error) => {
+                    let _ = this.update_in(cx, |this, _window, cx| {
+                        this.inner = cx.new(|cx| {
+                            with_button_error!(
+                                cx,
+                                "Load error".into(),
+                                format!("Error during load releases"),
+                                error.into()
+                            )
+                        });
+                        cx.notify();
+                    });
+                }
+            }
+        })
+        .detach();
+    }
+
+    fn create_release(&mut self, window: &mut Window, cx: &mut Context<Self>, tag_name: String) {
+        let name = self.new_name.read(cx).value().trim().to_string();
+        let name = if name.is_empty() {
+            tag_name.clone()
+        } else {
+            name
+        };
+        let description = self.new_notes.read(cx).value().to_string();
+        let endpoint = CreateRelease::new(self.project_id, tag_name, name.clone(), description);
+        let gitlab = self.gitlab.clone();
+
+        cx.spawn_in(window, async move |this, cx| {
+            let result = api::ignore(endpoint).query_async(&gitlab).await;
+            let _ = this.update_in(cx, |this, window, cx| match result {
+                Ok(_) => {
+                    window.push_notification(
+                        SharedString::new(format!("Release {} created", name)),
+                        cx,
+                    );
+                    for input in [&this.new_name, &this.new_notes] {
+                        input.update(cx, |input, cx| {
+                            input.set_value(SharedString::new(""), window, cx);
+                        });
+                    }
+                    this.load(window, cx);
+                }
+                Err(error) => window.push_notification(
+                    (
+                        NotificationType::Error,
+                        SharedString::new(format!("Can't create release: {}", error)),
+                    ),
+                    cx,
+                ),
+            });
+        })
+        .detach();
+    }
+}
+
+impl Render for Releases {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        div().child(self.inner.clone())
+    }
+}
+
+#[derive(Constructor)]
+pub struct ReleasesInner {
+    view: Entity<Releases>,
+    releases: Vec<Release>,
+    tags: Vec<Tag>,
+    since_last_release: Option<(String, Compare)>,
+}
+
+impl Render for ReleasesInner {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let view = self.view.clone();
+        let (new_name, new_notes) = {
+            let view = view.read(cx);
+            (view.new_name.clone(), view.new_notes.clone())
+        };
+
+        v_flex()
+            .gap_3()
+            .when_some(
+                self.since_last_release.clone(),
+                |element, (tag_name, compare)| {
+                    let label = format!(
+                        "{} commits since last release ({})",
+                        compare.commits.len(),
+                        tag_name
+                    );
+
+                    element.child(
+                        h_flex().child(Icon::new(IconName::ArrowRight)).child(
+                            Button::new("since-last-release")
+                                .link()
+                                .label(label)
+                                .on_click(move |_, _, _| {
+                                    if let Some(web_url) = compare.web_url.clone() {
+                                        open::that(web_url).unwrap_or_log();
+                                    }
+                                }),
+                        ),
+                    )
+                },
+            )
+            .child(Label::new("Releases").text_lg())
+            .when(self.releases.is_empty(), |element| {
+                element.child("n/a".to_string())
+            })
+            .children(self.releases.iter().map(|release| {
+                let tag_name = release.tag_name.clone();
+                let url = release.links.self_.clone();
+
+                v_flex()
+                    .id(ElementId::Name(SharedString::new(format!(
+                        "release-{}",
+                        tag_name
+                    ))))
+                    .child(
+                        h_flex()
+                            .child(
+                                Button::new("release-link")
+                                    .link()
+                                    .label(release.title())
+                                    .on_click(move |_, _, _| {
+                                        if let Some(url) = url.clone() {
+                                            open::that(url).unwrap_or_log();
+                                        }
+                                    }),
+                            )
+                            .child(format!(
+                                " {} ({})",
+                                release.tag_name,
+                                short_datetime(
+                                    release.released_at.as_ref().unwrap_or(&release.created_at)
+                                )
+                            )),
+                    )
+                    .when_some(release.description.clone(), |element, description| {
+                        element.child(TextView::markdown(
+                            ElementId::Name(SharedString::new(format!(
+                                "release-notes-{}",
+                                tag_name
+                            ))),
+                            description,
+                            window,
+                            cx,
+                        ))
+                    })
+                    .children(release.assets.links.iter().map(|link| {
+                        let url = link.url.clone();
+
+                        Button::new(ElementId::Name(SharedString::new(format!(
+                            "asset-{}",
+                            link.url
+                        ))))
+                        .link()
+                        .icon(IconName::ExternalLink)
+                        .label(link.name.clone())
+                        .on_click(move |_, _, _| {
+                            open::that(url.clone()).unwrap_or_log();
+                        })
+                    }))
+            }))
+            .child(Label::new("Tags").text_lg())
+            .child(
+                h_form()
+                    .child(
+                        field()
+                            .label("New release name")
+                            .child(Input::new(&new_name)),
+                    )
+                    .child(
+                        field()
+                            .label("New release notes (markdown)")
+                            .child(Input::new(&new_notes)),
+                    ),
+            )
+            .when(self.tags.is_empty(), |element| {
+                element.child("n/a".to_string())
+            })
+            .children(self.tags.iter().map(|tag| {
+                let web_url = tag.commit.web_url.clone();
+
+                h_flex()
+                    .id(ElementId::Name(SharedString::new(format!(
+                        "tag-{}",
+                        tag.name
+                    ))))
+                    .gap_1()
+                    .child(Label::new(tag.name.clone()).font_semibold())
+                    .child(
+                        Button::new("tag-commit")
+                            .link()
+                            .label(tag.commit.short_id.clone())
+                            .on_click(move |_, _, _| {
+                                if let Some(web_url) = web_url.clone() {
+                                    open::that(web_url).unwrap_or_log();
+                                }
+                            }),
+                    )
+                    .child(short_datetime(&tag.commit.created_at))
+                    .when(tag.release.is_none(), |element| {
+                        element.child(
+                            Button::new("create-release")
+                                .link()
+                                .label("Create release")
+                                .on_click({
+                                    let view = view.clone();
+                                    let tag_name = tag.name.clone();
+
+                                    move |_, window, cx| {
+                                        view.update(cx, |this, cx| {
+                                            this.create_release(window, cx, tag_name.clone());
+                                        });
+                                    }
+                                }),
+                        )
+                    })
+            }))
+    }
+}
+
+async fn get_releases(
+    gitlab: GitlabClient,
+    project_id: u64,
+    default_branch: String,
+) -> Result<(Vec<Release>, Vec<Tag>, Option<(String, Compare)>), GitlabError> {
+    let releases: Vec<Release> = api::paged(
+        ProjectReleases::new(project_id),
+        api::Pagination::Limit(RELEASES_COUNT),
+    )
+    .query_async(&gitlab)
+    .await?;
+    let tags: Vec<Tag> = api::paged(
+        ProjectTags::new(project_id),
+        api::Pagination::Limit(TAGS_COUNT),
+    )
+    .query_async(&gitlab)
+    .await?;
+
+    let since_last_release = match releases.first() {
+        Some(release) => {
+            let compare: Compare =
+                endpoint::Compare::new(project_id, release.tag_name.clone(), default_branch)
+                    .query_async(&gitlab)
+                    .await?;
+            Some((release.tag_name.clone(), compare))
+        }
+        None => None,
+    };
+
+    Ok((releases, tags, since_last_release))
+}
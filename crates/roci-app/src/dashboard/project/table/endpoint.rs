@@ -0,0 +1,25 @@
+use derive_more::Constructor;
+use gitlab::api::{Endpoint, Pageable, QueryParams};
+
+#[derive(Debug, Constructor)]
+pub struct OpenedMergeRequests {
+    project_id: u64,
+}
+
+impl Endpoint for OpenedMergeRequests {
+    fn method(&self) -> reqwest::Method {
+        reqwest::Method::GET
+    }
+
+    fn endpoint(&self) -> std::borrow::Cow<'static, str> {
+        format!("projects/{}/merge_requests", self.project_id).into()
+    }
+
+    fn parameters(&self) -> QueryParams<'_> {
+        let mut params = QueryParams::default();
+        params.push("state", "opened");
+        params
+    }
+}
+
+impl Pageable for OpenedMergeRequests {}
@@ -0,0 +1,468 @@
+use futures::future::join_all;
+use gitlab::api::{self, projects, AsyncQuery};
+use gpui::{prelude::FluentBuilder, *};
+use gpui_component::{
+    button::{Button, ButtonVariants},
+    h_flex,
+    label::Label,
+    spinner::Spinner,
+    v_flex, ActiveTheme,
+};
+use roci_app_components::{
+    error::{ErrorDetails, WithButtonModalError},
+    table::{SortValue, SortableTable, TableColumn, TableRow},
+};
+use tracing_unwrap::ResultExt;
+
+use crate::{
+    api::snapshot::ProjectSnapshot,
+    config::gitlab_::Fetcher,
+    dashboard::{
+        error::GitlabError,
+        project::{
+            get_project,
+            graphql::{get_projects, Batch, ProjectData},
+            merge_request::get_last_pipeline,
+            pipeline::status_style,
+            table::endpoint::OpenedMergeRequests,
+        },
+        refresh::wait_refresh,
+    },
+    state::{
+        gitlab::{
+            project::{
+                merge_request::MergeRequest,
+                pipeline::{Pipeline, PipelineStatus},
+            },
+            GitlabClient,
+        },
+        navigation::{NavItem, NavKey, NavKind},
+        refresh::RefreshKey,
+        AppState,
+    },
+    tray::TrayItem,
+    utils::{age, project_path},
+};
+
+mod endpoint;
+
+pub struct ProjectsTable {
+    table: Entity<SortableTable<ProjectRow>>,
+    errors: Vec<Entity<WithButtonModalError<GitlabError>>>,
+    loading: bool,
+}
+
+impl ProjectsTable {
+    pub fn new(
+        window: &mut Window,
+        cx: &mut Context<Self>,
+        host: String,
+        instance_url: String,
+        gitlab: GitlabClient,
+        project_ids: Vec<u64>,
+        fetcher: Fetcher,
+    ) -> Self {
+        let table = cx.new(|cx| SortableTable::new(window, cx, vec![]).flag_filter("Only failing"));
+        let refresh_key = RefreshKey::ProjectsTable(host);
+
+        // Rows are replaced in place to keep user sort and filters between refreshes
+        cx.spawn_in(window, async move |this, cx| loop {
+            let results = match fetcher {
+                Fetcher::Rest => {
+                    join_all(
+                        project_ids
+                            .iter()
+                            .map(|project_id| get_row(&gitlab, *project_id)),
+                    )
+                    .await
+                }
+                Fetcher::GraphQl => get_rows(&gitlab, &instance_url, &project_ids).await,
+            };
+
+            let updated = this.update_in(cx, |this, _window, cx| {
+                let mut rows = vec![];
+                this.errors.clear();
+                this.loading = false;
+
+                for (project_id, result) in project_ids.iter().zip(results) {
+                    match result {
+                        Ok(row) => rows.push(row),
+                        Err(error) => {
+                            let details = cx.new(|_cx| {
+                                ErrorDetails::new(
+                                    format!("Error during load project {}", project_id),
+                                    Some(error),
+                                )
+                            });
+                            this.errors.push(cx.new(|_cx| {
+                                WithButtonModalError::new("Load error".into(), details)
+                            }));
+                        }
+                    }
+                }
+
+                AppState::record_pipelines(
+                    cx,
+                    refresh_key.host().to_string(),
+                    rows.iter()
+                        .filter_map(|row| row.last_pipeline.clone())
+                        .collect(),
+                );
+                let refreshes = AppState::global_mut(cx).refreshes_mut();
+                if this.errors.is_empty() {
+                    refreshes.loaded(
+                        &refresh_key,
+                        fingerprint(&rows),
+                        rows.iter().any(|row| {
+                            row.last_pipeline
+                                .as_ref()
+                                .is_some_and(|pipeline| pipeline.status.is_running())
+                        }),
+                    );
+                } else {
+                    refreshes.failed(&refresh_key);
+                }
+
+                this.table.update(cx, |table, cx| table.set_rows(rows, cx));
+                cx.notify();
+            });
+            if updated.is_err() {
+                break;
+            }
+
+            wait_refresh(cx, &refresh_key).await;
+        })
+        .detach();
+
+        Self {
+            table,
+            errors: vec![],
+            loading: true,
+        }
+    }
+
+    pub fn failing_pipelines(&self, cx: &App) -> Vec<TrayItem> {
+        self.table
+            .read(cx)
+            .rows()
+            .iter()
+            .filter(|row| row.pipeline_failing())
+            .filter_map(|row| {
+                row.last_pipeline
+                    .as_ref()
+                    .map(|pipeline| TrayItem::new(row.name.clone(), pipeline.web_url.clone()))
+            })
+            .collect()
+    }
+
+    /// Last default branch pipeline of each project with its full path
+    pub fn last_pipelines(&self, cx: &App) -> Vec<(String, Pipeline)> {
+        self.table
+            .read(cx)
+            .rows()
+            .iter()
+            .filter_map(|row| {
+                row.last_pipeline
+                    .clone()
+                    .map(|pipeline| (project_path(&row.web_url).to_string(), pipeline))
+            })
+            .collect()
+    }
+
+    pub fn merge_request_statuses(&self, cx: &App) -> Vec<String> {
+        self.table
+            .read(cx)
+            .rows()
+            .iter()
+            .flat_map(|row| {
+                row.merge_requests
+                    .iter()
+                    .map(|merge_request| merge_request.detailed_merge_status.clone())
+            })
+            .collect()
+    }
+
+    pub fn snapshots(&self, cx: &App) -> Vec<ProjectSnapshot> {
+        self.table
+            .read(cx)
+            .rows()
+            .iter()
+            .map(|row| {
+                ProjectSnapshot::new(
+                    row.project_id,
+                    &row.web_url,
+                    row.last_pipeline.as_ref(),
+                    &row.merge_requests,
+                )
+            })
+            .collect()
+    }
+
+    pub fn nav_items(&self, host: &str, cx: &App) -> Vec<NavItem> {
+        self.table
+            .read(cx)
+            .rows()
+            .iter()
+            .map(|row| NavItem {
+                key: NavKey::new(NavKind::Project, row.web_url.clone()),
+                host: host.to_string(),
+                id: row.project_id,
+            })
+            .collect()
+    }
+}
+
+impl Render for ProjectsTable {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .when(self.loading, |element| element.child(Spinner::new()))
+            .children(self.errors.iter().cloned())
+            .child(self.table.clone())
+    }
+}
+
+pub struct ProjectRow {
+    project_id: u64,
+    name: String,
+    namespace: String,
+    web_url: String,
+    last_pipeline: Option<Pipeline>,
+    opened_merge_requests: usize,
+    failing_merge_requests: usize,
+    merge_requests: Vec<MergeRequest>,
+    last_activity_at: String,
+}
+
+impl ProjectRow {
+    fn from_data(project_id: u64, data: ProjectData) -> Self {
+        Self {
+            project_id,
+            name: data.project.name,
+            namespace: data.project.namespace.full_path,
+            web_url: data.project.web_url,
+            last_pipeline: data.pipelines.into_iter().last(),
+            opened_merge_requests: data.merge_requests.len(),
+            merge_requests: data
+                .merge_requests
+                .iter()
+                .map(|merge_request| (**merge_request).clone())
+                .collect(),
+            failing_merge_requests: data
+                .merge_requests
+                .iter()
+                .filter(|merge_request| {
+                    merge_request
+                        .last_pipeline
+                        .as_ref()
+                        .is_some_and(|pipeline| pipeline.status.is_error())
+                })
+                .count(),
+            last_activity_at: data.project.last_activity_at,
+        }
+    }
+
+    fn pipeline_failing(&self) -> bool {
+        self.last_pipeline
+            .as_ref()
+            .is_some_and(|pipeline| pipeline.status.is_error())
+    }
+}
+
+impl TableRow for ProjectRow {
+    fn columns() -> Vec<TableColumn> {
+        vec![
+            TableColumn::new("Project".into(), None),
+            TableColumn::new("Namespace".into(), None),
+            TableColumn::new("Pipeline".into(), Some(px(120.))),
+            TableColumn::new("Open MRs".into(), Some(px(120.))),
+            TableColumn::new("Failing MRs".into(), Some(px(120.))),
+            TableColumn::new("Last activity".into(), Some(px(140.))),
+        ]
+    }
+
+    fn cell(&self, column: usize, _window: &mut Window, cx: &mut App) -> AnyElement {
+        match column {
+            0 => {
+                let web_url = self.web_url.clone();
+
+                Button::new(ElementId::Integer(self.project_id))
+                    .link()
+                    .label(self.name.clone())
+                    .on_click(move |_, _, _| {
+                        open::that(web_url.clone()).unwrap_or_log();
+                    })
+                    .into_any_element()
+            }
+            1 => Label::new(self.namespace.clone()).into_any_element(),
+            2 => match &self.last_pipeline {
+                Some(pipeline) => {
+                    let style = status_style(pipeline.status, cx);
+                    let web_url = pipeline.web_url.clone();
+
+                    h_flex()
+                        .gap_1()
+                        .child(
+                            Button::new(ElementId::Integer(pipeline.id))
+                                .icon(style.icon(cx))
+                                .link()
+                                .tooltip(pipeline.status.to_string())
+                                .on_click(move |_, _, _| {
+                                    open::that(web_url.clone()).unwrap_or_log();
+                                }),
+                        )
+                        .child(age(&pipeline.created_at))
+                        .into_any_element()
+                }
+                None => Label::new("n/a").into_any_element(),
+            },
+            3 => Label::new(self.opened_merge_requests.to_string()).into_any_element(),
+            4 => {
+                let label = Label::new(self.failing_merge_requests.to_string());
+
+                if self.failing_merge_requests > 0 {
+                    label.text_color(cx.theme().red).into_any_element()
+                } else {
+                    label.into_any_element()
+                }
+            }
+            _ => Label::new(age(&self.last_activity_at)).into_any_element(),
+        }
+    }
+
+    fn sort_value(&self, column: usize) -> SortValue {
+        match column {
+            0 => SortValue::Text(self.name.to_lowercase()),
+            1 => SortValue::Text(self.namespace.to_lowercase()),
+            2 => match &self.last_pipeline {
+                Some(pipeline) => SortValue::Number(match pipeline.status {
+                    PipelineStatus::Failed => 0,
+                    PipelineStatus::Success => 2,
+                    _ => 1,
+                }),
+                None => SortValue::None,
+            },
+            3 => SortValue::Number(self.opened_merge_requests as i64),
+            4 => SortValue::Number(self.failing_merge_requests as i64),
+            _ => SortValue::Text(self.last_activity_at.clone()),
+        }
+    }
+
+    fn matches(&self, filter: &str) -> bool {
+        self.name.to_lowercase().contains(filter) || self.namespace.to_lowercase().contains(filter)
+    }
+
+    fn flagged(&self) -> bool {
+        self.pipeline_failing() || self.failing_merge_requests > 0
+    }
+
+    fn highlighted(&self, cx: &App) -> bool {
+        AppState::global(cx).is_selected(&NavKey::new(NavKind::Project, self.web_url.clone()))
+    }
+}
+
+fn fingerprint(rows: &[ProjectRow]) -> String {
+    rows.iter()
+        .map(|row| {
+            format!(
+                "{}:{}:{}:{}:{}",
+                row.project_id,
+                row.last_pipeline
+                    .as_ref()
+                    .map(|pipeline| format!("{}{}", pipeline.id, pipeline.status))
+                    .unwrap_or_default(),
+                row.opened_merge_requests,
+                row.failing_merge_requests,
+                row.last_activity_at
+            )
+        })
+        .collect::<Vec<String>>()
+        .join(",")
+}
+
+async fn get_row(gitlab: &GitlabClient, project_id: u64) -> Result<ProjectRow, GitlabError> {
+    let project = get_project(gitlab.clone(), project_id).await?;
+
+    // Same last pipeline as list layout
+    let pipelines_endpoint = projects::pipelines::Pipelines::builder()
+        .project(project_id)
+        .source(projects::pipelines::PipelineSource::Push)
+        .ref_(&project.default_branch)
+        .build()?;
+    let (pipelines, merge_requests) = futures::join!(
+        async {
+            let pipelines: Result<Vec<Pipeline>, _> =
+                api::paged(pipelines_endpoint, api::Pagination::Limit(1))
+                    .query_async(gitlab)
+                    .await;
+            pipelines
+        },
+        async {
+            let merge_requests: Result<Vec<MergeRequest>, _> =
+                api::paged(OpenedMergeRequests::new(project_id), api::Pagination::All)
+                    .query_async(gitlab)
+                    .await;
+            merge_requests
+        }
+    );
+    let (pipelines, merge_requests) = (pipelines?, merge_requests?);
+
+    // A merge request whose pipeline can't be fetched is not counted rather than failing the row
+    let failing_merge_requests = join_all(merge_requests.iter().map(|merge_request| async {
+        get_last_pipeline(gitlab, project_id, merge_request.iid)
+            .await
+            .inspect_err(|error| {
+                tracing::warn!(
+                    "Unable to get last pipeline of merge request !{} of project {}: {}",
+                    merge_request.iid,
+                    project_id,
+                    error
+                )
+            })
+            .ok()
+            .flatten()
+            .is_some_and(|pipeline| pipeline.status.is_error())
+    }))
+    .await
+    .into_iter()
+    .filter(|failing| *failing)
+    .count();
+
+    Ok(ProjectRow {
+        project_id,
+        name: project.name,
+        namespace: project.namespace.full_path,
+        web_url: project.web_url,
+        last_pipeline: pipelines.into_iter().next(),
+        opened_merge_requests: merge_requests.len(),
+        failing_merge_requests,
+        last_activity_at: project.last_activity_at,
+        merge_requests,
+    })
+}
+
+/// Rows of all projects from batched GraphQL queries, in `project_ids` order
+async fn get_rows(
+    gitlab: &GitlabClient,
+    instance_url: &str,
+    project_ids: &[u64],
+) -> Vec<Result<ProjectRow, GitlabError>> {
+    match get_projects(gitlab, instance_url, project_ids, &Batch::Table).await {
+        Ok(mut projects) => project_ids
+            .iter()
+            .map(|project_id| match projects.remove(project_id) {
+                Some(data) => Ok(ProjectRow::from_data(*project_id, data)),
+                None => Err(GitlabError::GraphQl(format!(
+                    "Project {} not found",
+                    project_id
+                ))),
+            })
+            .collect(),
+        Err(error) => {
+            let message = error.to_string();
+            project_ids
+                .iter()
+                .map(|_| Err(GitlabError::GraphQl(message.clone())))
+                .collect()
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use gpui::{prelude::FluentBuilder, *};
+use gpui_component::spinner::Spinner;
+
+use crate::dashboard::project::Projects;
+
+/// Popped out project, looked up again in its instance projects as batches replace them
+pub struct ProjectPopOut {
+    projects: Entity<Projects>,
+    project_id: u64,
+    _projects: Subscription,
+}
+
+impl ProjectPopOut {
+    pub fn new(cx: &mut Context<Self>, projects: Entity<Projects>, project_id: u64) -> Self {
+        let _projects = cx.observe(&projects, |_this, _projects, cx| cx.notify());
+
+        Self {
+            projects,
+            project_id,
+            _projects,
+        }
+    }
+}
+
+impl Render for ProjectPopOut {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let project = self.projects.read(cx).project(self.project_id, cx);
+
+        div()
+            .when(project.is_none(), |element| element.child(Spinner::new()))
+            .children(project)
+    }
+}
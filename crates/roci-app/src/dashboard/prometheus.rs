@@ -0,0 +1,47 @@
+use gpui::*;
+
+use crate::{
+    dashboard::Dashboard,
+    prometheus::{DashboardMetrics, PipelineMetric},
+};
+
+impl Dashboard {
+    pub fn prometheus_metrics(&self, cx: &App) -> DashboardMetrics {
+        let mut metrics = DashboardMetrics::default();
+
+        for projects in &self.projects.read(cx).0 {
+            let projects = projects.read(cx);
+            let host = projects.host().to_string();
+            metrics
+                .pipelines
+                .extend(
+                    projects
+                        .last_pipelines(cx)
+                        .into_iter()
+                        .map(|(project, pipeline)| PipelineMetric {
+                            instance: host.clone(),
+                            project,
+                            ref_: pipeline.ref_,
+                            status: pipeline.status,
+                        }),
+                );
+            for status in projects.merge_request_statuses(cx) {
+                *metrics
+                    .merge_requests
+                    .entry((host.clone(), status))
+                    .or_default() += 1;
+            }
+        }
+        for todos in &self.todos.read(cx).0 {
+            let todos = todos.read(cx);
+            for action in todos.actions(cx) {
+                *metrics
+                    .todos
+                    .entry((todos.host().to_string(), action))
+                    .or_default() += 1;
+            }
+        }
+
+        metrics
+    }
+}
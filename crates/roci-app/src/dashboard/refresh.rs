@@ -0,0 +1,70 @@
+use std::{
+    cell::Cell,
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+use gpui::*;
+
+use crate::state::{refresh::RefreshKey, AppState};
+
+/// Delay between two checks while refreshes are paused
+pub const PAUSED_CHECK_EVERY: Duration = Duration::from_secs(5);
+/// Delay between two checks of an early refresh request (eg. from a webhook event)
+pub const TRIGGER_CHECK_EVERY: Duration = Duration::from_secs(1);
+/// A window not drawn during this delay is minimized or covered
+pub const VISIBILITY_CHECK_EVERY: Duration = Duration::from_secs(5);
+
+/// Wait until `key` must be refreshed again
+pub async fn wait_refresh(cx: &mut AsyncWindowContext, key: &RefreshKey) {
+    let Ok(interval) = cx.update(|_window, cx| AppState::global(cx).refresh_interval(key)) else {
+        return;
+    };
+    let started = Instant::now();
+    while started.elapsed() < interval {
+        Timer::after(TRIGGER_CHECK_EVERY.min(interval.saturating_sub(started.elapsed()))).await;
+
+        if cx
+            .update(|_window, cx| AppState::global_mut(cx).refreshes_mut().take_triggered(key))
+            .unwrap_or(false)
+        {
+            break;
+        }
+    }
+
+    while cx
+        .update(|_window, cx| AppState::global(cx).refresh_paused())
+        .unwrap_or(false)
+    {
+        Timer::after(PAUSED_CHECK_EVERY).await;
+    }
+}
+
+/// Track whether `window` is hidden, to pause refreshes. Platforms stop requesting frames of
+/// minimized or covered windows, so a window not drawn lately (and inactive) is hidden.
+pub fn watch_visibility(window: &mut Window, cx: &mut App) {
+    window
+        .spawn(cx, async move |cx| loop {
+            let drawn = Rc::new(Cell::new(false));
+            let Ok(()) = cx.update(|window, _cx| {
+                let drawn = drawn.clone();
+                window.on_next_frame(move |_window, cx| {
+                    drawn.set(true);
+                    AppState::global_mut(cx).refreshes_mut().set_hidden(false);
+                });
+            }) else {
+                break;
+            };
+
+            Timer::after(VISIBILITY_CHECK_EVERY).await;
+
+            let Ok(()) = cx.update(|window, cx| {
+                if !drawn.get() && !window.is_window_active() {
+                    AppState::global_mut(cx).refreshes_mut().set_hidden(true);
+                }
+            }) else {
+                break;
+            };
+        })
+        .detach();
+}
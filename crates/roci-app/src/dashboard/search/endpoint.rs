@@ -0,0 +1,53 @@
+use derive_more::Constructor;
+use gitlab::api::{Endpoint, QueryParams};
+
+use crate::dashboard::search::SEARCH_RESULTS_COUNT;
+
+#[derive(Debug, Constructor)]
+pub struct Search<'a> {
+    scope: &'a str,
+    search: &'a str,
+}
+
+impl<'a> Endpoint for Search<'a> {
+    fn method(&self) -> reqwest::Method {
+        reqwest::Method::GET
+    }
+
+    fn endpoint(&self) -> std::borrow::Cow<'static, str> {
+        "search".into()
+    }
+
+    fn parameters(&self) -> QueryParams<'_> {
+        let mut params = QueryParams::default();
+        params.push("scope", self.scope);
+        params.push("search", self.search);
+        params.push("per_page", SEARCH_RESULTS_COUNT);
+        params
+    }
+}
+
+#[derive(Debug, Constructor)]
+pub struct ProjectSearch<'a> {
+    project_id: u64,
+    scope: &'a str,
+    search: &'a str,
+}
+
+impl<'a> Endpoint for ProjectSearch<'a> {
+    fn method(&self) -> reqwest::Method {
+        reqwest::Method::GET
+    }
+
+    fn endpoint(&self) -> std::borrow::Cow<'static, str> {
+        format!("projects/{}/search", self.project_id).into()
+    }
+
+    fn parameters(&self) -> QueryParams<'_> {
+        let mut params = QueryParams::default();
+        params.push("scope", self.scope);
+        params.push("search", self.search);
+        params.push("per_page", SEARCH_RESULTS_COUNT);
+        params
+    }
+}
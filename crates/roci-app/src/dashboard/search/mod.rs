@@ -0,0 +1,360 @@
+use std::time::Duration;
+
+use futures::future::join_all;
+use gitlab::api::AsyncQuery;
+use gpui::{prelude::FluentBuilder, *};
+use gpui_component::{
+    button::{Button, ButtonVariants},
+    h_flex,
+    input::{Input, InputEvent, InputState},
+    label::Label,
+    spinner::Spinner,
+    text::TextView,
+    v_flex, Icon, IconName, StyledExt, WindowExt,
+};
+use roci_app_components::{
+    badge::badge,
+    error::{ErrorDetails, WithButtonModalError},
+};
+use tracing_unwrap::ResultExt;
+
+use crate::{
+    config::gitlab_::Gitlab,
+    dashboard::{
+        error::GitlabError,
+        search::endpoint::{ProjectSearch, Search},
+        Dashboard,
+    },
+    state::{
+        gitlab::{
+            issue::Issue,
+            project::{commit::Commit, merge_request::MergeRequest},
+            search::{SearchProject, SearchResult, SearchResultKind},
+            Gitlabs,
+        },
+        AppState,
+    },
+    utils::Truncate,
+};
+
+mod endpoint;
+
+pub const SEARCH_RESULTS_COUNT: u64 = 10;
+pub const SEARCH_MIN_LENGTH: usize = 3;
+pub const SEARCH_DEBOUNCE: Duration = Duration::from_millis(300);
+
+impl Dashboard {
+    pub fn show_search_dialog(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let palette = cx.new(|cx| SearchPalette::new(window, cx));
+        let query = palette.read(cx).query.clone();
+
+        window.open_dialog(cx, move |dialog, _window, _cx| {
+            dialog
+                .title("Search everywhere")
+                .width(px(900.))
+                .child(palette.clone())
+        });
+        window.focus(&query.focus_handle(cx));
+    }
+}
+
+pub struct SearchPalette {
+    query: Entity<InputState>,
+    results: Vec<SearchResult>,
+    errors: Vec<Entity<WithButtonModalError<GitlabError>>>,
+    searching: bool,
+    generation: u64,
+}
+
+impl SearchPalette {
+    fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let query = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder("Search issues, merge requests, projects, commits")
+        });
+        cx.subscribe_in(&query, window, Self::on_query_event)
+            .detach();
+
+        Self {
+            query,
+            results: vec![],
+            errors: vec![],
+            searching: false,
+            generation: 0,
+        }
+    }
+
+    fn on_query_event(
+        &mut self,
+        _: &Entity<InputState>,
+        event: &InputEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !matches!(event, InputEvent::Change | InputEvent::PressEnter { .. }) {
+            return;
+        }
+
+        self.generation += 1;
+        let generation = self.generation;
+        let query = self.query.read(cx).value().trim().to_string();
+
+        if query.chars().count() < SEARCH_MIN_LENGTH {
+            self.results.clear();
+            self.errors.clear();
+            self.searching = false;
+            cx.notify();
+            return;
+        }
+
+        let gitlabs = AppState::global(cx).gitlabs();
+        let configs = AppState::global(cx).config().gitlabs.clone();
+        self.searching = true;
+        cx.notify();
+
+        cx.spawn_in(window, async move |this, cx| {
+            Timer::after(SEARCH_DEBOUNCE).await;
+            if this
+                .read_with(cx, |this, _cx| this.generation != generation)
+                .unwrap_or(true)
+            {
+                return;
+            }
+
+            let results = join_all(configs.into_iter().map(|config| {
+                let gitlabs = gitlabs.clone();
+                let query = query.clone();
+
+                async move {
+                    let host = config.host.clone();
+                    (host, search_instance(gitlabs, config, query).await)
+                }
+            }))
+            .await;
+
+            let _ = this.update_in(cx, |this, _window, cx| {
+                if this.generation != generation {
+                    return;
+                }
+
+                this.searching = false;
+                this.results.clear();
+                this.errors.clear();
+                for (host, result) in results {
+                    match result {
+                        Ok(results) => this.results.extend(results),
+                        Err(error) => {
+                            let details = cx.new(|_cx| {
+                                ErrorDetails::new(
+                                    format!("Error during search on {}", host),
+                                    Some(error),
+                                )
+                            });
+                            this.errors.push(cx.new(|_cx| {
+                                WithButtonModalError::new(format!("{} search error", host), details)
+                            }));
+                        }
+                    }
+                }
+                cx.notify();
+            });
+        })
+        .detach();
+    }
+}
+
+impl Render for SearchPalette {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .gap_3()
+            .child(
+                h_flex()
+                    .gap_1()
+                    .child(Icon::new(IconName::Search))
+                    .child(Input::new(&self.query))
+                    .when(self.searching, |element| element.child(Spinner::new())),
+            )
+            .children(self.errors.iter().cloned())
+            .when(
+                !self.searching
+                    && self.results.is_empty()
+                    && !self.query.read(cx).value().is_empty(),
+                |element| element.child("n/a".to_string()),
+            )
+            .children(self.results.iter().enumerate().map(|(index, result)| {
+                let icon = match result.kind {
+                    SearchResultKind::Project => IconName::Folder,
+                    SearchResultKind::Issue => IconName::CircleCheck,
+                    SearchResultKind::MergeRequest => IconName::LayoutDashboard,
+                    SearchResultKind::Commit => IconName::Dash,
+                };
+                let web_url = result.web_url.clone();
+                let result_ = result.clone();
+
+                h_flex()
+                    .id(ElementId::NamedInteger(
+                        "search-result".into(),
+                        index as u64,
+                    ))
+                    .gap_1()
+                    .child(badge(result.host.clone(), cx))
+                    .child(Icon::new(icon))
+                    .child(Label::new(result.reference.clone()).font_semibold())
+                    .child(
+                        Button::new("open")
+                            .link()
+                            .label(result.title.clone().truncated(120))
+                            .on_click(move |_, _, _| {
+                                open::that(web_url.clone()).unwrap_or_log();
+                            }),
+                    )
+                    .child(
+                        Button::new("details")
+                            .icon(IconName::PanelRightOpen)
+                            .link()
+                            .tooltip("Details")
+                            .on_click(move |_, window, cx| {
+                                show_result_sheet(window, cx, result_.clone());
+                            }),
+                    )
+            }))
+    }
+}
+
+fn show_result_sheet(window: &mut Window, cx: &mut App, result: SearchResult) {
+    window.open_sheet(cx, move |sheet, window, cx| {
+        let web_url = result.web_url.clone();
+
+        sheet
+            .title(format!("{} {}", result.reference, result.title))
+            .size(px(600.))
+            .child(
+                v_flex()
+                    .gap_3()
+                    .child(Label::new(result.host.clone()).text_xs())
+                    .child(
+                        Button::new("open-in-browser")
+                            .icon(IconName::ExternalLink)
+                            .label("Open in browser")
+                            .on_click(move |_, _, _| {
+                                open::that(web_url.clone()).unwrap_or_log();
+                            }),
+                    )
+                    .child(TextView::markdown(
+                        "search-result-details",
+                        result.details.clone().unwrap_or_default(),
+                        window,
+                        cx,
+                    )),
+            )
+    });
+}
+
+async fn search_instance(
+    gitlabs: Gitlabs,
+    config: Gitlab,
+    query: String,
+) -> Result<Vec<SearchResult>, GitlabError> {
+    let gitlab = gitlabs.get(&config).await?;
+    let host = config.host.clone();
+
+    let projects_endpoint = Search::new("projects", &query);
+    let issues_endpoint = Search::new("issues", &query);
+    let merge_requests_endpoint = Search::new("merge_requests", &query);
+    let commits_endpoints = config
+        .project_ids
+        .iter()
+        .map(|project_id| {
+            (
+                *project_id,
+                ProjectSearch::new(*project_id, "commits", &query),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let (projects, issues, merge_requests, commits) = futures::join!(
+        async {
+            let projects: Result<Vec<SearchProject>, _> =
+                projects_endpoint.query_async(&gitlab).await;
+            projects
+        },
+        async {
+            let issues: Result<Vec<Issue>, _> = issues_endpoint.query_async(&gitlab).await;
+            issues
+        },
+        async {
+            let merge_requests: Result<Vec<MergeRequest>, _> =
+                merge_requests_endpoint.query_async(&gitlab).await;
+            merge_requests
+        },
+        join_all(
+            commits_endpoints
+                .iter()
+                .map(|(project_id, endpoint)| async {
+                    let commits: Result<Vec<Commit>, _> = endpoint.query_async(&gitlab).await;
+                    (*project_id, commits)
+                })
+        ),
+    );
+    let (projects, issues, merge_requests) = (projects?, issues?, merge_requests?);
+
+    let mut results = vec![];
+    results.extend(projects.into_iter().map(|project| SearchResult {
+        host: host.clone(),
+        kind: SearchResultKind::Project,
+        reference: format!("#{}", project.id),
+        title: project.name_with_namespace,
+        web_url: project.web_url,
+        details: project.description,
+    }));
+    results.extend(issues.into_iter().map(|issue| SearchResult {
+        host: host.clone(),
+        kind: SearchResultKind::Issue,
+        reference: format!("#{}", issue.iid),
+        title: issue.title,
+        web_url: issue.web_url,
+        details: issue.description,
+    }));
+    results.extend(
+        merge_requests
+            .into_iter()
+            .map(|merge_request| SearchResult {
+                host: host.clone(),
+                kind: SearchResultKind::MergeRequest,
+                reference: format!("!{}", merge_request.iid),
+                title: merge_request.title,
+                web_url: merge_request.web_url,
+                details: merge_request.description,
+            }),
+    );
+    for (project_id, commits) in commits {
+        // Empty, disabled or forbidden repository must not hide other results
+        let commits = match commits {
+            Ok(commits) => commits,
+            Err(error) => {
+                tracing::warn!(
+                    "Unable to search commits of project {} on {}: {}",
+                    project_id,
+                    host,
+                    error
+                );
+                continue;
+            }
+        };
+        results.extend(commits.into_iter().map(|commit| SearchResult {
+            host: host.clone(),
+            kind: SearchResultKind::Commit,
+            reference: commit.short_id,
+            title: commit.title,
+            web_url: commit.web_url.unwrap_or(format!(
+                "{}{}/projects/{}",
+                config.protocol(),
+                host,
+                project_id
+            )),
+            details: None,
+        }));
+    }
+
+    Ok(results)
+}
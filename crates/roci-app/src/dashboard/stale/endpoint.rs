@@ -0,0 +1,54 @@
+use derive_more::Constructor;
+use gitlab::api::{Endpoint, Pageable, QueryParams};
+
+#[derive(Debug, Constructor)]
+pub struct InactiveMergeRequests {
+    project_id: u64,
+    /// ISO 8601 date
+    updated_before: String,
+}
+
+impl Endpoint for InactiveMergeRequests {
+    fn method(&self) -> reqwest::Method {
+        reqwest::Method::GET
+    }
+
+    fn endpoint(&self) -> std::borrow::Cow<'static, str> {
+        format!("projects/{}/merge_requests", self.project_id).into()
+    }
+
+    fn parameters(&self) -> QueryParams<'_> {
+        let mut params = QueryParams::default();
+        params.push("state", "opened");
+        params.push("updated_before", self.updated_before.as_str());
+        params.push("order_by", "updated_at");
+        params.push("sort", "asc");
+        params
+    }
+}
+
+impl Pageable for InactiveMergeRequests {}
+
+/// Opened and ready (not draft) merge requests of current user, on every project
+#[derive(Debug, Default)]
+pub struct MyReadyMergeRequests;
+
+impl Endpoint for MyReadyMergeRequests {
+    fn method(&self) -> reqwest::Method {
+        reqwest::Method::GET
+    }
+
+    fn endpoint(&self) -> std::borrow::Cow<'static, str> {
+        "merge_requests".into()
+    }
+
+    fn parameters(&self) -> QueryParams<'_> {
+        let mut params = QueryParams::default();
+        params.push("scope", "created_by_me");
+        params.push("state", "opened");
+        params.push("wip", "no");
+        params
+    }
+}
+
+impl Pageable for MyReadyMergeRequests {}
@@ -0,0 +1,378 @@
+use chrono::{TimeDelta, Utc};
+use futures::future::join_all;
+use gitlab::api::{self, AsyncQuery};
+use gpui::{prelude::FluentBuilder, *};
+use gpui_component::{
+    button::{Button, ButtonVariants},
+    h_flex,
+    label::Label,
+    notification::NotificationType,
+    spinner::Spinner,
+    v_flex, ActiveTheme, Icon, IconName, StyledExt, WindowExt,
+};
+use roci_app_components::{
+    badge::badge, error::WithButtonModalError, with_button_error, LoadState,
+};
+use tracing_unwrap::ResultExt;
+
+use crate::{
+    config::{gitlab_::Gitlab, layout::ALL_INSTANCES},
+    dashboard::{
+        error::GitlabError,
+        refresh::wait_refresh,
+        stale::endpoint::{InactiveMergeRequests, MyReadyMergeRequests},
+    },
+    state::{
+        gitlab::{
+            project::merge_request::{MergeRequest, MERGE_STATUS_NOT_APPROVED},
+            GitlabClient,
+        },
+        navigation::{NavItem, NavKey, NavKind},
+        refresh::RefreshKey,
+        AppState,
+    },
+    utils::{age, days_since, project_path},
+};
+
+mod endpoint;
+
+/// Stale merge requests fetched at most per project
+pub const STALE_MERGE_REQUESTS_COUNT: usize = 50;
+/// My opened merge requests checked for a too long review
+pub const MY_MERGE_REQUESTS_COUNT: usize = 100;
+
+pub struct StaleMergeRequests {
+    inner: Entity<LoadState<StaleMergeRequestsInner, WithButtonModalError<GitlabError>>>,
+    host: String,
+}
+
+impl StaleMergeRequests {
+    pub fn new(window: &mut Window, cx: &mut Context<Self>, config: Gitlab) -> Self {
+        let host = config.host.clone();
+        let host_ = config.host.clone();
+        let gitlabs = AppState::global(cx).gitlabs();
+        let aging = AppState::global(cx).config().merge_request_aging.clone();
+
+        let config_ = config.clone();
+        let refresh_key = RefreshKey::StaleMergeRequests(config.host.clone());
+        cx.spawn_in(window, async move |stale, cx| {
+            let result = match gitlabs.get(&config_).await {
+                Ok(gitlab) => get_merge_requests(
+                    gitlab,
+                    &config_.project_ids,
+                    aging.stale_after_days,
+                    aging.notify_waiting_review_after_days,
+                )
+                .await
+                .map_err(|error| {
+                    (
+                        format!("Error during load merge requests of {}", host),
+                        error,
+                    )
+                }),
+                Err(error) => Err((
+                    format!("Error during connect gitlab {}", host),
+                    error.into(),
+                )),
+            };
+
+            let _ = stale.update_in(cx, |stale, window, cx| {
+                match result {
+                    Ok((merge_requests, waiting)) => {
+                        AppState::global_mut(cx).refreshes_mut().loaded(
+                            &refresh_key,
+                            merge_requests
+                                .iter()
+                                .map(|merge_request| {
+                                    format!("{}:{}", merge_request.id, merge_request.updated_at)
+                                })
+                                .collect::<Vec<String>>()
+                                .join(","),
+                            false,
+                        );
+                        for merge_request in waiting {
+                            if AppState::global_mut(cx).first_notification(&host, merge_request.id)
+                            {
+                                window.push_notification(
+                                    (
+                                        NotificationType::Warning,
+                                        SharedString::new(format!(
+                                            "Your merge request \"{}\" waits for review for {}",
+                                            merge_request.title,
+                                            age(&merge_request.updated_at)
+                                        )),
+                                    ),
+                                    cx,
+                                );
+                            }
+                        }
+                        let inner = cx.new(|_cx| StaleMergeRequestsInner(merge_requests));
+                        stale.inner = cx.new(|_cx| LoadState::Ready(inner));
+                    }
+                    Err((message, error)) => {
+                        AppState::global_mut(cx)
+                            .refreshes_mut()
+                            .failed(&refresh_key);
+                        stale.inner = cx.new(|cx| {
+                            with_button_error!(cx, "Load error".into(), message, error.into())
+                        });
+                    }
+                }
+                cx.notify();
+            });
+        })
+        .detach();
+
+        let config_ = config.clone();
+        let refresh_key = RefreshKey::StaleMergeRequests(config.host.clone());
+        cx.spawn_in(window, async move |stale, cx| {
+            wait_refresh(cx, &refresh_key).await;
+            let _ = stale.update_in(cx, |stale, window, cx| {
+                *stale = Self::new(window, cx, config_.clone());
+                cx.notify();
+            });
+        })
+        .detach();
+
+        Self {
+            inner: cx.new(|_cx| LoadState::Loading),
+            host: host_,
+        }
+    }
+
+    pub fn nav_items(&self, cx: &App) -> Vec<NavItem> {
+        let LoadState::Ready(merge_requests) = self.inner.read(cx) else {
+            return vec![];
+        };
+
+        merge_requests
+            .read(cx)
+            .0
+            .iter()
+            .map(|merge_request| NavItem {
+                key: NavKey::new(NavKind::MergeRequest, merge_request.web_url.clone()),
+                host: self.host.clone(),
+                id: merge_request.id,
+            })
+            .collect()
+    }
+}
+
+impl Render for StaleMergeRequests {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .child(
+                h_flex()
+                    .child(Icon::new(IconName::ArrowRight))
+                    .child(Label::new(format!("{} stale merge requests", self.host)).text_xl()),
+            )
+            .child(self.inner.clone())
+    }
+}
+
+/// Least recently updated first
+pub struct StaleMergeRequestsInner(Vec<MergeRequest>);
+
+impl Render for StaleMergeRequestsInner {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .paddings(Edges::all(px(5.)))
+            .when(!self.0.is_empty(), |element| {
+                element.children(
+                    self.0
+                        .iter()
+                        .map(|merge_request| render_merge_request(merge_request, None, cx)),
+                )
+            })
+            .when(self.0.is_empty(), |element| {
+                element.child("n/a".to_string())
+            })
+    }
+}
+
+/// Stale merge requests of every instance, least recently updated first
+pub struct UnifiedStaleMergeRequests {
+    sources: Vec<Entity<StaleMergeRequests>>,
+}
+
+impl UnifiedStaleMergeRequests {
+    pub fn new(cx: &mut Context<Self>, sources: Vec<Entity<StaleMergeRequests>>) -> Self {
+        for source in &sources {
+            cx.observe(source, |_this, _source, cx| cx.notify())
+                .detach();
+        }
+
+        Self { sources }
+    }
+
+    fn merge_requests(&self, cx: &App) -> Vec<(String, MergeRequest)> {
+        let mut merge_requests = vec![];
+        for source in &self.sources {
+            let source = source.read(cx);
+            if let LoadState::Ready(inner) = source.inner.read(cx) {
+                merge_requests.extend(
+                    inner
+                        .read(cx)
+                        .0
+                        .iter()
+                        .map(|merge_request| (source.host.clone(), merge_request.clone())),
+                );
+            }
+        }
+        merge_requests.sort_by(|(_, a), (_, b)| a.updated_at.cmp(&b.updated_at));
+
+        merge_requests
+    }
+
+    pub fn nav_items(&self, cx: &App) -> Vec<NavItem> {
+        self.merge_requests(cx)
+            .into_iter()
+            .map(|(host, merge_request)| NavItem {
+                key: NavKey::new(NavKind::MergeRequest, merge_request.web_url),
+                host,
+                id: merge_request.id,
+            })
+            .collect()
+    }
+}
+
+impl Render for UnifiedStaleMergeRequests {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let merge_requests = self.merge_requests(cx);
+        let mut loading = false;
+        let mut errors = vec![];
+        for source in &self.sources {
+            match source.read(cx).inner.read(cx) {
+                LoadState::Loading => loading = true,
+                LoadState::Ready(_) => {}
+                LoadState::Error(error) => errors.push(error.clone()),
+            }
+        }
+
+        div()
+            .child(
+                h_flex()
+                    .child(Icon::new(IconName::ArrowRight))
+                    .child(Label::new(format!("{} stale merge requests", ALL_INSTANCES)).text_xl()),
+            )
+            .when(loading, |element| element.child(Spinner::new()))
+            .children(errors)
+            .child(
+                v_flex()
+                    .paddings(Edges::all(px(5.)))
+                    .when(merge_requests.is_empty() && !loading, |element| {
+                        element.child("n/a".to_string())
+                    })
+                    .children(merge_requests.iter().map(|(host, merge_request)| {
+                        render_merge_request(merge_request, Some(host), cx)
+                    })),
+            )
+    }
+}
+
+fn render_merge_request(
+    merge_request: &MergeRequest,
+    host: Option<&str>,
+    cx: &App,
+) -> impl IntoElement {
+    let web_url = merge_request.web_url.clone();
+    let selected = AppState::global(cx).is_selected(&NavKey::new(
+        NavKind::MergeRequest,
+        merge_request.web_url.clone(),
+    ));
+    let id = match host {
+        Some(host) => ElementId::NamedInteger(SharedString::new(host), merge_request.id),
+        None => ElementId::Integer(merge_request.id),
+    };
+
+    h_flex()
+        .id(id)
+        .when(selected, |element| element.bg(cx.theme().accent))
+        .when_some(host, |element, host| {
+            element
+                .child(badge(host.to_string(), cx))
+                .child(" ".to_string())
+        })
+        .child(
+            Button::new("id-link")
+                .link()
+                .label(format!(
+                    "{}!{}",
+                    project_path(&merge_request.web_url),
+                    merge_request.iid
+                ))
+                .on_click(move |_, _, _| {
+                    open::that(web_url.clone()).unwrap_or_log();
+                }),
+        )
+        .child(" ".to_string())
+        .child(merge_request.title.clone())
+        .child(" ".to_string())
+        .child(
+            Label::new(format!(
+                "no activity for {}",
+                age(&merge_request.updated_at)
+            ))
+            .text_xs()
+            .text_color(cx.theme().muted_foreground),
+        )
+}
+
+/// Stale merge requests of given projects and my merge requests waiting for review too long
+async fn get_merge_requests(
+    gitlab: GitlabClient,
+    project_ids: &[u64],
+    stale_after_days: u32,
+    notify_waiting_review_after_days: Option<u32>,
+) -> Result<(Vec<MergeRequest>, Vec<MergeRequest>), GitlabError> {
+    let updated_before = (Utc::now() - TimeDelta::days(stale_after_days as i64)).to_rfc3339();
+    let mut merge_requests = join_all(
+        project_ids
+            .iter()
+            .map(|project_id| get_inactive_merge_requests(&gitlab, *project_id, &updated_before)),
+    )
+    .await
+    .into_iter()
+    .collect::<Result<Vec<Vec<MergeRequest>>, GitlabError>>()?
+    .into_iter()
+    .flatten()
+    .collect::<Vec<MergeRequest>>();
+    merge_requests.sort_by(|a, b| a.updated_at.cmp(&b.updated_at));
+
+    let waiting = match notify_waiting_review_after_days {
+        Some(days) => {
+            let mine: Vec<MergeRequest> = api::paged(
+                MyReadyMergeRequests,
+                api::Pagination::Limit(MY_MERGE_REQUESTS_COUNT),
+            )
+            .query_async(&gitlab)
+            .await?;
+
+            mine.into_iter()
+                // Waiting since last update (marked as ready, review requested, pushed), a
+                // merge request may stay draft long after its creation
+                .filter(|merge_request| {
+                    merge_request.detailed_merge_status == MERGE_STATUS_NOT_APPROVED
+                        && days_since(&merge_request.updated_at)
+                            .is_some_and(|waiting| waiting >= days as i64)
+                })
+                .collect()
+        }
+        None => vec![],
+    };
+
+    Ok((merge_requests, waiting))
+}
+
+async fn get_inactive_merge_requests(
+    gitlab: &GitlabClient,
+    project_id: u64,
+    updated_before: &str,
+) -> Result<Vec<MergeRequest>, GitlabError> {
+    Ok(api::paged(
+        InactiveMergeRequests::new(project_id, updated_before.to_string()),
+        api::Pagination::Limit(STALE_MERGE_REQUESTS_COUNT),
+    )
+    .query_async(gitlab)
+    .await?)
+}
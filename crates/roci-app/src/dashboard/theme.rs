@@ -0,0 +1,157 @@
+use std::rc::Rc;
+
+use gpui::*;
+use gpui_component::{
+    notification::NotificationType,
+    select::{SelectEvent, SelectState},
+    IndexPath, Theme, WindowExt,
+};
+
+use crate::{
+    config::{
+        theme::{load_theme, ThemeMode, DEFAULT_DARK_THEME, DEFAULT_LIGHT_THEME},
+        Config,
+    },
+    dashboard::Dashboard,
+    state::AppState,
+};
+
+/// Select of theme names, one for dark mode and one for light mode
+pub type ThemeSelect = Entity<SelectState<Vec<SharedString>>>;
+
+/// Select of every known theme name, with given one selected
+pub fn theme_select(
+    window: &mut Window,
+    cx: &mut Context<Dashboard>,
+    selected: &str,
+) -> ThemeSelect {
+    let names = AppState::global(cx)
+        .themes()
+        .iter()
+        .map(|theme| theme.name.clone())
+        .collect::<Vec<SharedString>>();
+    let index = names.iter().position(|name| name.as_ref() == selected);
+
+    cx.new(|cx| SelectState::new(names, index.map(|v| IndexPath::new(v)), window, cx))
+}
+
+impl Dashboard {
+    pub fn new_theme_selects(
+        window: &mut Window,
+        cx: &mut Context<Self>,
+        config: &Config,
+    ) -> (ThemeSelect, ThemeSelect) {
+        let dark_theme = theme_select(
+            window,
+            cx,
+            config.dark_theme.as_deref().unwrap_or(DEFAULT_DARK_THEME),
+        );
+        cx.subscribe_in(&dark_theme, window, Self::on_select_dark_theme)
+            .detach();
+
+        let light_theme = theme_select(
+            window,
+            cx,
+            config.light_theme.as_deref().unwrap_or(DEFAULT_LIGHT_THEME),
+        );
+        cx.subscribe_in(&light_theme, window, Self::on_select_light_theme)
+            .detach();
+
+        (dark_theme, light_theme)
+    }
+
+    /// Apply theme matching config and current window appearance
+    pub fn apply_theme(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let state = AppState::global(cx);
+        let (theme, warnings) =
+            match load_theme(state.themes(), state.config(), Some(window.appearance())) {
+                Ok(value) => value,
+                Err(error) => {
+                    window.push_notification(
+                        (
+                            NotificationType::Error,
+                            SharedString::new(format!("Failed to apply theme: {:#}", error)),
+                        ),
+                        cx,
+                    );
+                    return;
+                }
+            };
+        Theme::global_mut(cx).apply_config(&Rc::new(theme));
+
+        for warning in warnings {
+            window.push_notification(warning.into_notification(), cx);
+        }
+        window.refresh();
+    }
+
+    fn update_theme_config(
+        &mut self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+        update: impl FnOnce(&mut Config),
+    ) {
+        let state = AppState::global_mut(cx);
+        let mut new_config = state.config().clone();
+        update(&mut new_config);
+
+        if let Err(error) = state.replace_config(new_config) {
+            window.push_notification(
+                (
+                    NotificationType::Error,
+                    SharedString::new(format!("Failed to write config on disk: {:#}", error)),
+                ),
+                cx,
+            );
+            return;
+        }
+
+        self.apply_theme(window, cx);
+        cx.notify();
+    }
+
+    pub fn on_select_theme_mode(
+        &mut self,
+        _: &Entity<SelectState<Vec<ThemeMode>>>,
+        event: &SelectEvent<Vec<ThemeMode>>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let SelectEvent::Confirm(Some(theme_mode)) = event else {
+            return;
+        };
+        let theme_mode = *theme_mode;
+
+        self.update_theme_config(window, cx, |config| config.theme_mode = theme_mode);
+    }
+
+    fn on_select_dark_theme(
+        &mut self,
+        _: &ThemeSelect,
+        event: &SelectEvent<Vec<SharedString>>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let SelectEvent::Confirm(Some(name)) = event else {
+            return;
+        };
+        let name = name.to_string();
+
+        self.update_theme_config(window, cx, |config| config.dark_theme = Some(name));
+    }
+
+    fn on_select_light_theme(
+        &mut self,
+        _: &ThemeSelect,
+        event: &SelectEvent<Vec<SharedString>>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let SelectEvent::Confirm(Some(name)) = event else {
+            return;
+        };
+        let name = name.to_string();
+
+        self.update_theme_config(window, cx, |config| config.light_theme = Some(name));
+    }
+}
@@ -1,3 +1,4 @@
+use derive_more::Constructor;
 use gitlab::api::{Endpoint, QueryParams};
 use serde::Serialize;
 
@@ -19,3 +20,18 @@ impl Endpoint for MyTodos {
         params
     }
 }
+
+#[derive(Debug, Constructor)]
+pub struct MarkTodoDone {
+    id: u64,
+}
+
+impl Endpoint for MarkTodoDone {
+    fn method(&self) -> reqwest::Method {
+        reqwest::Method::POST
+    }
+
+    fn endpoint(&self) -> std::borrow::Cow<'static, str> {
+        format!("todos/{}/mark_as_done", self.id).into()
+    }
+}
@@ -1,17 +1,37 @@
-use gitlab::{api::AsyncQuery, AsyncGitlab};
+use gitlab::api::{self, AsyncQuery};
 use gpui::{prelude::FluentBuilder, *};
 use gpui_component::{
     button::{Button, ButtonVariants},
     h_flex,
     label::Label,
-    v_flex, Icon, IconName, StyledExt,
+    notification::NotificationType,
+    spinner::Spinner,
+    v_flex, ActiveTheme, Icon, IconName, StyledExt, WindowExt,
+};
+use roci_app_components::{
+    badge::badge, error::WithButtonModalError, with_button_error, LoadState,
 };
-use roci_app_components::{error::WithButtonModalError, with_button_error, LoadState};
 use tracing_unwrap::ResultExt;
 
 use crate::{
-    dashboard::{error::GitlabError, todo::endpoint::MyTodos},
-    state::{gitlab::todo::Todo, AppState},
+    api::snapshot::TodoSnapshot,
+    config::layout::ALL_INSTANCES,
+    dashboard::{
+        error::GitlabError,
+        refresh::wait_refresh,
+        todo::endpoint::{MarkTodoDone, MyTodos},
+    },
+    popout::{self, PopOutKey},
+    state::{
+        gitlab::{
+            todo::{Todo, TODO_ACTION_REVIEW_REQUESTED},
+            GitlabClient,
+        },
+        navigation::{NavItem, NavKey, NavKind},
+        refresh::RefreshKey,
+        AppState,
+    },
+    tray::TrayItem,
     utils::Truncate,
 };
 
@@ -19,7 +39,7 @@ mod endpoint;
 
 pub struct Todos {
     inner: Entity<LoadState<TodosInner, WithButtonModalError<GitlabError>>>,
-    host: String,
+    config: crate::config::gitlab_::Gitlab,
 }
 
 impl Todos {
@@ -29,21 +49,34 @@ impl Todos {
         config: crate::config::gitlab_::Gitlab,
     ) -> Self {
         let host = config.host.clone();
-        let host_ = config.host.clone();
         let gitlabs = AppState::global(cx).gitlabs();
 
+        let refresh_key = RefreshKey::Todos(config.host.clone());
         let config_ = config.clone();
         cx.spawn_in(window, async move |this, cx| {
             match gitlabs.get(&config_).await {
                 Ok(gitlab) => match get_todos(gitlab).await {
                     Ok(todos_) => {
                         let _ = this.update_in(cx, |this, _window, cx| {
+                            AppState::global_mut(cx).refreshes_mut().loaded(
+                                &refresh_key,
+                                todos_
+                                    .iter()
+                                    .map(|todo| todo.id.to_string())
+                                    .collect::<Vec<String>>()
+                                    .join(","),
+                                false,
+                            );
                             let todos = cx.new(|_cx| TodosInner(todos_));
                             this.inner = cx.new(|_cx| LoadState::Ready(todos));
+                            cx.notify();
                         });
                     }
                     Err(error) => {
                         let _ = this.update_in(cx, |this, _window, cx| {
+                            AppState::global_mut(cx)
+                                .refreshes_mut()
+                                .failed(&refresh_key);
                             this.inner = cx.new(|cx| {
                                 with_button_error!(
                                     cx,
@@ -52,11 +85,15 @@ impl Todos {
                                     error.into()
                                 )
                             });
+                            cx.notify();
                         });
                     }
                 },
                 Err(error) => {
                     let _ = this.update_in(cx, |this, _window, cx| {
+                        AppState::global_mut(cx)
+                            .refreshes_mut()
+                            .failed(&refresh_key);
                         this.inner = cx.new(|cx| {
                             with_button_error!(
                                 cx,
@@ -65,6 +102,7 @@ impl Todos {
                                 error.into()
                             )
                         });
+                        cx.notify();
                     });
                 }
             };
@@ -72,9 +110,9 @@ impl Todos {
         .detach();
 
         let config_ = config.clone();
-        let interval = AppState::global(cx).config().refresh_every.duration();
+        let refresh_key = RefreshKey::Todos(config.host.clone());
         cx.spawn_in(window, async move |this, cx| {
-            Timer::after(interval).await;
+            wait_refresh(cx, &refresh_key).await;
             let _ = this.update_in(cx, |this, window, cx| {
                 *this = Self::new(window, cx, config_.clone());
                 cx.notify();
@@ -84,20 +122,126 @@ impl Todos {
 
         Self {
             inner: cx.new(|_cx| LoadState::Loading),
-            host: host_,
+            config,
         }
     }
+
+    pub fn host(&self) -> &str {
+        &self.config.host
+    }
+
+    pub fn nav_items(&self, cx: &App) -> Vec<NavItem> {
+        let LoadState::Ready(todos) = self.inner.read(cx) else {
+            return vec![];
+        };
+
+        todos
+            .read(cx)
+            .0
+            .iter()
+            .map(|todo| NavItem {
+                key: NavKey::new(NavKind::Todo, todo.target_url.clone()),
+                host: self.config.host.clone(),
+                id: todo.id,
+            })
+            .collect()
+    }
+
+    /// Action (eg. `assigned`, `review_requested`) of each pending todo
+    pub fn actions(&self, cx: &App) -> Vec<String> {
+        let LoadState::Ready(todos) = self.inner.read(cx) else {
+            return vec![];
+        };
+
+        todos
+            .read(cx)
+            .0
+            .iter()
+            .map(|todo| todo.action_name.clone())
+            .collect()
+    }
+
+    pub fn snapshots(&self, cx: &App) -> Vec<TodoSnapshot> {
+        let LoadState::Ready(todos) = self.inner.read(cx) else {
+            return vec![];
+        };
+
+        todos.read(cx).0.iter().map(TodoSnapshot::from).collect()
+    }
+
+    /// Pending todos then review requests, as tray items
+    pub fn tray_items(&self, cx: &App) -> (Vec<TrayItem>, Vec<TrayItem>) {
+        let LoadState::Ready(todos) = self.inner.read(cx) else {
+            return (vec![], vec![]);
+        };
+
+        let (review_requests, todos): (Vec<&Todo>, Vec<&Todo>) = todos
+            .read(cx)
+            .0
+            .iter()
+            .partition(|todo| todo.action_name == TODO_ACTION_REVIEW_REQUESTED);
+        let item = |todo: &&Todo| TrayItem::new(todo.body.truncated(80), todo.target_url.clone());
+
+        (
+            todos.iter().map(item).collect(),
+            review_requests.iter().map(item).collect(),
+        )
+    }
+
+    pub fn mark_done(&mut self, window: &mut Window, cx: &mut Context<Self>, todo_id: u64) {
+        let gitlabs = AppState::global(cx).gitlabs();
+        let config = self.config.clone();
+
+        cx.spawn_in(window, async move |this, cx| {
+            let result = mark_todo_done(gitlabs, config, todo_id).await;
+            let _ = this.update_in(cx, |this, window, cx| match result {
+                Ok(_) => {
+                    if let LoadState::Ready(todos) = this.inner.read(cx) {
+                        todos.clone().update(cx, |todos, cx| {
+                            todos.0.retain(|todo| todo.id != todo_id);
+                            cx.notify();
+                        });
+                    }
+                    window.push_notification("Todo marked as done", cx);
+                }
+                Err(error) => window.push_notification(
+                    (
+                        NotificationType::Error,
+                        SharedString::new(format!("Can't mark todo as done: {}", error)),
+                    ),
+                    cx,
+                ),
+            });
+        })
+        .detach();
+    }
 }
 
 impl Render for Todos {
-    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
-        let host = self.host.clone();
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let host = self.config.host.clone();
 
         div()
             .child(
                 h_flex()
                     .child(Icon::new(IconName::ArrowRight))
-                    .child(Label::new(format!("{} todos", host)).text_xl()),
+                    .child(Label::new(format!("{} todos", host)).text_xl())
+                    .child(" ".to_string())
+                    .child(
+                        Button::new("pop-out-todos")
+                            .icon(IconName::PanelRightOpen)
+                            .link()
+                            .tooltip("Open in a new window")
+                            .on_click(cx.listener(|this, _, _window, cx| {
+                                let view = cx.entity().into();
+                                popout::open(
+                                    cx,
+                                    PopOutKey::Todos(this.config.host.clone()),
+                                    SharedString::new(format!("{} todos", this.config.host)),
+                                    view,
+                                )
+                            })),
+                    ),
             )
             .child(self.inner.clone())
     }
@@ -106,22 +250,11 @@ impl Render for Todos {
 pub struct TodosInner(Vec<Todo>);
 
 impl Render for TodosInner {
-    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         v_flex()
             .paddings(Edges::all(px(5.)))
             .when(!self.0.is_empty(), |element| {
-                element.children(self.0.iter().map(|todo| {
-                    let target_url = todo.target_url.clone();
-
-                    h_flex().id(ElementId::Integer(todo.id)).child(
-                        Button::new("id-link")
-                            .link()
-                            .label(todo.body.to_string().truncated(254))
-                            .on_click(move |_, _, _| {
-                                open::that(target_url.clone()).unwrap_or_log();
-                            }),
-                    )
-                }))
+                element.children(self.0.iter().map(|todo| render_todo(todo, None, cx)))
             })
             .when(self.0.is_empty(), |element| {
                 element.child("n/a".to_string())
@@ -129,6 +262,143 @@ impl Render for TodosInner {
     }
 }
 
-async fn get_todos(gitlab: AsyncGitlab) -> Result<Vec<Todo>, GitlabError> {
+/// Todos of every instance, most recent first
+pub struct UnifiedTodos {
+    sources: Vec<Entity<Todos>>,
+}
+
+impl UnifiedTodos {
+    pub fn new(cx: &mut Context<Self>, sources: Vec<Entity<Todos>>) -> Self {
+        for source in &sources {
+            cx.observe(source, |_this, _source, cx| cx.notify())
+                .detach();
+        }
+
+        Self { sources }
+    }
+
+    fn todos(&self, cx: &App) -> Vec<(String, Todo)> {
+        let mut todos = vec![];
+        for source in &self.sources {
+            let source = source.read(cx);
+            if let LoadState::Ready(inner) = source.inner.read(cx) {
+                todos.extend(
+                    inner
+                        .read(cx)
+                        .0
+                        .iter()
+                        .map(|todo| (source.config.host.clone(), todo.clone())),
+                );
+            }
+        }
+        todos.sort_by(|(_, a), (_, b)| b.created_at.cmp(&a.created_at));
+
+        todos
+    }
+
+    pub fn nav_items(&self, cx: &App) -> Vec<NavItem> {
+        self.todos(cx)
+            .into_iter()
+            .map(|(host, todo)| NavItem {
+                key: NavKey::new(NavKind::Todo, todo.target_url),
+                host,
+                id: todo.id,
+            })
+            .collect()
+    }
+}
+
+impl Render for UnifiedTodos {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let todos = self.todos(cx);
+        let mut loading = false;
+        let mut errors = vec![];
+        for source in &self.sources {
+            match source.read(cx).inner.read(cx) {
+                LoadState::Loading => loading = true,
+                LoadState::Ready(_) => {}
+                LoadState::Error(error) => errors.push(error.clone()),
+            }
+        }
+
+        div()
+            .child(
+                h_flex()
+                    .child(Icon::new(IconName::ArrowRight))
+                    .child(Label::new(format!("{} todos", ALL_INSTANCES)).text_xl())
+                    .child(" ".to_string())
+                    .child(
+                        Button::new("pop-out-todos")
+                            .icon(IconName::PanelRightOpen)
+                            .link()
+                            .tooltip("Open in a new window")
+                            .on_click(cx.listener(|_this, _, _window, cx| {
+                                let view = cx.entity().into();
+                                popout::open(
+                                    cx,
+                                    PopOutKey::Todos(ALL_INSTANCES.to_string()),
+                                    SharedString::new(format!("{} todos", ALL_INSTANCES)),
+                                    view,
+                                )
+                            })),
+                    ),
+            )
+            .when(loading, |element| element.child(Spinner::new()))
+            .children(errors)
+            .child(
+                v_flex()
+                    .paddings(Edges::all(px(5.)))
+                    .when(todos.is_empty() && !loading, |element| {
+                        element.child("n/a".to_string())
+                    })
+                    .children(
+                        todos
+                            .iter()
+                            .map(|(host, todo)| render_todo(todo, Some(host), cx)),
+                    ),
+            )
+    }
+}
+
+fn render_todo(todo: &Todo, host: Option<&str>, cx: &App) -> impl IntoElement {
+    let target_url = todo.target_url.clone();
+    let selected =
+        AppState::global(cx).is_selected(&NavKey::new(NavKind::Todo, todo.target_url.clone()));
+    let id = match host {
+        Some(host) => ElementId::NamedInteger(SharedString::new(host), todo.id),
+        None => ElementId::Integer(todo.id),
+    };
+
+    h_flex()
+        .id(id)
+        .gap_1()
+        .when(selected, |element| element.bg(cx.theme().accent))
+        .when_some(host, |element, host| {
+            element.child(badge(host.to_string(), cx))
+        })
+        .child(
+            Button::new("id-link")
+                .link()
+                .label(todo.body.to_string().truncated(254))
+                .on_click(move |_, _, _| {
+                    open::that(target_url.clone()).unwrap_or_log();
+                }),
+        )
+}
+
+async fn get_todos(gitlab: GitlabClient) -> Result<Vec<Todo>, GitlabError> {
     Ok(MyTodos.query_async(&gitlab).await?)
 }
+
+async fn mark_todo_done(
+    gitlabs: crate::state::gitlab::Gitlabs,
+    config: crate::config::gitlab_::Gitlab,
+    todo_id: u64,
+) -> Result<(), GitlabError> {
+    let gitlab = gitlabs.get(&config).await?;
+    api::ignore(MarkTodoDone::new(todo_id))
+        .query_async(&gitlab)
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,60 @@
+use gpui::*;
+use gpui_component::{notification::NotificationType, WindowExt};
+
+use crate::{
+    config::tray::TrayConfig,
+    dashboard::Dashboard,
+    popout,
+    state::AppState,
+    tray::{self, TraySummary},
+};
+
+impl Dashboard {
+    pub fn tray_summary(&self, cx: &App) -> TraySummary {
+        let mut summary = TraySummary::default();
+
+        for projects in &self.projects.read(cx).0 {
+            summary
+                .failing_pipelines
+                .extend(projects.read(cx).failing_pipelines(cx));
+        }
+        for todos in &self.todos.read(cx).0 {
+            let (todos, review_requests) = todos.read(cx).tray_items(cx);
+            summary.todos.extend(todos);
+            summary.review_requests.extend(review_requests);
+        }
+
+        summary
+    }
+
+    /// Minimize instead of closing if app must keep running in tray
+    pub fn close_window(window: &mut Window, cx: &mut App) {
+        if tray::keep_running(cx) {
+            tray::hide_window(window, cx);
+        } else {
+            popout::close_all(cx);
+            window.remove_window();
+        }
+    }
+
+    pub fn update_tray_config(
+        &mut self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+        update: impl FnOnce(&mut TrayConfig),
+    ) {
+        let state = AppState::global_mut(cx);
+        let mut new_config = state.config().clone();
+        update(&mut new_config.tray);
+
+        if let Err(error) = state.replace_config(new_config) {
+            window.push_notification(
+                (
+                    NotificationType::Error,
+                    SharedString::new(format!("Failed to write config on disk: {:#}", error)),
+                ),
+                cx,
+            );
+        }
+    }
+}
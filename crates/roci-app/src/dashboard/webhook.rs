@@ -0,0 +1,176 @@
+use gpui::*;
+use gpui_component::{
+    button::{Button, ButtonVariants},
+    form::{field, v_form},
+    input::Input,
+    notification::NotificationType,
+    switch::Switch,
+    WindowExt,
+};
+
+use crate::{
+    config::{set_password, webhook::WEBHOOK_SECRET_KEY},
+    dashboard::Dashboard,
+    state::{refresh::RefreshKey, AppState},
+    webhook::{WebhookEvent, WebhookKind},
+};
+
+impl Dashboard {
+    /// Update what the event changes in place and refresh affected sections now
+    pub fn apply_webhook(&mut self, event: WebhookEvent, cx: &mut Context<Self>) {
+        let state = AppState::global(cx);
+        if !state
+            .config()
+            .gitlabs
+            .iter()
+            .any(|gitlab| gitlab.host == event.host)
+        {
+            tracing::debug!("Webhook event of unknown instance {}", event.host);
+            return;
+        }
+
+        let host = event.host.clone();
+        let project_keys = [
+            RefreshKey::Project(host.clone(), event.project_id),
+            RefreshKey::ProjectsTable(host.clone()),
+            RefreshKey::Projects(host.clone()),
+        ];
+        let keys = match &event.kind {
+            WebhookKind::Pipeline { ref_, pipeline } => {
+                AppState::record_pipelines(cx, host.clone(), vec![(**pipeline).clone()]);
+                for projects in self.projects.read(cx).0.clone() {
+                    projects.update(cx, |projects, cx| {
+                        projects.apply_pipeline(&host, event.project_id, ref_, pipeline, cx)
+                    });
+                }
+                project_keys.to_vec()
+            }
+            WebhookKind::MergeRequest | WebhookKind::Note => {
+                let mut keys = project_keys.to_vec();
+                keys.push(RefreshKey::Todos(host.clone()));
+                keys.push(RefreshKey::StaleMergeRequests(host.clone()));
+                keys
+            }
+            WebhookKind::Issue => vec![RefreshKey::Issues(host.clone())],
+        };
+
+        let refreshes = AppState::global_mut(cx).refreshes_mut();
+        for key in keys {
+            refreshes.trigger(key);
+        }
+        refreshes.trigger(RefreshKey::Activity(host));
+    }
+
+    pub fn show_webhook_dialog(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let config = AppState::global(cx).config().webhook.clone();
+        self.new_webhook_address.update(cx, |address, cx| {
+            address.set_value(SharedString::new(config.address), window, cx)
+        });
+        self.new_webhook_secret
+            .update(cx, |secret, cx| secret.set_value("", window, cx));
+        self.new_webhook_enabled
+            .update(cx, |enabled, _cx| enabled.set(config.enabled));
+
+        let address = self.new_webhook_address.clone();
+        let secret = self.new_webhook_secret.clone();
+        let enabled = self.new_webhook_enabled.clone();
+
+        window.open_dialog(cx, move |dialog, _window, cx| {
+            let enabled_ = enabled.read(cx);
+
+            dialog
+                .title("Webhook receiver (on next start)")
+                .child(
+                    v_form()
+                        .child(
+                            field().label("Listen for Gitlab webhooks").child(
+                                Switch::new("webhook-enabled")
+                                    .checked(enabled_.get())
+                                    .on_click({
+                                        let enabled = enabled.clone();
+                                        move |_, _window, cx| {
+                                            enabled.update(cx, |this, _cx| {
+                                                this.toggle();
+                                            });
+                                        }
+                                    }),
+                            ),
+                        )
+                        .child(
+                            field()
+                                .label("Address (eg. `127.0.0.1:8787`)")
+                                .child(Input::new(&address)),
+                        )
+                        .child(
+                            field()
+                                .label("Secret token (empty to keep current one)")
+                                .child(Input::new(&secret)),
+                        ),
+                )
+                .footer({
+                    let address = address.clone();
+                    let secret = secret.clone();
+                    let enabled = enabled.clone();
+
+                    move |_, _, _, _cx| {
+                        vec![
+                            Button::new("confirm").primary().label("Confirm").on_click({
+                                let address = address.clone();
+                                let secret = secret.clone();
+                                let enabled = enabled.clone();
+
+                                move |_, window, cx| {
+                                    let secret = secret.read(cx).value().trim().to_string();
+                                    if !secret.is_empty() {
+                                        if let Err(error) =
+                                            set_password(WEBHOOK_SECRET_KEY, &secret)
+                                        {
+                                            window.push_notification(
+                                                (
+                                                    NotificationType::Error,
+                                                    SharedString::new(format!(
+                                                        "Failed to save webhook secret: {}",
+                                                        error
+                                                    )),
+                                                ),
+                                                cx,
+                                            );
+                                            return;
+                                        }
+                                    }
+
+                                    let enabled = enabled.read(cx).get();
+                                    let address = address.read(cx).value().trim().to_string();
+                                    let state = AppState::global_mut(cx);
+                                    let mut new_config = state.config().clone();
+                                    new_config.webhook.enabled = enabled;
+                                    new_config.webhook.address = address;
+
+                                    if let Err(error) = state.replace_config(new_config) {
+                                        window.push_notification(
+                                            (
+                                                NotificationType::Error,
+                                                SharedString::new(format!(
+                                                    "Failed to write config on disk: {:#}",
+                                                    error
+                                                )),
+                                            ),
+                                            cx,
+                                        );
+                                        return;
+                                    }
+
+                                    window.close_dialog(cx);
+                                }
+                            }),
+                            Button::new("cancel")
+                                .label("Cancel")
+                                .on_click(move |_, window, cx| {
+                                    window.close_dialog(cx);
+                                }),
+                        ]
+                    }
+                })
+        });
+    }
+}
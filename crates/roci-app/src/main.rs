@@ -6,13 +6,19 @@ use gpui_component::*;
 
 use crate::{config::Config, logging::configure_logging};
 
+mod actions;
+mod api;
 mod assets;
 mod config;
 mod dashboard;
 mod logging;
+mod popout;
+mod prometheus;
 mod runtime;
 mod state;
+mod tray;
 mod utils;
+mod webhook;
 
 fn main() -> Result<(), anyhow::Error> {
     configure_logging();
@@ -20,12 +26,50 @@ fn main() -> Result<(), anyhow::Error> {
 
     let app = Application::new().with_assets(assets::Assets);
     let (config, info) = Config::from_env()?;
-    let (theme, theme_error) = config::theme::load_theme(config.theme_mode)?;
+    let (themes, mut theme_warnings) = config::theme::load_themes()?;
+    let (theme, warnings) = config::theme::load_theme(&themes, &config, None)?;
+    theme_warnings.extend(warnings);
+    let (tray_sender, tray_receiver) = futures::channel::mpsc::unbounded();
+    let tray_enabled = config.tray.enabled;
+    let (webhook_sender, webhook_receiver) = futures::channel::mpsc::unbounded();
+    let webhook_config = config.webhook.clone();
+    let prometheus_config = config.prometheus.clone();
+    let api_config = config.api.clone();
 
     app.run(move |cx| {
         gpui_component::init(cx);
-        state::AppState::init(cx, config);
+        actions::bind_keys(cx);
+        state::AppState::init(cx, config, themes);
         Theme::global_mut(cx).apply_config(&Rc::new(theme));
+        if tray_enabled {
+            match tray::Tray::spawn(tray_sender) {
+                Ok(tray) => cx.set_global(tray),
+                // Desktop may not support tray icons, nothing to bother user with
+                Err(error) => tracing::warn!("Tray icon not available: {}", error),
+            }
+        }
+        let mut webhook_error = None;
+        if webhook_config.enabled {
+            match webhook::Webhook::spawn(&webhook_config, webhook_sender) {
+                Ok(webhook) => cx.set_global(webhook),
+                Err(error) => webhook_error = Some(error.to_string()),
+            }
+        }
+        let mut prometheus_error = None;
+        if prometheus_config.enabled {
+            let gitlabs = state::AppState::global(cx).gitlabs();
+            match prometheus::Prometheus::spawn(&prometheus_config, gitlabs) {
+                Ok(prometheus) => cx.set_global(prometheus),
+                Err(error) => prometheus_error = Some(error.to_string()),
+            }
+        }
+        let mut api_error = None;
+        if api_config.enabled {
+            match api::Api::spawn(&api_config) {
+                Ok(api) => cx.set_global(api),
+                Err(error) => api_error = Some(error.to_string()),
+            }
+        }
 
         let mut window_size = size(px(1600.0), px(1200.0));
         if let Some(display) = cx.primary_display() {
@@ -33,10 +77,17 @@ fn main() -> Result<(), anyhow::Error> {
             window_size.width = window_size.width.min(display_size.width * 0.85);
             window_size.height = window_size.height.min(display_size.height * 0.85);
         }
-        let window_bounds = Bounds::centered(None, window_size, cx);
+        let window_bounds = popout::restored_bounds(popout::MAIN_WINDOW, cx)
+            .unwrap_or_else(|| WindowBounds::Windowed(Bounds::centered(None, window_size, cx)));
+        // Bounds of closed windows are already written
+        cx.on_app_quit(|cx| {
+            popout::persist(cx);
+            async {}
+        })
+        .detach();
 
         let window_options = WindowOptions {
-            window_bounds: Some(WindowBounds::Windowed(window_bounds)),
+            window_bounds: Some(window_bounds),
             titlebar: Some(TitlebarOptions {
                 title: None,
                 appears_transparent: true,
@@ -60,13 +111,45 @@ fn main() -> Result<(), anyhow::Error> {
                 if let Some(info) = info {
                     notifications.push(info.into_notification());
                 }
-                if let Some(theme_error) = theme_error {
-                    notifications.push(theme_error.into_notification());
+                notifications.extend(
+                    theme_warnings
+                        .iter()
+                        .map(|warning| warning.into_notification()),
+                );
+                if let Some(error) = webhook_error {
+                    notifications.push((
+                        notification::NotificationType::Error,
+                        SharedString::new(format!("Webhook receiver not started: {}", error)),
+                    ));
+                }
+                if let Some(error) = prometheus_error {
+                    notifications.push((
+                        notification::NotificationType::Error,
+                        SharedString::new(format!("Metrics endpoint not started: {}", error)),
+                    ));
+                }
+                if let Some(error) = api_error {
+                    notifications.push((
+                        notification::NotificationType::Error,
+                        SharedString::new(format!("Local API not started: {}", error)),
+                    ));
                 }
 
                 let view = cx.new(|cx| {
                     dashboard::Dashboard::new(window, cx).with_notifications(notifications)
                 });
+                window.on_window_should_close(cx, |window, cx| {
+                    if tray::keep_running(cx) {
+                        tray::hide_window(window, cx);
+                        return false;
+                    }
+                    popout::close_all(cx);
+                    true
+                });
+                tray::run(window, cx, view.clone(), tray_receiver);
+                webhook::run(window, cx, view.clone(), webhook_receiver);
+                prometheus::run(window, cx, view.clone());
+                api::run(window, cx, view.clone());
                 cx.new(|cx| Root::new(view, window, cx))
             })?;
 
@@ -0,0 +1,323 @@
+use std::collections::HashMap;
+
+use gpui::*;
+use gpui_component::{h_flex, label::Label, switch::Switch, v_flex, Root, StyledExt, TitleBar};
+
+use crate::{dashboard::CONTAINER_PADDING, state::AppState};
+
+/// State key of the dashboard window
+pub const MAIN_WINDOW: &str = "dashboard";
+
+/// What a popped out window shows
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PopOutKey {
+    /// Host and project id
+    Project(String, u64),
+    /// Host, or every instance
+    Todos(String),
+    /// Pipeline web URL
+    PipelineLog(String),
+}
+
+impl PopOutKey {
+    /// Identifies the opened window, a key is opened at most once
+    fn id(&self) -> String {
+        match self {
+            PopOutKey::Project(host, project_id) => format!("project:{}:{}", host, project_id),
+            PopOutKey::Todos(host) => format!("todos:{}", host),
+            PopOutKey::PipelineLog(web_url) => format!("pipeline-log:{}", web_url),
+        }
+    }
+
+    /// Pipeline logs share their position, size and always on top setting
+    fn state_key(&self) -> String {
+        match self {
+            PopOutKey::PipelineLog(_) => "pipeline-log".to_string(),
+            _ => self.id(),
+        }
+    }
+
+    fn default_size(&self) -> Size<Pixels> {
+        match self {
+            PopOutKey::Project(_, _) => size(px(1000.), px(300.)),
+            PopOutKey::Todos(_) => size(px(600.), px(700.)),
+            PopOutKey::PipelineLog(_) => size(px(1000.), px(700.)),
+        }
+    }
+}
+
+/// Opened pop out window
+struct Opened {
+    key: PopOutKey,
+    handle: AnyWindowHandle,
+    view: WeakEntity<PopOut>,
+}
+
+/// Opened pop out windows
+#[derive(Default)]
+struct PopOuts(HashMap<String, Opened>);
+
+impl Global for PopOuts {}
+
+/// Window showing a single dashboard part, `content` keeps being updated by its owner
+pub struct PopOut {
+    key: PopOutKey,
+    title: SharedString,
+    content: AnyView,
+    always_on_top: bool,
+    _bounds: Subscription,
+}
+
+impl PopOut {
+    fn new(
+        window: &mut Window,
+        cx: &mut Context<Self>,
+        key: PopOutKey,
+        title: SharedString,
+        content: AnyView,
+    ) -> Self {
+        let state_key = key.state_key();
+        let always_on_top = AppState::global(cx).windows().get(&state_key).always_on_top;
+        let _bounds = cx.observe_window_bounds(window, move |_this, window, cx| {
+            save_bounds(&state_key, window, cx);
+        });
+        cx.on_release({
+            let id = key.id();
+            let handle = window.window_handle();
+
+            move |_this, cx| {
+                let pop_outs = &mut cx.default_global::<PopOuts>().0;
+                // Key may already be opened again (eg. always on top toggled)
+                if pop_outs.get(&id).map(|opened| opened.handle) == Some(handle) {
+                    pop_outs.remove(&id);
+                }
+                persist(cx);
+            }
+        })
+        .detach();
+
+        Self {
+            key,
+            title,
+            content,
+            always_on_top,
+            _bounds,
+        }
+    }
+
+    /// Window kind can't change, window is opened again at the same place
+    fn toggle_always_on_top(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        AppState::global_mut(cx)
+            .windows_mut()
+            .set_always_on_top(&self.key.state_key(), !self.always_on_top);
+        save_bounds(&self.key.state_key(), window, cx);
+
+        cx.default_global::<PopOuts>().0.remove(&self.key.id());
+        window.remove_window();
+        open(
+            cx,
+            self.key.clone(),
+            self.title.clone(),
+            self.content.clone(),
+        );
+    }
+}
+
+impl Render for PopOut {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .size_full()
+            .child(
+                TitleBar::new().child(
+                    h_flex()
+                        .w_full()
+                        .pr_2()
+                        .justify_between()
+                        .child(Label::new(self.title.clone()))
+                        .child(
+                            h_flex()
+                                .gap_1()
+                                .child(Label::new("Always on top").text_sm())
+                                .child(
+                                    Switch::new("always-on-top")
+                                        .checked(self.always_on_top)
+                                        .on_click(cx.listener(|this, _, window, cx| {
+                                            this.toggle_always_on_top(window, cx)
+                                        })),
+                                ),
+                        ),
+                ),
+            )
+            .child(
+                v_flex().scrollable(Axis::Vertical).child(
+                    div()
+                        .flex_1()
+                        .overflow_hidden()
+                        .paddings(Edges::all(CONTAINER_PADDING))
+                        .child(self.content.clone()),
+                ),
+            )
+            .children(Root::render_dialog_layer(window, cx))
+            .children(Root::render_sheet_layer(window, cx))
+            .children(Root::render_notification_layer(window, cx))
+    }
+}
+
+/// Open `content` in its own window, or bring its window to front if already opened
+pub fn open(cx: &mut App, key: PopOutKey, title: SharedString, content: AnyView) {
+    let id = key.id();
+    if let Some(handle) = cx
+        .try_global::<PopOuts>()
+        .and_then(|pop_outs| pop_outs.0.get(&id))
+        .map(|opened| opened.handle)
+    {
+        // Fails if asked from the pop out itself, already in front
+        let _ = handle.update(cx, |_root, window, _cx| window.activate_window());
+        return;
+    }
+
+    let state = AppState::global(cx).windows().get(&key.state_key());
+    let window_bounds = restored_bounds(&key.state_key(), cx)
+        .unwrap_or_else(|| WindowBounds::centered(key.default_size(), cx));
+    let window_options = WindowOptions {
+        window_bounds: Some(window_bounds),
+        titlebar: Some(TitlebarOptions {
+            title: Some(title.clone()),
+            appears_transparent: true,
+            traffic_light_position: Some(point(px(9.0), px(9.0))),
+        }),
+        window_min_size: Some(gpui::Size {
+            width: px(320.),
+            height: px(200.),
+        }),
+        kind: if state.always_on_top {
+            WindowKind::PopUp
+        } else {
+            WindowKind::Normal
+        },
+        #[cfg(target_os = "linux")]
+        window_background: gpui::WindowBackgroundAppearance::Transparent,
+        #[cfg(target_os = "linux")]
+        window_decorations: Some(gpui::WindowDecorations::Client),
+        ..Default::default()
+    };
+
+    let mut view = None;
+    let result = cx.open_window(window_options, |window, cx| {
+        let view_ = cx.new(|cx| PopOut::new(window, cx, key.clone(), title, content));
+        view = Some(view_.downgrade());
+        cx.new(|cx| Root::new(view_, window, cx))
+    });
+    match result {
+        Ok(handle) => {
+            // Set by the root view builder, run when window is opened
+            if let Some(view) = view {
+                cx.default_global::<PopOuts>().0.insert(
+                    id,
+                    Opened {
+                        key,
+                        handle: handle.into(),
+                        view,
+                    },
+                );
+            }
+        }
+        Err(error) => tracing::warn!("Unable to open window: {}", error),
+    }
+}
+
+/// Keys of opened pop outs
+pub fn opened(cx: &App) -> Vec<PopOutKey> {
+    cx.try_global::<PopOuts>()
+        .map(|pop_outs| {
+            pop_outs
+                .0
+                .values()
+                .map(|opened| opened.key.clone())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Show `content` in `key` pop out instead of its former content (eg. rebuilt by the dashboard)
+pub fn rebind(cx: &mut App, key: &PopOutKey, content: AnyView) {
+    let Some(view) = cx
+        .try_global::<PopOuts>()
+        .and_then(|pop_outs| pop_outs.0.get(&key.id()))
+        .map(|opened| opened.view.clone())
+    else {
+        return;
+    };
+
+    let _ = view.update(cx, |pop_out, cx| {
+        pop_out.content = content;
+        cx.notify();
+    });
+}
+
+/// Close `key` pop out, if opened
+pub fn close(cx: &mut App, key: &PopOutKey) {
+    let Some(handle) = cx
+        .try_global::<PopOuts>()
+        .and_then(|pop_outs| pop_outs.0.get(&key.id()))
+        .map(|opened| opened.handle)
+    else {
+        return;
+    };
+
+    let _ = handle.update(cx, |_root, window, _cx| window.remove_window());
+}
+
+/// Close every pop out along with the dashboard window, window states are written on disk
+pub fn close_all(cx: &mut App) {
+    let handles = cx
+        .try_global::<PopOuts>()
+        .map(|pop_outs| {
+            pop_outs
+                .0
+                .values()
+                .map(|opened| opened.handle)
+                .collect::<Vec<AnyWindowHandle>>()
+        })
+        .unwrap_or_default();
+
+    for handle in handles {
+        let _ = handle.update(cx, |_root, window, _cx| window.remove_window());
+    }
+    persist(cx);
+}
+
+/// Saved bounds of `key` window, if still on a connected display
+pub fn restored_bounds(key: &str, cx: &App) -> Option<WindowBounds> {
+    let state = AppState::global(cx).windows().get(key);
+    let bounds = state.bounds?;
+    if !cx
+        .displays()
+        .iter()
+        .any(|display| display.bounds().intersects(&bounds))
+    {
+        return None;
+    }
+
+    Some(if state.maximized {
+        WindowBounds::Maximized(bounds)
+    } else {
+        WindowBounds::Windowed(bounds)
+    })
+}
+
+/// Keep current bounds of `window`, written on disk when a window is closed or on quit
+pub fn save_bounds(key: &str, window: &Window, cx: &mut App) {
+    let bounds = window.window_bounds();
+    AppState::global_mut(cx).windows_mut().set_bounds(
+        key,
+        bounds.get_bounds(),
+        matches!(bounds, WindowBounds::Maximized(_)),
+    );
+}
+
+pub fn persist(cx: &App) {
+    if let Err(error) = AppState::global(cx).windows().persist() {
+        tracing::warn!("Unable to write window states: {}", error);
+    }
+}
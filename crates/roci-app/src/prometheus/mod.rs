@@ -0,0 +1,269 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    sync::{Arc, Mutex},
+    thread,
+    time::Duration,
+};
+
+use gpui::*;
+use strum::IntoEnumIterator;
+use thiserror::Error;
+use tiny_http::{Header, Method, Request, Response, Server};
+use tracing_unwrap::ResultExt;
+
+use crate::{
+    config::prometheus::PrometheusConfig,
+    dashboard::Dashboard,
+    state::gitlab::{
+        client::{FetchStats, LATENCY_BUCKETS},
+        project::pipeline::PipelineStatus,
+        Gitlabs,
+    },
+    utils::snake_case,
+};
+
+/// Endpoint reads dashboard state, it doesn't query Gitlab itself
+pub const PROMETHEUS_UPDATE_EVERY: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PipelineMetric {
+    pub instance: String,
+    /// Full path of the project
+    pub project: String,
+    pub ref_: String,
+    pub status: PipelineStatus,
+}
+
+/// Dashboard state exposed as metrics
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct DashboardMetrics {
+    /// Last default branch pipeline of each project
+    pub pipelines: Vec<PipelineMetric>,
+    /// Displayed opened merge requests count per instance and detailed merge status
+    pub merge_requests: BTreeMap<(String, String), usize>,
+    /// Pending todos count per instance and action
+    pub todos: BTreeMap<(String, String), usize>,
+}
+
+impl DashboardMetrics {
+    /// Prometheus text exposition format
+    pub fn render(&self, fetch_stats: &[(String, FetchStats)]) -> String {
+        let mut text = String::new();
+
+        header(
+            &mut text,
+            "roci_pipeline_status",
+            "gauge",
+            "Status of the last default branch pipeline of each project, 1 for current one",
+        );
+        for pipeline in &self.pipelines {
+            for status in PipelineStatus::iter() {
+                let _ = writeln!(
+                    text,
+                    "roci_pipeline_status{{instance=\"{}\",project=\"{}\",ref=\"{}\",status=\"{}\"}} {}",
+                    escape(&pipeline.instance),
+                    escape(&pipeline.project),
+                    escape(&pipeline.ref_),
+                    snake_case(&status.to_string()),
+                    (status == pipeline.status) as u8
+                );
+            }
+        }
+
+        header(
+            &mut text,
+            "roci_merge_requests",
+            "gauge",
+            "Displayed opened merge requests by detailed merge status",
+        );
+        for ((instance, status), count) in &self.merge_requests {
+            let _ = writeln!(
+                text,
+                "roci_merge_requests{{instance=\"{}\",status=\"{}\"}} {}",
+                escape(instance),
+                escape(status),
+                count
+            );
+        }
+
+        header(&mut text, "roci_todos", "gauge", "Pending todos by action");
+        for ((instance, action), count) in &self.todos {
+            let _ = writeln!(
+                text,
+                "roci_todos{{instance=\"{}\",action=\"{}\"}} {}",
+                escape(instance),
+                escape(action),
+                count
+            );
+        }
+
+        header(
+            &mut text,
+            "roci_fetch_requests_total",
+            "counter",
+            "Requests sent to the instance, retries included",
+        );
+        for (instance, stats) in fetch_stats {
+            let _ = writeln!(
+                text,
+                "roci_fetch_requests_total{{instance=\"{}\"}} {}",
+                escape(instance),
+                stats.requests
+            );
+        }
+
+        header(
+            &mut text,
+            "roci_fetch_errors_total",
+            "counter",
+            "Failed requests (connection errors, 4xx and 5xx answers)",
+        );
+        for (instance, stats) in fetch_stats {
+            let _ = writeln!(
+                text,
+                "roci_fetch_errors_total{{instance=\"{}\"}} {}",
+                escape(instance),
+                stats.errors
+            );
+        }
+
+        header(
+            &mut text,
+            "roci_fetch_duration_seconds",
+            "histogram",
+            "Latency of requests sent to the instance",
+        );
+        for (instance, stats) in fetch_stats {
+            let instance = escape(instance);
+            for (count, upper_bound) in stats.latency_buckets.iter().zip(LATENCY_BUCKETS) {
+                let _ = writeln!(
+                    text,
+                    "roci_fetch_duration_seconds_bucket{{instance=\"{}\",le=\"{}\"}} {}",
+                    instance, upper_bound, count
+                );
+            }
+            let _ = writeln!(
+                text,
+                "roci_fetch_duration_seconds_bucket{{instance=\"{}\",le=\"+Inf\"}} {}",
+                instance, stats.requests
+            );
+            let _ = writeln!(
+                text,
+                "roci_fetch_duration_seconds_sum{{instance=\"{}\"}} {}",
+                instance, stats.latency_sum
+            );
+            let _ = writeln!(
+                text,
+                "roci_fetch_duration_seconds_count{{instance=\"{}\"}} {}",
+                instance, stats.requests
+            );
+        }
+
+        text
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum PrometheusError {
+    #[error("Unable to listen on {0}: {1}")]
+    Bind(String, String),
+}
+
+/// Running metrics endpoint, stopped when dropped
+pub struct Prometheus {
+    server: Arc<Server>,
+    metrics: Arc<Mutex<DashboardMetrics>>,
+}
+
+impl Global for Prometheus {}
+
+impl Prometheus {
+    pub fn spawn(config: &PrometheusConfig, gitlabs: Gitlabs) -> Result<Self, PrometheusError> {
+        let server = Server::http(&config.address)
+            .map_err(|error| PrometheusError::Bind(config.address.clone(), error.to_string()))?;
+        let server = Arc::new(server);
+        let metrics = Arc::new(Mutex::new(DashboardMetrics::default()));
+        tracing::info!("Exposing Prometheus metrics on {}", config.address);
+
+        thread::spawn({
+            let server = server.clone();
+            let metrics = metrics.clone();
+
+            move || {
+                for request in server.incoming_requests() {
+                    handle(request, &metrics, &gitlabs);
+                }
+            }
+        });
+
+        Ok(Self { server, metrics })
+    }
+
+    fn update(&self, metrics: DashboardMetrics) {
+        *self.metrics.lock().unwrap_or_log() = metrics;
+    }
+}
+
+impl Drop for Prometheus {
+    fn drop(&mut self) {
+        self.server.unblock();
+    }
+}
+
+/// Feed metrics endpoint with dashboard state
+pub fn run(window: &mut Window, cx: &mut App, dashboard: Entity<Dashboard>) {
+    window
+        .spawn(cx, {
+            let dashboard = dashboard.downgrade();
+
+            async move |cx| loop {
+                let Ok(metrics) =
+                    dashboard.read_with(cx, |dashboard, cx| dashboard.prometheus_metrics(cx))
+                else {
+                    break;
+                };
+                let _ = cx.update(|_window, cx| {
+                    if let Some(prometheus) = cx.try_global::<Prometheus>() {
+                        prometheus.update(metrics);
+                    }
+                });
+
+                Timer::after(PROMETHEUS_UPDATE_EVERY).await;
+            }
+        })
+        .detach();
+}
+
+fn handle(request: Request, metrics: &Mutex<DashboardMetrics>, gitlabs: &Gitlabs) {
+    let response = if *request.method() != Method::Get {
+        Response::from_string("").with_status_code(405)
+    } else if request.url().split('?').next() != Some("/metrics") {
+        Response::from_string("").with_status_code(404)
+    } else {
+        let text = metrics
+            .lock()
+            .unwrap_or_log()
+            .render(&gitlabs.fetch_stats());
+        let content_type = Header::from_bytes("Content-Type", "text/plain; version=0.0.4")
+            .expect_or_log("Valid header");
+        Response::from_string(text).with_header(content_type)
+    };
+
+    if let Err(error) = request.respond(response) {
+        tracing::warn!("Unable to answer metrics request: {}", error);
+    }
+}
+
+fn header(text: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(text, "# HELP {} {}", name, help);
+    let _ = writeln!(text, "# TYPE {} {}", name, kind);
+}
+
+/// Label value with backslashes, quotes and line feeds escaped
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
@@ -0,0 +1,456 @@
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::{
+    channel::oneshot,
+    future::{FutureExt, Shared},
+};
+use gitlab::{
+    api::{ApiError, AsyncClient, RestClient},
+    AsyncGitlab, RestError,
+};
+use gpui::Timer;
+use http::{header, HeaderMap, Method, Response, StatusCode, Uri};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+use tokio::sync::Semaphore;
+use tracing_unwrap::ResultExt;
+use url::Url;
+
+/// Maximum number of concurrent requests sent to one instance
+pub const MAX_CONCURRENT_REQUESTS: usize = 6;
+/// Number of retries of a request which failed with a transient error
+pub const MAX_RETRIES: u32 = 3;
+/// Delay before the first retry, doubled on each attempt
+pub const RETRY_BACKOFF: Duration = Duration::from_millis(500);
+/// Upper bound of any wait asked by the instance
+pub const MAX_RATE_LIMIT_WAIT: Duration = Duration::from_secs(60);
+/// Maximum total body size of responses kept for conditional requests
+pub const MAX_CACHED_BYTES: usize = 32 * 1024 * 1024;
+/// Larger responses (eg. job logs) are not kept for conditional requests
+pub const MAX_CACHED_RESPONSE_BYTES: usize = 1024 * 1024;
+/// Upper bounds (in seconds) of request latency histogram buckets
+pub const LATENCY_BUCKETS: [f64; 7] = [0.1, 0.25, 0.5, 1., 2.5, 5., 10.];
+
+/// Requests sent to an instance since startup, each retry counts
+#[derive(Debug, Clone, Default)]
+pub struct FetchStats {
+    pub requests: u64,
+    /// Failed requests (connection errors, 4xx and 5xx answers)
+    pub errors: u64,
+    /// Requests count per `LATENCY_BUCKETS` upper bound, cumulative
+    pub latency_buckets: [u64; LATENCY_BUCKETS.len()],
+    pub latency_sum: f64,
+}
+
+impl FetchStats {
+    fn record(&mut self, latency: Duration, failed: bool) {
+        let latency = latency.as_secs_f64();
+        self.requests += 1;
+        if failed {
+            self.errors += 1;
+        }
+        for (bucket, upper_bound) in self.latency_buckets.iter_mut().zip(LATENCY_BUCKETS) {
+            if latency <= upper_bound {
+                *bucket += 1;
+            }
+        }
+        self.latency_sum += latency;
+    }
+}
+
+/// Client of a Gitlab instance sharing a cache, a rate limit and a
+/// concurrency limit between all its clones
+#[derive(Clone)]
+pub struct GitlabClient {
+    inner: AsyncGitlab,
+    layer: Arc<HttpLayer>,
+}
+
+impl GitlabClient {
+    pub fn new(inner: AsyncGitlab) -> Self {
+        Self {
+            inner,
+            layer: Arc::new(HttpLayer::new()),
+        }
+    }
+
+    pub fn fetch_stats(&self) -> FetchStats {
+        self.layer.stats.lock().unwrap_or_log().clone()
+    }
+
+    async fn send(
+        &self,
+        request: &RequestParts,
+        body: &[u8],
+    ) -> Result<Response<Bytes>, ApiError<RestError>> {
+        let mut attempt = 0;
+
+        loop {
+            self.layer.wait_rate_limit().await;
+
+            let result = {
+                let _permit = self
+                    .layer
+                    .permits
+                    .acquire()
+                    .await
+                    .expect_or_log("Semaphore is never closed");
+                let started = Instant::now();
+                let result = self
+                    .inner
+                    .rest_async(request.builder(), body.to_vec())
+                    .await;
+                let failed = match &result {
+                    Ok(response) => {
+                        response.status().is_client_error() || response.status().is_server_error()
+                    }
+                    Err(_) => true,
+                };
+                self.layer
+                    .stats
+                    .lock()
+                    .unwrap_or_log()
+                    .record(started.elapsed(), failed);
+                result
+            };
+
+            // Other methods may have been processed despite the error, only a rate limited one
+            // is retried to not repeat its side effects
+            let retry = match &result {
+                Ok(response) => {
+                    self.layer.update_rate_limit(response);
+                    if request.method == Method::GET {
+                        is_transient_status(response.status())
+                    } else {
+                        response.status() == StatusCode::TOO_MANY_REQUESTS
+                    }
+                }
+                Err(error) => request.method == Method::GET && is_transient_error(error),
+            };
+            if !retry || attempt >= MAX_RETRIES {
+                return result;
+            }
+
+            let backoff = RETRY_BACKOFF * 2u32.pow(attempt);
+            attempt += 1;
+            tracing::warn!(
+                "Transient error on {} {}, retry {}/{} in {:?}",
+                request.method,
+                request.uri,
+                attempt,
+                MAX_RETRIES,
+                backoff
+            );
+            Timer::after(backoff).await;
+        }
+    }
+
+    async fn send_cached(
+        &self,
+        request: &RequestParts,
+        body: &[u8],
+        key: &str,
+    ) -> Result<Response<Bytes>, ApiError<RestError>> {
+        let cached = self.layer.cached(key);
+
+        let mut request = request.clone();
+        if let Some(etag) = cached.as_ref().and_then(|cached| cached.etag()) {
+            request.headers.insert(header::IF_NONE_MATCH, etag);
+        }
+
+        let response = self.send(&request, body).await?;
+
+        if response.status() == StatusCode::NOT_MODIFIED {
+            if let Some(cached) = cached {
+                return Ok(cached.response());
+            }
+        }
+        if response.status().is_success() {
+            self.layer.store(key, CachedResponse::new(&response));
+        }
+
+        Ok(response)
+    }
+}
+
+impl RestClient for GitlabClient {
+    type Error = RestError;
+
+    fn rest_endpoint(&self, endpoint: &str) -> Result<Url, ApiError<Self::Error>> {
+        self.inner.rest_endpoint(endpoint)
+    }
+
+    fn instance_endpoint(&self, endpoint: &str) -> Result<Url, ApiError<Self::Error>> {
+        self.inner.instance_endpoint(endpoint)
+    }
+}
+
+#[async_trait]
+impl AsyncClient for GitlabClient {
+    async fn rest_async(
+        &self,
+        request: http::request::Builder,
+        body: Vec<u8>,
+    ) -> Result<Response<Bytes>, ApiError<Self::Error>> {
+        let request = RequestParts::new(&request);
+
+        if request.method != Method::GET {
+            return self.send(&request, &body).await;
+        }
+
+        let key = request.uri.to_string();
+        match self.layer.in_flight(&key) {
+            InFlight::Follower(pending) => {
+                // Same request already running, reuse its response
+                if let Ok(cached) = pending.await {
+                    return Ok(cached.response());
+                }
+                // Leader failed, each waiting request goes on its own
+                self.send_cached(&request, &body, &key).await
+            }
+            InFlight::Leader(sender) => {
+                // Entry must be removed even if this future is dropped before completion
+                let guard = InFlightGuard {
+                    layer: &self.layer,
+                    key: &key,
+                };
+                let result = self.send_cached(&request, &body, &key).await;
+                drop(guard);
+                if let Ok(response) = &result {
+                    if response.status().is_success() {
+                        let _ = sender.send(CachedResponse::new(response));
+                    }
+                }
+                result
+            }
+        }
+    }
+}
+
+type Pending = Shared<oneshot::Receiver<CachedResponse>>;
+
+enum InFlight {
+    Leader(oneshot::Sender<CachedResponse>),
+    Follower(Pending),
+}
+
+struct HttpLayer {
+    permits: Semaphore,
+    blocked_until: Mutex<Option<Instant>>,
+    cache: Mutex<ResponseCache>,
+    in_flight: Mutex<HashMap<String, Pending>>,
+    stats: Mutex<FetchStats>,
+}
+
+impl HttpLayer {
+    fn new() -> Self {
+        Self {
+            permits: Semaphore::new(MAX_CONCURRENT_REQUESTS),
+            blocked_until: Mutex::new(None),
+            cache: Mutex::new(ResponseCache::default()),
+            in_flight: Mutex::new(HashMap::new()),
+            stats: Mutex::new(FetchStats::default()),
+        }
+    }
+
+    async fn wait_rate_limit(&self) {
+        let blocked_until = *self.blocked_until.lock().unwrap_or_log();
+        if let Some(blocked_until) = blocked_until {
+            let now = Instant::now();
+            if blocked_until > now {
+                Timer::after(blocked_until - now).await;
+            }
+        }
+    }
+
+    fn update_rate_limit(&self, response: &Response<Bytes>) {
+        let headers = response.headers();
+        let wait = if matches!(
+            response.status(),
+            StatusCode::TOO_MANY_REQUESTS | StatusCode::SERVICE_UNAVAILABLE
+        ) {
+            header_u64(headers, header::RETRY_AFTER.as_str()).map(Duration::from_secs)
+        } else if header_u64(headers, "ratelimit-remaining") == Some(0) {
+            header_u64(headers, "ratelimit-reset").map(|reset| {
+                let now = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs();
+                Duration::from_secs(reset.saturating_sub(now))
+            })
+        } else {
+            None
+        };
+
+        if let Some(wait) = wait {
+            let wait = wait.min(MAX_RATE_LIMIT_WAIT);
+            tracing::warn!("Rate limited, wait {:?} before next requests", wait);
+            *self.blocked_until.lock().unwrap_or_log() = Some(Instant::now() + wait);
+        }
+    }
+
+    fn cached(&self, key: &str) -> Option<CachedResponse> {
+        self.cache.lock().unwrap_or_log().get(key)
+    }
+
+    fn store(&self, key: &str, response: CachedResponse) {
+        if response.etag().is_none() || response.body.len() > MAX_CACHED_RESPONSE_BYTES {
+            return;
+        }
+        self.cache.lock().unwrap_or_log().insert(key, response);
+    }
+
+    fn in_flight(&self, key: &str) -> InFlight {
+        let mut in_flight = self.in_flight.lock().unwrap_or_log();
+        if let Some(pending) = in_flight.get(key) {
+            return InFlight::Follower(pending.clone());
+        }
+        let (sender, receiver) = oneshot::channel();
+        in_flight.insert(key.to_string(), receiver.shared());
+
+        InFlight::Leader(sender)
+    }
+
+    fn done(&self, key: &str) {
+        self.in_flight.lock().unwrap_or_log().remove(key);
+    }
+}
+
+/// Remove its in flight entry when dropped, so followers of a cancelled
+/// leader go on their own and later requests can lead again
+struct InFlightGuard<'a> {
+    layer: &'a HttpLayer,
+    key: &'a str,
+}
+
+impl Drop for InFlightGuard<'_> {
+    fn drop(&mut self) {
+        self.layer.done(self.key);
+    }
+}
+
+/// Responses kept for conditional requests, least recently used ones are
+/// evicted past `MAX_CACHED_BYTES`
+#[derive(Default)]
+struct ResponseCache {
+    /// Response and its last use
+    entries: HashMap<String, (CachedResponse, u64)>,
+    bytes: usize,
+    uses: u64,
+}
+
+impl ResponseCache {
+    fn get(&mut self, key: &str) -> Option<CachedResponse> {
+        self.uses += 1;
+        let (response, last_use) = self.entries.get_mut(key)?;
+        *last_use = self.uses;
+
+        Some(response.clone())
+    }
+
+    fn insert(&mut self, key: &str, response: CachedResponse) {
+        self.uses += 1;
+        self.bytes += response.body.len();
+        if let Some((replaced, _)) = self.entries.insert(key.to_string(), (response, self.uses)) {
+            self.bytes -= replaced.body.len();
+        }
+
+        while self.bytes > MAX_CACHED_BYTES {
+            let Some(oldest) = self
+                .entries
+                .iter()
+                .min_by_key(|(_, (_, last_use))| *last_use)
+                .map(|(key, _)| key.clone())
+            else {
+                break;
+            };
+            if let Some((evicted, _)) = self.entries.remove(&oldest) {
+                self.bytes -= evicted.body.len();
+            }
+        }
+    }
+}
+
+#[derive(Clone)]
+struct CachedResponse {
+    status: StatusCode,
+    headers: HeaderMap,
+    body: Bytes,
+}
+
+impl CachedResponse {
+    fn new(response: &Response<Bytes>) -> Self {
+        Self {
+            status: response.status(),
+            headers: response.headers().clone(),
+            body: response.body().clone(),
+        }
+    }
+
+    fn etag(&self) -> Option<http::HeaderValue> {
+        self.headers.get(header::ETAG).cloned()
+    }
+
+    fn response(&self) -> Response<Bytes> {
+        let mut response = Response::new(self.body.clone());
+        *response.status_mut() = self.status;
+        *response.headers_mut() = self.headers.clone();
+
+        response
+    }
+}
+
+/// Parts of a request, kept to rebuild it on each attempt
+#[derive(Clone)]
+struct RequestParts {
+    method: Method,
+    uri: Uri,
+    headers: HeaderMap,
+}
+
+impl RequestParts {
+    fn new(builder: &http::request::Builder) -> Self {
+        Self {
+            method: builder.method_ref().cloned().unwrap_or_default(),
+            uri: builder.uri_ref().cloned().unwrap_or_default(),
+            headers: builder.headers_ref().cloned().unwrap_or_default(),
+        }
+    }
+
+    fn builder(&self) -> http::request::Builder {
+        let mut builder = http::Request::builder()
+            .method(self.method.clone())
+            .uri(self.uri.clone());
+        if let Some(headers) = builder.headers_mut() {
+            headers.extend(self.headers.clone());
+        }
+
+        builder
+    }
+}
+
+fn header_u64(headers: &HeaderMap, name: &str) -> Option<u64> {
+    headers.get(name)?.to_str().ok()?.trim().parse().ok()
+}
+
+fn is_transient_status(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::TOO_MANY_REQUESTS
+            | StatusCode::BAD_GATEWAY
+            | StatusCode::SERVICE_UNAVAILABLE
+            | StatusCode::GATEWAY_TIMEOUT
+    )
+}
+
+fn is_transient_error(error: &ApiError<RestError>) -> bool {
+    matches!(
+        error,
+        ApiError::Client {
+            source: RestError::Communication { .. }
+        }
+    )
+}
@@ -0,0 +1,128 @@
+use serde::Deserialize;
+
+use crate::state::gitlab::user::User;
+
+#[allow(dead_code)]
+#[derive(Debug, Deserialize, Clone)]
+pub struct Event {
+    pub id: u64,
+    pub project_id: Option<u64>,
+    pub action_name: String,
+    pub target_id: Option<u64>,
+    pub target_iid: Option<u64>,
+    pub target_type: Option<String>,
+    pub target_title: Option<String>,
+    pub created_at: String,
+    pub author: User,
+    pub push_data: Option<PushData>,
+    pub note: Option<EventNote>,
+}
+
+impl Event {
+    pub fn kind(&self) -> EventKind {
+        if self.push_data.is_some() {
+            return EventKind::Push;
+        }
+
+        match self.target_type.as_deref() {
+            Some("Note" | "DiffNote" | "DiscussionNote") => EventKind::Comment,
+            Some("MergeRequest") => EventKind::MergeRequest,
+            Some("Issue" | "WorkItem") => EventKind::Issue,
+            _ => EventKind::Other,
+        }
+    }
+
+    pub fn day(&self) -> String {
+        self.created_at.chars().take(10).collect()
+    }
+
+    pub fn summary(&self) -> String {
+        if let Some(push_data) = &self.push_data {
+            return format!(
+                "{} {} {} {}",
+                self.action_name,
+                push_data.ref_type,
+                push_data.ref_.clone().unwrap_or_default(),
+                push_data
+                    .commit_title
+                    .clone()
+                    .map(|title| format!("({})", title))
+                    .unwrap_or_default(),
+            );
+        }
+
+        format!(
+            "{} {} {}",
+            self.action_name,
+            self.target_type.clone().unwrap_or_default(),
+            self.target_title.clone().unwrap_or_default()
+        )
+    }
+
+    /// Url of the event target, from the web url of its project
+    pub fn target_url(&self, project_url: &str) -> String {
+        if let Some(push_data) = &self.push_data {
+            return match &push_data.ref_ {
+                Some(ref_) if push_data.ref_type == "branch" => {
+                    format!("{}/-/commits/{}", project_url, ref_)
+                }
+                Some(ref_) => format!("{}/-/tags/{}", project_url, ref_),
+                None => project_url.to_string(),
+            };
+        }
+
+        match (self.target_type.as_deref(), self.target_iid, &self.note) {
+            (Some("MergeRequest"), Some(iid), _) => {
+                format!("{}/-/merge_requests/{}", project_url, iid)
+            }
+            (Some("Issue" | "WorkItem"), Some(iid), _) => {
+                format!("{}/-/issues/{}", project_url, iid)
+            }
+            (Some("Milestone"), Some(iid), _) => format!("{}/-/milestones/{}", project_url, iid),
+            (Some("Note" | "DiffNote" | "DiscussionNote"), _, Some(note)) => {
+                let noteable = match note.noteable_type.as_str() {
+                    "MergeRequest" => "merge_requests",
+                    "Issue" => "issues",
+                    _ => return project_url.to_string(),
+                };
+                format!(
+                    "{}/-/{}/{}#note_{}",
+                    project_url,
+                    noteable,
+                    note.noteable_iid.unwrap_or_default(),
+                    note.id
+                )
+            }
+            _ => project_url.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventKind {
+    Push,
+    Comment,
+    MergeRequest,
+    Issue,
+    Other,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Deserialize, Clone)]
+pub struct PushData {
+    pub commit_count: u64,
+    pub action: String,
+    pub ref_type: String,
+    #[serde(rename = "ref")]
+    pub ref_: Option<String>,
+    pub commit_title: Option<String>,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Deserialize, Clone)]
+pub struct EventNote {
+    pub id: u64,
+    pub body: String,
+    pub noteable_type: String,
+    pub noteable_iid: Option<u64>,
+}
@@ -0,0 +1,232 @@
+use serde::{de::DeserializeOwned, Deserialize};
+
+use crate::state::gitlab::project::{
+    merge_request::{DiffRefs, MergeRequest, MergeRequestContainer, MergeRequestState},
+    pipeline::{Pipeline, PipelineStatus},
+    Namespace, Project,
+};
+
+/// GraphQL answer, `errors` may be filled even with `data`
+#[derive(Debug, Deserialize)]
+pub struct Response<T> {
+    pub data: Option<T>,
+    #[serde(default)]
+    pub errors: Vec<ResponseError>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ResponseError {
+    pub message: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Connection<T> {
+    pub nodes: Vec<T>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectsData {
+    pub current_user: Option<CurrentUser>,
+    pub projects: Connection<ProjectNode>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CurrentUser {
+    pub username: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectNode {
+    pub id: String,
+    pub name: String,
+    pub full_path: String,
+    pub web_url: String,
+    pub last_activity_at: String,
+    pub namespace: Option<NamespaceNode>,
+    pub repository: Option<RepositoryNode>,
+}
+
+impl ProjectNode {
+    pub fn project_id(&self) -> Option<u64> {
+        global_id(&self.id)
+    }
+
+    pub fn default_branch(&self) -> String {
+        self.repository
+            .as_ref()
+            .and_then(|repository| repository.root_ref.clone())
+            .unwrap_or_default()
+    }
+
+    pub fn into_project(self) -> Project {
+        let default_branch = self.default_branch();
+        let namespace = self.namespace.unwrap_or_else(|| NamespaceNode {
+            name: String::new(),
+            full_path: String::new(),
+        });
+
+        Project {
+            name: self.name,
+            default_branch,
+            web_url: self.web_url,
+            namespace: Namespace {
+                name: namespace.name,
+                full_path: namespace.full_path,
+            },
+            last_activity_at: self.last_activity_at,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NamespaceNode {
+    pub name: String,
+    pub full_path: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RepositoryNode {
+    pub root_ref: Option<String>,
+}
+
+/// Pipelines and merge requests of a project, queried with an alias per project
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectDetailsNode {
+    pub pipelines: Option<Connection<PipelineNode>>,
+    pub merge_requests: Option<Connection<MergeRequestNode>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PipelineNode {
+    pub id: String,
+    pub iid: String,
+    pub status: String,
+    /// Path on the instance, GraphQL pipelines have no web url
+    pub path: Option<String>,
+    pub created_at: String,
+    #[serde(rename = "ref")]
+    pub ref_: Option<String>,
+    pub started_at: Option<String>,
+    pub finished_at: Option<String>,
+    pub duration: Option<u64>,
+    pub queued_duration: Option<f64>,
+}
+
+impl PipelineNode {
+    /// `None` for statuses unknown to REST models
+    pub fn into_pipeline(self, project_id: u64, instance_url: &str) -> Option<Pipeline> {
+        Some(Pipeline {
+            id: global_id(&self.id)?,
+            iid: self.iid.parse().ok()?,
+            project_id,
+            status: from_enum::<PipelineStatus>(&self.status)?,
+            web_url: format!("{}{}", instance_url, self.path.unwrap_or_default()),
+            created_at: self.created_at,
+            ref_: self.ref_.unwrap_or_default(),
+            started_at: self.started_at,
+            finished_at: self.finished_at,
+            duration: self.duration,
+            queued_duration: self.queued_duration,
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeRequestNode {
+    pub id: String,
+    pub iid: String,
+    pub title: String,
+    pub state: MergeRequestState,
+    pub web_url: String,
+    pub created_at: String,
+    pub updated_at: String,
+    pub merged_at: Option<String>,
+    pub detailed_merge_status: Option<String>,
+    #[serde(default)]
+    pub draft: bool,
+    pub description: Option<String>,
+    pub diff_refs: Option<DiffRefsNode>,
+    pub head_pipeline: Option<PipelineNode>,
+    pub discussions: Option<Connection<DiscussionNode>>,
+}
+
+impl MergeRequestNode {
+    pub fn into_container(
+        self,
+        project_id: u64,
+        instance_url: &str,
+    ) -> Option<MergeRequestContainer> {
+        let last_pipeline = self
+            .head_pipeline
+            .and_then(|pipeline| pipeline.into_pipeline(project_id, instance_url));
+        let unresolved_discussions = self
+            .discussions
+            .map(|discussions| {
+                discussions
+                    .nodes
+                    .iter()
+                    .filter(|discussion| discussion.resolvable && !discussion.resolved)
+                    .count()
+            });
+
+        let merge_request = MergeRequest {
+            id: global_id(&self.id)?,
+            iid: self.iid.parse().ok()?,
+            project_id,
+            title: self.title,
+            state: self.state,
+            web_url: self.web_url,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+            merged_at: self.merged_at,
+            detailed_merge_status: self
+                .detailed_merge_status
+                .unwrap_or_default()
+                .to_lowercase(),
+            draft: self.draft,
+            description: self.description,
+            diff_refs: self.diff_refs.map(|diff_refs| DiffRefs {
+                base_sha: diff_refs.base_sha,
+                head_sha: diff_refs.head_sha,
+                start_sha: diff_refs.start_sha,
+            }),
+        };
+
+        Some(MergeRequestContainer::new(
+            merge_request,
+            last_pipeline,
+            unresolved_discussions,
+        ))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiffRefsNode {
+    pub base_sha: String,
+    pub head_sha: String,
+    pub start_sha: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DiscussionNode {
+    pub resolvable: bool,
+    pub resolved: bool,
+}
+
+/// Numeric id of a global id (eg. `gid://gitlab/Project/42`)
+pub fn global_id(id: &str) -> Option<u64> {
+    id.rsplit('/').next()?.parse().ok()
+}
+
+/// GraphQL enums are the uppercased REST values
+fn from_enum<T: DeserializeOwned>(value: &str) -> Option<T> {
+    serde_json::from_value(serde_json::Value::String(value.to_lowercase())).ok()
+}
@@ -1,11 +1,14 @@
 use serde::Deserialize;
 
 #[allow(dead_code)]
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct Issue {
     pub id: u64,
     pub iid: u64,
     pub title: String,
     pub state: String,
     pub web_url: String,
+    pub updated_at: String,
+    #[serde(default)]
+    pub description: Option<String>,
 }
@@ -1,23 +1,31 @@
-use gitlab::{AsyncGitlab, GitlabBuilder};
+use gitlab::GitlabBuilder;
 use std::sync::Arc;
 use thiserror::Error;
 use tokio::sync::Mutex;
 
 use crate::config::get_password;
 
+pub mod client;
+pub mod event;
+pub mod graphql;
 pub mod issue;
 pub mod project;
+pub mod search;
 pub mod todo;
 pub mod user;
 
+pub use client::GitlabClient;
+
+use crate::state::gitlab::client::FetchStats;
+
 #[derive(Clone, Default)]
-pub struct Gitlabs(Arc<Mutex<Vec<(String, AsyncGitlab)>>>);
+pub struct Gitlabs(Arc<Mutex<Vec<(String, GitlabClient)>>>);
 
 impl Gitlabs {
     pub async fn get(
         &self,
         config: &crate::config::gitlab_::Gitlab,
-    ) -> Result<AsyncGitlab, GitlabsError> {
+    ) -> Result<GitlabClient, GitlabsError> {
         let mut values = self.0.lock().await;
 
         if values
@@ -34,7 +42,7 @@ impl Gitlabs {
                 builder.cert_insecure();
             }
             let gitlab = builder.build_async().await?;
-            values.push((config.host.clone(), gitlab));
+            values.push((config.host.clone(), GitlabClient::new(gitlab)));
         }
 
         Ok(values
@@ -45,6 +53,15 @@ impl Gitlabs {
             .clone())
     }
 
+    /// Requests statistics of each connected instance, not to be called from async code
+    pub fn fetch_stats(&self) -> Vec<(String, FetchStats)> {
+        self.0
+            .blocking_lock()
+            .iter()
+            .map(|(host, gitlab)| (host.clone(), gitlab.fetch_stats()))
+            .collect()
+    }
+
     pub fn invalidate(&self, host: &str) {
         let mut values = self.0.blocking_lock();
         values.retain(|(host_, _)| host_ != host);
@@ -0,0 +1,12 @@
+use serde::Deserialize;
+
+#[allow(dead_code)]
+#[derive(Debug, Deserialize, Clone)]
+pub struct Commit {
+    pub id: String,
+    pub short_id: String,
+    pub title: String,
+    pub author_name: String,
+    pub created_at: String,
+    pub web_url: Option<String>,
+}
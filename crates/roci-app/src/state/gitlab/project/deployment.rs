@@ -0,0 +1,87 @@
+use serde::Deserialize;
+use strum::{Display, EnumString};
+
+use crate::state::gitlab::user::User;
+
+#[allow(dead_code)]
+#[derive(Debug, Deserialize, Clone)]
+pub struct Environment {
+    pub id: u64,
+    pub name: String,
+    pub state: String,
+    pub external_url: Option<String>,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Deserialize, Clone)]
+pub struct Deployment {
+    pub id: u64,
+    pub iid: u64,
+    #[serde(rename = "ref")]
+    pub ref_: String,
+    pub sha: String,
+    pub status: DeploymentStatus,
+    pub created_at: String,
+    pub user: User,
+    pub environment: DeploymentEnvironment,
+    pub deployable: Option<Deployable>,
+}
+
+impl Deployment {
+    pub fn short_sha(&self) -> &str {
+        &self.sha[..self.sha.len().min(8)]
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Deserialize, Clone)]
+pub struct DeploymentEnvironment {
+    pub id: u64,
+    pub name: String,
+}
+
+/// Job which did the deployment, retrying it re-deploys its ref
+#[allow(dead_code)]
+#[derive(Debug, Deserialize, Clone)]
+pub struct Deployable {
+    pub id: u64,
+    pub name: String,
+    pub web_url: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, EnumString, Display)]
+#[serde(rename_all = "lowercase")]
+pub enum DeploymentStatus {
+    Created,
+    Running,
+    Success,
+    Failed,
+    Canceled,
+    Skipped,
+    Blocked,
+}
+
+/// An environment with its deployments, most recent first
+#[derive(Debug, Clone)]
+pub struct EnvironmentDeployments {
+    pub environment: Environment,
+    pub deployments: Vec<Deployment>,
+}
+
+impl EnvironmentDeployments {
+    pub fn last(&self) -> Option<&Deployment> {
+        self.deployments.first()
+    }
+
+    /// Previous successful deployments, candidates for a rollback
+    pub fn rollback_candidates(&self) -> impl Iterator<Item = &Deployment> {
+        let last_sha = self.last().map(|deployment| deployment.sha.clone());
+
+        self.deployments
+            .iter()
+            .skip(1)
+            .filter(|deployment| deployment.status == DeploymentStatus::Success)
+            .filter(move |deployment| Some(&deployment.sha) != last_sha.as_ref())
+            .filter(|deployment| deployment.deployable.is_some())
+    }
+}
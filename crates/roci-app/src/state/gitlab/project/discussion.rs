@@ -0,0 +1,151 @@
+use serde::Deserialize;
+
+use crate::state::gitlab::user::User;
+
+#[allow(dead_code)]
+#[derive(Debug, Deserialize, Clone)]
+pub struct Discussion {
+    pub id: String,
+    pub individual_note: bool,
+    pub notes: Vec<Note>,
+}
+
+impl Discussion {
+    pub fn is_resolvable(&self) -> bool {
+        self.notes.iter().any(|note| note.resolvable)
+    }
+
+    pub fn is_unresolved(&self) -> bool {
+        self.notes
+            .iter()
+            .any(|note| note.resolvable && !note.resolved)
+    }
+
+    /// File and line the discussion is attached to, if it's a diff discussion
+    pub fn location(&self) -> Option<(String, Option<u64>)> {
+        self.notes
+            .first()
+            .and_then(|note| note.position.as_ref())
+            .map(|position| (position.path(), position.line()))
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Deserialize, Clone)]
+pub struct Note {
+    pub id: u64,
+    pub body: String,
+    pub author: User,
+    pub created_at: String,
+    #[serde(default)]
+    pub system: bool,
+    #[serde(default)]
+    pub resolvable: bool,
+    #[serde(default)]
+    pub resolved: bool,
+    pub position: Option<NotePosition>,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Deserialize, Clone)]
+pub struct NotePosition {
+    pub base_sha: String,
+    pub start_sha: String,
+    pub head_sha: String,
+    pub old_path: Option<String>,
+    pub new_path: Option<String>,
+    pub old_line: Option<u64>,
+    pub new_line: Option<u64>,
+}
+
+impl NotePosition {
+    pub fn path(&self) -> String {
+        self.new_path
+            .clone()
+            .or(self.old_path.clone())
+            .unwrap_or_default()
+    }
+
+    pub fn line(&self) -> Option<u64> {
+        self.new_line.or(self.old_line)
+    }
+}
+
+/// Changes of a file by a merge request
+#[allow(dead_code)]
+#[derive(Debug, Deserialize, Clone)]
+pub struct FileDiff {
+    pub old_path: String,
+    pub new_path: String,
+    /// Unified diff hunks
+    pub diff: String,
+}
+
+/// Lines of a diff position, `old_line` is unset for an added line
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DiffLine {
+    pub old_line: Option<u64>,
+    pub new_line: u64,
+}
+
+impl FileDiff {
+    /// Position of `new_line` of the new file, which is an added, a context or an unchanged line
+    pub fn line(&self, new_line: u64) -> DiffLine {
+        // Difference between old and new line numbers of unchanged lines
+        let mut offset: i64 = 0;
+        let mut lines = self.diff.lines().peekable();
+
+        while let Some(header) = lines.next() {
+            let Some((old_start, new_start)) = hunk_starts(header) else {
+                continue;
+            };
+            if new_line < new_start {
+                break;
+            }
+
+            let (mut old, mut new) = (old_start, new_start);
+            while let Some(line) = lines.next_if(|line| !line.starts_with("@@")) {
+                match line.chars().next() {
+                    Some('+') => {
+                        if new == new_line {
+                            return DiffLine {
+                                old_line: None,
+                                new_line,
+                            };
+                        }
+                        new += 1;
+                    }
+                    Some('-') => old += 1,
+                    // "No newline at end of file"
+                    Some('\\') => {}
+                    _ => {
+                        if new == new_line {
+                            return DiffLine {
+                                old_line: Some(old),
+                                new_line,
+                            };
+                        }
+                        old += 1;
+                        new += 1;
+                    }
+                }
+            }
+            offset = old as i64 - new as i64;
+        }
+
+        DiffLine {
+            old_line: u64::try_from(new_line as i64 + offset).ok(),
+            new_line,
+        }
+    }
+}
+
+/// Old and new start lines of a `@@ -1,5 +1,6 @@` hunk header
+fn hunk_starts(header: &str) -> Option<(u64, u64)> {
+    let mut ranges = header.strip_prefix("@@ ")?.split_whitespace();
+    let start = |range: &str| range.split(',').next()?.parse::<u64>().ok();
+    let old_start = start(ranges.next()?.strip_prefix('-')?)?;
+    let new_start = start(ranges.next()?.strip_prefix('+')?)?;
+
+    Some((old_start, new_start))
+}
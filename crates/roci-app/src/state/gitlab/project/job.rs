@@ -0,0 +1,240 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+use strum::{Display, EnumString};
+
+#[allow(dead_code)]
+#[derive(Debug, Deserialize, Clone)]
+pub struct Job {
+    pub id: u64,
+    pub name: String,
+    pub stage: String,
+    pub status: JobStatus,
+    pub web_url: String,
+    pub created_at: String,
+    #[serde(default)]
+    pub allow_failure: bool,
+    pub pipeline: JobPipeline,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Deserialize, Clone)]
+pub struct JobPipeline {
+    pub id: u64,
+    pub sha: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, EnumString, Display)]
+#[serde(rename_all = "snake_case")]
+pub enum JobStatus {
+    Created,
+    Pending,
+    Running,
+    Failed,
+    Success,
+    Canceled,
+    Skipped,
+    WaitingForResource,
+    Manual,
+    Scheduled,
+    Preparing,
+    Canceling,
+    WaitingForCallback,
+    /// Status introduced by a newer GitLab
+    #[serde(other)]
+    Unknown,
+}
+
+impl JobStatus {
+    /// Job not finished yet, its log may still grow
+    pub fn is_running(&self) -> bool {
+        matches!(
+            self,
+            Self::Running
+                | Self::Pending
+                | Self::Created
+                | Self::Preparing
+                | Self::WaitingForResource
+                | Self::WaitingForCallback
+                | Self::Canceling
+        )
+    }
+}
+
+/// A job name whose outcome changed without code change, the higher `score` the flakier
+#[derive(Debug, Clone)]
+pub struct FlakyJob {
+    pub name: String,
+    pub stage: String,
+    /// Finished runs (success or failure) analysed
+    pub runs: usize,
+    pub failures: usize,
+    /// Commits where the job both failed and succeeded (retries, re-run pipelines)
+    pub retried_flips: usize,
+    /// Failures between two successful pipelines
+    pub isolated_failures: usize,
+    /// Most recent failures showing the flakiness
+    pub examples: Vec<Job>,
+}
+
+impl FlakyJob {
+    /// Retries with a different outcome are stronger evidences than isolated failures
+    pub fn score(&self) -> usize {
+        self.retried_flips * 2 + self.isolated_failures
+    }
+
+    /// Flaky jobs of given pipelines jobs (retried ones included), flakiest first
+    pub fn rank(jobs: &[Job], examples_count: usize) -> Vec<Self> {
+        let mut by_name: HashMap<&str, Vec<&Job>> = HashMap::new();
+        for job in jobs.iter().filter(|job| {
+            matches!(job.status, JobStatus::Success | JobStatus::Failed) && !job.allow_failure
+        }) {
+            by_name.entry(&job.name).or_default().push(job);
+        }
+
+        let mut flaky_jobs = by_name
+            .into_values()
+            .filter_map(|mut runs| {
+                runs.sort_by_key(|job| job.id);
+                Self::of_runs(&runs, examples_count)
+            })
+            .collect::<Vec<Self>>();
+        flaky_jobs.sort_by(|a, b| {
+            b.score()
+                .cmp(&a.score())
+                .then(b.failures.cmp(&a.failures))
+                .then(a.name.cmp(&b.name))
+        });
+
+        flaky_jobs
+    }
+
+    /// `runs` of a same job name, oldest first
+    fn of_runs(runs: &[&Job], examples_count: usize) -> Option<Self> {
+        let last = runs.last()?;
+        let mut examples = vec![];
+
+        let mut by_sha: HashMap<&str, Vec<&Job>> = HashMap::new();
+        for job in runs {
+            by_sha.entry(&job.pipeline.sha).or_default().push(*job);
+        }
+        let mut retried_flips = 0;
+        for jobs in by_sha.values() {
+            if jobs.iter().any(|job| job.status == JobStatus::Success) {
+                let failures = jobs
+                    .iter()
+                    .filter(|job| job.status == JobStatus::Failed)
+                    .collect::<Vec<_>>();
+                if !failures.is_empty() {
+                    retried_flips += 1;
+                    examples.extend(failures.into_iter().map(|job| (**job).clone()));
+                }
+            }
+        }
+
+        // Outcome of each pipeline is the one of its last attempt
+        let mut pipelines: Vec<&Job> = vec![];
+        for job in runs {
+            match pipelines
+                .iter_mut()
+                .find(|job_| job_.pipeline.id == job.pipeline.id)
+            {
+                Some(job_) => *job_ = *job,
+                None => pipelines.push(*job),
+            }
+        }
+        pipelines.sort_by_key(|job| job.pipeline.id);
+        let mut isolated_failures = 0;
+        for window in pipelines.windows(3) {
+            if window[0].status == JobStatus::Success
+                && window[1].status == JobStatus::Failed
+                && window[2].status == JobStatus::Success
+            {
+                isolated_failures += 1;
+                if !examples.iter().any(|job| job.id == window[1].id) {
+                    examples.push(window[1].clone());
+                }
+            }
+        }
+
+        if retried_flips == 0 && isolated_failures == 0 {
+            return None;
+        }
+
+        examples.sort_by_key(|job| std::cmp::Reverse(job.id));
+        examples.truncate(examples_count);
+
+        Some(Self {
+            name: last.name.clone(),
+            stage: last.stage.clone(),
+            runs: runs.len(),
+            failures: runs
+                .iter()
+                .filter(|job| job.status == JobStatus::Failed)
+                .count(),
+            retried_flips,
+            isolated_failures,
+            examples,
+        })
+    }
+}
+
+/// Last `count` lines of a job log, without terminal escape codes nor collapsible section markers
+pub fn log_lines(log: &str, count: usize) -> Vec<String> {
+    let mut lines = log
+        .lines()
+        .map(|line| {
+            let line = strip_escape_codes(line);
+            // Carriage returns overwrite the line in a terminal (progress bars, section headers)
+            line.split('\r')
+                .rev()
+                .find(|part| !part.is_empty())
+                .unwrap_or_default()
+                .to_string()
+        })
+        .filter(|line| !line.starts_with("section_start:") && !line.starts_with("section_end:"))
+        .collect::<Vec<String>>();
+    if lines.len() > count {
+        lines.drain(..lines.len() - count);
+    }
+
+    lines
+}
+
+/// Remove ANSI CSI sequences (colors, line clearing)
+fn strip_escape_codes(line: &str) -> String {
+    let mut stripped = String::with_capacity(line.len());
+    let mut chars = line.chars().peekable();
+    while let Some(char) = chars.next() {
+        if char == '\u{1b}' && chars.peek() == Some(&'[') {
+            chars.next();
+            // Parameters then a final byte between `@` and `~`
+            for char in chars.by_ref() {
+                if ('@'..='~').contains(&char) {
+                    break;
+                }
+            }
+        } else {
+            stripped.push(char);
+        }
+    }
+
+    stripped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recent_and_unknown_statuses_deserialize() {
+        let status = |raw: &str| serde_json::from_str::<JobStatus>(raw).unwrap();
+
+        assert_eq!(status("\"canceling\""), JobStatus::Canceling);
+        assert_eq!(
+            status("\"waiting_for_callback\""),
+            JobStatus::WaitingForCallback
+        );
+        assert_eq!(status("\"something_new\""), JobStatus::Unknown);
+    }
+}
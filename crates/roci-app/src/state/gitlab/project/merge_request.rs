@@ -4,12 +4,16 @@ use serde::Deserialize;
 use strum::{Display, EnumString};
 
 pub const MERGE_STATUS_MERGEABLE: &str = "mergeable";
+/// Approvals still required, ie. waiting for review
+pub const MERGE_STATUS_NOT_APPROVED: &str = "not_approved";
 
 #[allow(dead_code)]
 #[derive(Debug, Deserialize, Constructor)]
 pub struct MergeRequestContainer {
     inner: MergeRequest,
     pub last_pipeline: Option<Pipeline>,
+    /// Unknown if discussions couldn't be fetched
+    pub unresolved_discussions: Option<usize>,
 }
 
 impl std::ops::Deref for MergeRequestContainer {
@@ -30,7 +34,24 @@ pub struct MergeRequest {
     pub state: MergeRequestState,
     pub web_url: String,
     pub created_at: String,
+    pub updated_at: String,
+    #[serde(default)]
+    pub merged_at: Option<String>,
     pub detailed_merge_status: String,
+    #[serde(default)]
+    pub draft: bool,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub diff_refs: Option<DiffRefs>,
+}
+
+/// Only given by single merge request endpoint, required to comment on a diff line
+#[derive(Debug, Deserialize, Clone)]
+pub struct DiffRefs {
+    pub base_sha: String,
+    pub head_sha: String,
+    pub start_sha: String,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, EnumString, Display)]
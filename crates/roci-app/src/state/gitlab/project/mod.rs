@@ -1,10 +1,25 @@
 use serde::Deserialize;
 
+pub mod commit;
+pub mod deployment;
+pub mod discussion;
+pub mod job;
 pub mod merge_request;
 pub mod pipeline;
+pub mod release;
 
 #[derive(Debug, Deserialize)]
 pub struct Project {
     pub name: String,
     pub default_branch: String,
+    pub web_url: String,
+    pub namespace: Namespace,
+    pub last_activity_at: String,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Deserialize)]
+pub struct Namespace {
+    pub name: String,
+    pub full_path: String,
 }
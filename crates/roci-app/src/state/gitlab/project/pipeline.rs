@@ -1,5 +1,5 @@
 use serde::Deserialize;
-use strum::{Display, EnumString};
+use strum::{Display, EnumIter, EnumString};
 
 #[allow(dead_code)]
 #[derive(Debug, Deserialize, Clone)]
@@ -10,10 +10,23 @@ pub struct Pipeline {
     pub status: PipelineStatus,
     pub web_url: String,
     pub created_at: String,
+    #[serde(default, rename = "ref")]
+    pub ref_: String,
+    /// Only given by single pipeline endpoint (and webhooks, GraphQL)
+    #[serde(default)]
+    pub started_at: Option<String>,
+    #[serde(default)]
+    pub finished_at: Option<String>,
+    /// Seconds
+    #[serde(default)]
+    pub duration: Option<u64>,
+    /// Seconds
+    #[serde(default)]
+    pub queued_duration: Option<f64>,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, EnumString, Display)]
-#[serde(rename_all = "lowercase")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, EnumString, Display, EnumIter)]
+#[serde(rename_all = "snake_case")]
 pub enum PipelineStatus {
     Running,
     Pending,
@@ -28,8 +41,27 @@ pub enum PipelineStatus {
     WaitingForResource,
 }
 
+impl Pipeline {
+    /// Finished pipeline whose duration is still unknown (not from single pipeline endpoint)
+    pub fn lacks_details(&self) -> bool {
+        !self.status.is_running() && self.finished_at.is_none()
+    }
+}
+
 impl PipelineStatus {
     pub fn is_error(&self) -> bool {
         matches!(self, Self::Failed)
     }
+
+    /// Pipeline not finished yet (manual and scheduled ones are waiting for someone)
+    pub fn is_running(&self) -> bool {
+        matches!(
+            self,
+            Self::Running
+                | Self::Pending
+                | Self::Created
+                | Self::Preparing
+                | Self::WaitingForResource
+        )
+    }
 }
@@ -0,0 +1,61 @@
+use serde::Deserialize;
+
+use crate::state::gitlab::project::commit::Commit;
+
+#[allow(dead_code)]
+#[derive(Debug, Deserialize, Clone)]
+pub struct Release {
+    pub name: Option<String>,
+    pub tag_name: String,
+    pub description: Option<String>,
+    pub created_at: String,
+    pub released_at: Option<String>,
+    pub assets: ReleaseAssets,
+    #[serde(rename = "_links")]
+    pub links: ReleaseLinks,
+}
+
+impl Release {
+    pub fn title(&self) -> String {
+        self.name.clone().unwrap_or(self.tag_name.clone())
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct ReleaseAssets {
+    #[serde(default)]
+    pub links: Vec<ReleaseAssetLink>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct ReleaseAssetLink {
+    pub name: String,
+    pub url: String,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct ReleaseLinks {
+    #[serde(rename = "self")]
+    pub self_: Option<String>,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Deserialize, Clone)]
+pub struct Tag {
+    pub name: String,
+    pub message: Option<String>,
+    pub commit: Commit,
+    pub release: Option<TagRelease>,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Deserialize, Clone)]
+pub struct TagRelease {
+    pub tag_name: String,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct Compare {
+    pub commits: Vec<Commit>,
+    pub web_url: Option<String>,
+}
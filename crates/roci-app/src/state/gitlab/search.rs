@@ -0,0 +1,29 @@
+use serde::Deserialize;
+
+#[allow(dead_code)]
+#[derive(Debug, Deserialize, Clone)]
+pub struct SearchProject {
+    pub id: u64,
+    pub name_with_namespace: String,
+    pub description: Option<String>,
+    pub web_url: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchResultKind {
+    Project,
+    Issue,
+    MergeRequest,
+    Commit,
+}
+
+/// Search result of any kind, from any configured instance
+#[derive(Debug, Clone)]
+pub struct SearchResult {
+    pub host: String,
+    pub kind: SearchResultKind,
+    pub reference: String,
+    pub title: String,
+    pub web_url: String,
+    pub details: Option<String>,
+}
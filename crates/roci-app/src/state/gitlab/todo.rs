@@ -1,9 +1,14 @@
 use serde::Deserialize;
 
+pub const TODO_ACTION_REVIEW_REQUESTED: &str = "review_requested";
+
 #[allow(dead_code)]
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct Todo {
     pub id: u64,
     pub target_url: String,
     pub body: String,
+    #[serde(default)]
+    pub action_name: String,
+    pub created_at: String,
 }
@@ -0,0 +1,298 @@
+use std::{
+    collections::HashMap,
+    fs, io,
+    path::Path,
+    str::FromStr,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, Row};
+use thiserror::Error;
+use tracing_unwrap::ResultExt;
+
+use crate::{
+    config::{history::HistoryConfig, Config, ConfigError},
+    state::gitlab::project::pipeline::{Pipeline, PipelineStatus},
+};
+
+pub const HISTORY_FILE: &str = "history.sqlite3";
+/// Retention is applied at startup then at most once per this duration
+pub const RETENTION_EVERY: Duration = Duration::from_secs(24 * 60 * 60);
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS pipelines (
+        host TEXT NOT NULL,
+        project_id INTEGER NOT NULL,
+        pipeline_id INTEGER NOT NULL,
+        iid INTEGER NOT NULL,
+        ref TEXT NOT NULL,
+        status TEXT NOT NULL,
+        web_url TEXT NOT NULL,
+        created_at INTEGER NOT NULL,
+        started_at INTEGER,
+        finished_at INTEGER,
+        duration INTEGER,
+        queued_duration REAL,
+        recorded_at INTEGER NOT NULL,
+        PRIMARY KEY (host, pipeline_id)
+    );
+    CREATE INDEX IF NOT EXISTS pipelines_project
+        ON pipelines (host, project_id, created_at);
+";
+
+/// Known details are kept when a pipeline is recorded again from a list without them
+const UPSERT: &str = "
+    INSERT INTO pipelines (host, project_id, pipeline_id, iid, ref, status, web_url, created_at,
+        started_at, finished_at, duration, queued_duration, recorded_at)
+    VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)
+    ON CONFLICT (host, pipeline_id) DO UPDATE SET
+        ref = CASE WHEN excluded.ref = '' THEN ref ELSE excluded.ref END,
+        status = excluded.status,
+        started_at = COALESCE(excluded.started_at, started_at),
+        finished_at = COALESCE(excluded.finished_at, finished_at),
+        duration = COALESCE(excluded.duration, duration),
+        queued_duration = COALESCE(excluded.queued_duration, queued_duration),
+        recorded_at = excluded.recorded_at
+";
+
+const SELECT_FIELDS: &str = "project_id, pipeline_id, iid, ref, status, web_url, created_at, \
+    started_at, finished_at, duration, queued_duration";
+
+#[derive(Debug, Error)]
+pub enum HistoryError {
+    #[error("Database error: {0}")]
+    Sqlite(#[from] rusqlite::Error),
+    #[error("Io error: {0}")]
+    Io(#[from] io::Error),
+    #[error("Config error: {0}")]
+    Config(#[from] ConfigError),
+}
+
+/// Local database of pipelines seen by any fetcher, kept across runs
+pub struct History {
+    connection: Mutex<Connection>,
+    config: HistoryConfig,
+    retained_at: Mutex<Instant>,
+}
+
+/// Summary of finished pipelines
+#[derive(Debug, Clone, Default)]
+pub struct PipelineStats {
+    pub succeeded: usize,
+    pub failed: usize,
+    /// Seconds, of pipelines with a known duration
+    pub average_duration: Option<f64>,
+}
+
+impl PipelineStats {
+    pub fn from_pipelines(pipelines: &[Pipeline]) -> Self {
+        let succeeded = pipelines
+            .iter()
+            .filter(|pipeline| pipeline.status == PipelineStatus::Success)
+            .count();
+        let failed = pipelines
+            .iter()
+            .filter(|pipeline| pipeline.status.is_error())
+            .count();
+        let durations = pipelines
+            .iter()
+            .filter_map(|pipeline| pipeline.duration)
+            .collect::<Vec<u64>>();
+        let average_duration = (!durations.is_empty())
+            .then(|| durations.iter().sum::<u64>() as f64 / durations.len() as f64);
+
+        Self {
+            succeeded,
+            failed,
+            average_duration,
+        }
+    }
+
+    /// Between 0 and 1, `None` without any succeeded or failed pipeline
+    pub fn success_rate(&self) -> Option<f64> {
+        let total = self.succeeded + self.failed;
+        (total > 0).then(|| self.succeeded as f64 / total as f64)
+    }
+}
+
+impl History {
+    /// Open (or create) the database in the data directory
+    pub fn open(config: &HistoryConfig) -> Result<Self, HistoryError> {
+        let dir = Config::data_dir()?;
+        fs::create_dir_all(&dir)?;
+
+        Self::open_path(&dir.join(HISTORY_FILE), config)
+    }
+
+    pub fn open_path(path: &Path, config: &HistoryConfig) -> Result<Self, HistoryError> {
+        let connection = Connection::open(path)?;
+        connection.execute_batch(SCHEMA)?;
+
+        let history = Self {
+            connection: Mutex::new(connection),
+            config: config.clone(),
+            retained_at: Mutex::new(Instant::now()),
+        };
+        history.apply_retention()?;
+
+        Ok(history)
+    }
+
+    /// Insert or update pipelines of an instance
+    pub fn record(&self, host: &str, pipelines: &[Pipeline]) -> Result<(), HistoryError> {
+        let now = Utc::now().timestamp();
+        {
+            let mut connection = self.connection.lock().expect_or_log("History lock poisoned");
+            let transaction = connection.transaction()?;
+            {
+                let mut statement = transaction.prepare_cached(UPSERT)?;
+                for pipeline in pipelines {
+                    let Some(created_at) = timestamp(&pipeline.created_at) else {
+                        tracing::debug!("Pipeline {} has an invalid date", pipeline.id);
+                        continue;
+                    };
+
+                    statement.execute(params![
+                        host,
+                        pipeline.project_id,
+                        pipeline.id,
+                        pipeline.iid,
+                        pipeline.ref_,
+                        pipeline.status.to_string(),
+                        pipeline.web_url,
+                        created_at,
+                        pipeline.started_at.as_deref().and_then(timestamp),
+                        pipeline.finished_at.as_deref().and_then(timestamp),
+                        pipeline.duration,
+                        pipeline.queued_duration,
+                        now,
+                    ])?;
+                }
+            }
+            transaction.commit()?;
+        }
+
+        let due = self
+            .retained_at
+            .lock()
+            .expect_or_log("History lock poisoned")
+            .elapsed()
+            > RETENTION_EVERY;
+        if due {
+            self.apply_retention()?;
+        }
+
+        Ok(())
+    }
+
+    /// Delete pipelines older than retention days, then oldest ones above the count per project
+    pub fn apply_retention(&self) -> Result<usize, HistoryError> {
+        let connection = self.connection.lock().expect_or_log("History lock poisoned");
+        let oldest = Utc::now() - chrono::Duration::days(self.config.retention_days as i64);
+
+        let mut deleted = connection.execute(
+            "DELETE FROM pipelines WHERE created_at < ?1",
+            [oldest.timestamp()],
+        )?;
+        deleted += connection.execute(
+            "DELETE FROM pipelines WHERE rowid IN (
+                SELECT rowid FROM (
+                    SELECT rowid, ROW_NUMBER() OVER (
+                        PARTITION BY host, project_id ORDER BY created_at DESC
+                    ) AS position FROM pipelines
+                ) WHERE position > ?1
+            )",
+            [self.config.max_pipelines_per_project],
+        )?;
+        *self.retained_at.lock().expect_or_log("History lock poisoned") = Instant::now();
+
+        if deleted > 0 {
+            tracing::info!("{} pipelines removed from history", deleted);
+        }
+
+        Ok(deleted)
+    }
+
+    /// Pipelines of a project created since given date, oldest first, of any ref if `None`
+    pub fn pipelines(
+        &self,
+        host: &str,
+        project_id: u64,
+        ref_: Option<&str>,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<Pipeline>, HistoryError> {
+        let connection = self.connection.lock().expect_or_log("History lock poisoned");
+        let mut statement = connection.prepare_cached(&format!(
+            "SELECT {} FROM pipelines
+            WHERE host = ?1 AND project_id = ?2 AND (?3 IS NULL OR ref = ?3) AND created_at >= ?4
+            ORDER BY created_at ASC",
+            SELECT_FIELDS
+        ))?;
+        let pipelines = statement
+            .query_map(params![host, project_id, ref_, since.timestamp()], pipeline)?
+            .collect::<Result<Vec<Option<Pipeline>>, rusqlite::Error>>()?;
+
+        Ok(pipelines.into_iter().flatten().collect())
+    }
+
+    /// Stored details (dates, durations) of given pipelines, only pipelines with details are
+    /// returned
+    pub fn details(
+        &self,
+        host: &str,
+        pipeline_ids: &[u64],
+    ) -> Result<HashMap<u64, Pipeline>, HistoryError> {
+        let connection = self.connection.lock().expect_or_log("History lock poisoned");
+        let mut statement = connection.prepare_cached(&format!(
+            "SELECT {} FROM pipelines
+            WHERE host = ?1 AND pipeline_id = ?2 AND finished_at IS NOT NULL",
+            SELECT_FIELDS
+        ))?;
+
+        let mut details = HashMap::new();
+        for pipeline_id in pipeline_ids {
+            let mut rows = statement.query_map(params![host, pipeline_id], pipeline)?;
+            if let Some(pipeline) = rows.next().transpose()?.flatten() {
+                details.insert(*pipeline_id, pipeline);
+            }
+        }
+
+        Ok(details)
+    }
+}
+
+/// `None` for a status unknown to this version
+fn pipeline(row: &Row) -> Result<Option<Pipeline>, rusqlite::Error> {
+    let status: String = row.get("status")?;
+    let Ok(status) = PipelineStatus::from_str(&status) else {
+        return Ok(None);
+    };
+
+    Ok(Some(Pipeline {
+        id: row.get("pipeline_id")?,
+        iid: row.get("iid")?,
+        project_id: row.get("project_id")?,
+        status,
+        web_url: row.get("web_url")?,
+        created_at: datetime(row.get("created_at")?),
+        ref_: row.get("ref")?,
+        started_at: row.get::<_, Option<i64>>("started_at")?.map(datetime),
+        finished_at: row.get::<_, Option<i64>>("finished_at")?.map(datetime),
+        duration: row.get("duration")?,
+        queued_duration: row.get("queued_duration")?,
+    }))
+}
+
+fn timestamp(datetime: &str) -> Option<i64> {
+    DateTime::parse_from_rfc3339(datetime)
+        .ok()
+        .map(|datetime| datetime.timestamp())
+}
+
+fn datetime(timestamp: i64) -> String {
+    DateTime::from_timestamp(timestamp, 0)
+        .unwrap_or_default()
+        .to_rfc3339()
+}
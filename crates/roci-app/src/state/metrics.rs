@@ -0,0 +1,156 @@
+use chrono::{DateTime, TimeDelta, Utc};
+
+use crate::state::gitlab::project::{
+    deployment::{Deployment, DeploymentStatus},
+    merge_request::MergeRequest,
+    pipeline::{Pipeline, PipelineStatus},
+};
+
+/// What a project did over the longest metrics window, metrics of shorter ones are computed
+/// from it
+#[derive(Debug, Clone)]
+pub struct ProjectActivity {
+    pub host: String,
+    pub name: String,
+    pub deployments: Vec<Deployment>,
+    /// Merged ones
+    pub merge_requests: Vec<MergeRequest>,
+    /// Default branch ones
+    pub pipelines: Vec<Pipeline>,
+}
+
+/// DORA metrics over a period, durations are medians in seconds
+#[derive(Debug, Clone, Default)]
+pub struct DoraMetrics {
+    pub days: u32,
+    /// Successful production deployments
+    pub deployments: usize,
+    pub failed_deployments: usize,
+    /// Merge request creation to merge
+    pub time_to_merge: Option<i64>,
+    /// Merge to first following production deployment
+    pub time_to_deploy: Option<i64>,
+    /// Merge request creation to production deployment
+    pub lead_time: Option<i64>,
+    /// First failed default branch pipeline to next successful one
+    pub time_to_restore: Option<i64>,
+}
+
+impl DoraMetrics {
+    /// Metrics of all given projects together over last `days`
+    pub fn of_projects(days: u32, projects: &[ProjectActivity]) -> Self {
+        let since = Utc::now() - TimeDelta::days(days as i64);
+
+        let mut deployments = 0;
+        let mut failed_deployments = 0;
+        let mut times_to_merge = vec![];
+        let mut times_to_deploy = vec![];
+        let mut lead_times = vec![];
+        let mut times_to_restore = vec![];
+
+        for project in projects {
+            let mut deployed = project
+                .deployments
+                .iter()
+                .filter(|deployment| is_production(&deployment.environment.name))
+                .filter_map(|deployment| Some((datetime(&deployment.created_at)?, deployment)))
+                .filter(|(created_at, _)| *created_at >= since)
+                .collect::<Vec<(DateTime<Utc>, &Deployment)>>();
+            deployed.sort_by_key(|(created_at, _)| *created_at);
+            deployments += deployed
+                .iter()
+                .filter(|(_, deployment)| deployment.status == DeploymentStatus::Success)
+                .count();
+            failed_deployments += deployed
+                .iter()
+                .filter(|(_, deployment)| deployment.status == DeploymentStatus::Failed)
+                .count();
+
+            for merge_request in &project.merge_requests {
+                let (Some(created_at), Some(merged_at)) = (
+                    datetime(&merge_request.created_at),
+                    merge_request.merged_at.as_deref().and_then(datetime),
+                ) else {
+                    continue;
+                };
+                if merged_at < since {
+                    continue;
+                }
+                times_to_merge.push((merged_at - created_at).num_seconds());
+
+                // A deployment ships everything merged before it
+                if let Some((deployed_at, _)) = deployed.iter().find(|(deployed_at, deployment)| {
+                    *deployed_at >= merged_at && deployment.status == DeploymentStatus::Success
+                }) {
+                    times_to_deploy.push((*deployed_at - merged_at).num_seconds());
+                    lead_times.push((*deployed_at - created_at).num_seconds());
+                }
+            }
+
+            let mut pipelines = project
+                .pipelines
+                .iter()
+                .filter_map(|pipeline| Some((datetime(&pipeline.created_at)?, pipeline)))
+                .filter(|(created_at, _)| *created_at >= since)
+                .collect::<Vec<(DateTime<Utc>, &Pipeline)>>();
+            pipelines.sort_by_key(|(created_at, _)| *created_at);
+            let mut failing_since = None;
+            for (created_at, pipeline) in pipelines {
+                match (pipeline.status, failing_since) {
+                    (PipelineStatus::Failed, None) => failing_since = Some(created_at),
+                    (PipelineStatus::Success, Some(failed_at)) => {
+                        let restored_at = pipeline
+                            .finished_at
+                            .as_deref()
+                            .and_then(datetime)
+                            .unwrap_or(created_at);
+                        times_to_restore.push((restored_at - failed_at).num_seconds());
+                        failing_since = None;
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        Self {
+            days,
+            deployments,
+            failed_deployments,
+            time_to_merge: median(times_to_merge),
+            time_to_deploy: median(times_to_deploy),
+            lead_time: median(lead_times),
+            time_to_restore: median(times_to_restore),
+        }
+    }
+
+    /// Successful production deployments per day
+    pub fn deployment_frequency(&self) -> f64 {
+        self.deployments as f64 / self.days.max(1) as f64
+    }
+
+    /// Between 0 and 1, `None` without any production deployment
+    pub fn change_failure_rate(&self) -> Option<f64> {
+        let total = self.deployments + self.failed_deployments;
+        (total > 0).then(|| self.failed_deployments as f64 / total as f64)
+    }
+}
+
+/// Environments named like `production`, `prod` or `production/eu`
+pub fn is_production(environment: &str) -> bool {
+    environment.to_lowercase().starts_with("prod")
+}
+
+fn datetime(raw: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(raw)
+        .ok()
+        .map(|datetime| datetime.with_timezone(&Utc))
+}
+
+fn median(mut values: Vec<i64>) -> Option<i64> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort();
+
+    Some(values[values.len() / 2])
+}
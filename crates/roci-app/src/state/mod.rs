@@ -1,22 +1,58 @@
+use std::{collections::HashSet, sync::Arc, time::Duration};
+
 use gpui::*;
+use gpui_component::ThemeConfig;
 
 use crate::{
     config::{Config, ConfigError},
-    state::gitlab::Gitlabs,
+    state::{
+        gitlab::{project::pipeline::Pipeline, Gitlabs},
+        history::History,
+        navigation::NavKey,
+        refresh::{RefreshKey, Refreshes},
+        window::WindowStates,
+    },
 };
 
 pub mod gitlab;
+pub mod history;
+pub mod metrics;
+pub mod navigation;
+pub mod refresh;
+pub mod report;
+pub mod window;
 
 pub struct AppState {
     config: Config,
     gitlabs: Gitlabs,
+    themes: Vec<ThemeConfig>,
+    selected: Option<NavKey>,
+    refreshes: Refreshes,
+    history: Option<Arc<History>>,
+    /// Merge requests (host and id) already notified as waiting for review
+    notified_merge_requests: HashSet<(String, u64)>,
+    windows: WindowStates,
 }
 
 impl AppState {
-    pub fn init(cx: &mut App, config: Config) {
+    pub fn init(cx: &mut App, config: Config, themes: Vec<ThemeConfig>) {
+        let history = if config.history.enabled {
+            History::open(&config.history)
+                .inspect_err(|error| tracing::warn!("Pipelines history unavailable: {}", error))
+                .ok()
+                .map(Arc::new)
+        } else {
+            None
+        };
         let state = Self {
             config,
             gitlabs: Gitlabs::default(),
+            themes,
+            selected: None,
+            refreshes: Refreshes::default(),
+            history,
+            notified_merge_requests: HashSet::new(),
+            windows: WindowStates::load(),
         };
 
         cx.set_global::<AppState>(state);
@@ -38,6 +74,70 @@ impl AppState {
         self.gitlabs.clone()
     }
 
+    pub fn themes(&self) -> &[ThemeConfig] {
+        &self.themes
+    }
+
+    pub fn selected(&self) -> Option<&NavKey> {
+        self.selected.as_ref()
+    }
+
+    pub fn is_selected(&self, key: &NavKey) -> bool {
+        self.selected.as_ref() == Some(key)
+    }
+
+    pub fn select(&mut self, key: Option<NavKey>) {
+        self.selected = key;
+    }
+
+    /// `None` if disabled or if the database can't be opened
+    pub fn history(&self) -> Option<Arc<History>> {
+        self.history.clone()
+    }
+
+    /// Store pipelines in history without blocking the UI
+    pub fn record_pipelines(cx: &App, host: String, pipelines: Vec<Pipeline>) {
+        let Some(history) = Self::global(cx).history() else {
+            return;
+        };
+        if pipelines.is_empty() {
+            return;
+        }
+
+        cx.background_spawn(async move {
+            if let Err(error) = history.record(&host, &pipelines) {
+                tracing::warn!("Unable to record pipelines history: {}", error);
+            }
+        })
+        .detach();
+    }
+
+    /// Whether merge request was not notified yet, it is not anymore afterwards
+    pub fn first_notification(&mut self, host: &str, merge_request_id: u64) -> bool {
+        self.notified_merge_requests
+            .insert((host.to_string(), merge_request_id))
+    }
+
+    pub fn windows(&self) -> &WindowStates {
+        &self.windows
+    }
+
+    pub fn windows_mut(&mut self) -> &mut WindowStates {
+        &mut self.windows
+    }
+
+    pub fn refreshes_mut(&mut self) -> &mut Refreshes {
+        &mut self.refreshes
+    }
+
+    pub fn refresh_interval(&self, key: &RefreshKey) -> Duration {
+        self.refreshes.interval(&self.config, key)
+    }
+
+    pub fn refresh_paused(&self) -> bool {
+        self.refreshes.paused(&self.config)
+    }
+
     pub fn replace_config(&mut self, new: Config) -> std::result::Result<(), ConfigError> {
         new.persist()?;
         self.config = new;
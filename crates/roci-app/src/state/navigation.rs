@@ -0,0 +1,30 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NavKind {
+    Todo,
+    Project,
+    MergeRequest,
+    Issue,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NavKey {
+    pub kind: NavKind,
+    pub url: String,
+}
+
+impl NavKey {
+    pub fn new(kind: NavKind, url: impl Into<String>) -> Self {
+        Self {
+            kind,
+            url: url.into(),
+        }
+    }
+}
+
+/// Keyboard navigable dashboard item
+#[derive(Debug, Clone)]
+pub struct NavItem {
+    pub key: NavKey,
+    pub host: String,
+    pub id: u64,
+}
@@ -0,0 +1,116 @@
+use std::{
+    collections::{HashMap, HashSet},
+    time::Duration,
+};
+
+use crate::config::Config;
+
+/// Something refreshed on its own
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum RefreshKey {
+    Project(String, u64),
+    ProjectsTable(String),
+    /// Every listed project of an instance, fetched at once
+    Projects(String),
+    Todos(String),
+    Issues(String),
+    Activity(String),
+    StaleMergeRequests(String),
+}
+
+impl RefreshKey {
+    pub fn host(&self) -> &str {
+        match self {
+            RefreshKey::Project(host, _)
+            | RefreshKey::ProjectsTable(host)
+            | RefreshKey::Projects(host)
+            | RefreshKey::Todos(host)
+            | RefreshKey::Issues(host)
+            | RefreshKey::Activity(host)
+            | RefreshKey::StaleMergeRequests(host) => host,
+        }
+    }
+}
+
+/// Adaptive refresh state: what changed since last load, what is running
+#[derive(Debug, Default)]
+pub struct Refreshes {
+    fingerprints: HashMap<RefreshKey, String>,
+    /// Count of consecutive loads without change (or with error)
+    idle: HashMap<RefreshKey, u32>,
+    running: HashSet<RefreshKey>,
+    /// Asked to refresh before their interval (eg. by a webhook event)
+    triggered: HashSet<RefreshKey>,
+    hidden: bool,
+}
+
+impl Refreshes {
+    /// Record a successful load, `fingerprint` must change when loaded data changes
+    pub fn loaded(&mut self, key: &RefreshKey, fingerprint: String, running: bool) {
+        let unchanged = self.fingerprints.get(key) == Some(&fingerprint);
+        self.fingerprints.insert(key.clone(), fingerprint);
+
+        if running {
+            self.running.insert(key.clone());
+        } else {
+            self.running.remove(key);
+        }
+
+        if unchanged && !running {
+            *self.idle.entry(key.clone()).or_default() += 1;
+        } else {
+            self.idle.remove(key);
+        }
+    }
+
+    pub fn failed(&mut self, key: &RefreshKey) {
+        self.running.remove(key);
+        *self.idle.entry(key.clone()).or_default() += 1;
+    }
+
+    pub fn trigger(&mut self, key: RefreshKey) {
+        self.triggered.insert(key);
+    }
+
+    /// Whether `key` was triggered since last call
+    pub fn take_triggered(&mut self, key: &RefreshKey) -> bool {
+        self.triggered.remove(key)
+    }
+
+    pub fn set_hidden(&mut self, hidden: bool) {
+        self.hidden = hidden;
+    }
+
+    pub fn paused(&self, config: &Config) -> bool {
+        self.hidden && config.adaptive_refresh.pause_when_hidden
+    }
+
+    /// Project interval, else instance one, else global one; shortened while running and
+    /// doubled (up to configured factor) while idle or failing
+    pub fn interval(&self, config: &Config, key: &RefreshKey) -> Duration {
+        let gitlab = config
+            .gitlabs
+            .iter()
+            .find(|gitlab| gitlab.host == key.host());
+        let project_every = match (key, gitlab) {
+            (RefreshKey::Project(_, project_id), Some(gitlab)) => {
+                gitlab.project_refresh_every.get(project_id)
+            }
+            _ => None,
+        };
+        let base = project_every
+            .or(gitlab.and_then(|gitlab| gitlab.refresh_every.as_ref()))
+            .unwrap_or(&config.refresh_every)
+            .duration();
+
+        if self.running.contains(key) {
+            return base.min(config.adaptive_refresh.while_running.duration());
+        }
+
+        let idle = self.idle.get(key).copied().unwrap_or(0).min(31);
+        let factor = 2u32
+            .saturating_pow(idle)
+            .min(config.adaptive_refresh.max_backoff.max(1));
+        base * factor
+    }
+}
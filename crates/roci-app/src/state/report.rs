@@ -0,0 +1,254 @@
+use std::{fs, io};
+
+use thiserror::Error;
+
+use crate::config::{Config, ConfigError};
+
+const MARKDOWN_TEMPLATE: &str = "# Standup {{date}}
+
+## Failing pipelines ({{failing_pipelines_count}})
+
+{{failing_pipelines}}
+
+## My open merge requests ({{merge_requests_count}})
+
+{{merge_requests}}
+
+## Assigned issues ({{issues_count}})
+
+{{issues}}
+
+## Todos
+
+{{todos_count}} pending todos, {{review_requests_count}} review requests
+";
+
+const HTML_TEMPLATE: &str = "<!DOCTYPE html>
+<html>
+<head>
+<meta charset=\"utf-8\">
+<title>Standup {{date}}</title>
+<style>
+body { font-family: sans-serif; max-width: 50em; margin: 2em auto; color: #222; }
+h2 { border-bottom: 1px solid #ddd; }
+a { color: #1f6feb; text-decoration: none; }
+.detail { color: #777; }
+</style>
+</head>
+<body>
+<h1>Standup {{date}}</h1>
+<h2>Failing pipelines ({{failing_pipelines_count}})</h2>
+{{failing_pipelines}}
+<h2>My open merge requests ({{merge_requests_count}})</h2>
+{{merge_requests}}
+<h2>Assigned issues ({{issues_count}})</h2>
+{{issues}}
+<h2>Todos</h2>
+<p>{{todos_count}} pending todos, {{review_requests_count}} review requests</p>
+</body>
+</html>
+";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    Markdown,
+    Html,
+}
+
+impl ReportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ReportFormat::Markdown => "md",
+            ReportFormat::Html => "html",
+        }
+    }
+
+    pub fn default_template(&self) -> &'static str {
+        match self {
+            ReportFormat::Markdown => MARKDOWN_TEMPLATE,
+            ReportFormat::Html => HTML_TEMPLATE,
+        }
+    }
+
+    /// `report.md` or `report.html` of templates dir, default one if missing
+    pub fn template(&self) -> Result<String, ReportError> {
+        let path = Config::templates_dir()?.join(format!("report.{}", self.extension()));
+
+        match fs::read_to_string(path) {
+            Ok(template) => Ok(template),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => {
+                Ok(self.default_template().to_string())
+            }
+            Err(error) => Err(error.into()),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ReportItem {
+    pub title: String,
+    pub url: String,
+    /// Status, project or instance, displayed after the link
+    pub detail: Option<String>,
+}
+
+impl ReportItem {
+    pub fn new(title: impl Into<String>, url: impl Into<String>, detail: Option<String>) -> Self {
+        Self {
+            title: title.into(),
+            url: url.into(),
+            detail,
+        }
+    }
+}
+
+/// Dashboard state at a given time, rendered through a template
+#[derive(Debug, Clone, Default)]
+pub struct Report {
+    pub date: String,
+    pub failing_pipelines: Vec<ReportItem>,
+    pub merge_requests: Vec<ReportItem>,
+    pub issues: Vec<ReportItem>,
+    pub todos: usize,
+    pub review_requests: usize,
+}
+
+impl Report {
+    /// Replace `{{placeholder}}` of `template`, lists are rendered in given format
+    pub fn render(&self, format: ReportFormat, template: &str) -> String {
+        let placeholders = [
+            ("date", escape(format, &self.date)),
+            ("failing_pipelines", list(format, &self.failing_pipelines)),
+            (
+                "failing_pipelines_count",
+                self.failing_pipelines.len().to_string(),
+            ),
+            ("merge_requests", list(format, &self.merge_requests)),
+            (
+                "merge_requests_count",
+                self.merge_requests.len().to_string(),
+            ),
+            ("issues", list(format, &self.issues)),
+            ("issues_count", self.issues.len().to_string()),
+            ("todos_count", self.todos.to_string()),
+            ("review_requests_count", self.review_requests.to_string()),
+        ];
+
+        // Single scan: values (titles, etc.) containing placeholders are never expanded
+        let mut rendered = String::with_capacity(template.len());
+        let mut rest = template;
+        while let Some(start) = rest.find("{{") {
+            rendered.push_str(&rest[..start]);
+            let after = &rest[start + 2..];
+            let value = after.find("}}").and_then(|end| {
+                placeholders
+                    .iter()
+                    .find(|(name, _)| *name == &after[..end])
+                    .map(|(_, value)| (value, end))
+            });
+            match value {
+                Some((value, end)) => {
+                    rendered.push_str(value);
+                    rest = &after[end + 2..];
+                }
+                None => {
+                    rendered.push_str("{{");
+                    rest = after;
+                }
+            }
+        }
+        rendered.push_str(rest);
+
+        rendered
+    }
+}
+
+fn list(format: ReportFormat, items: &[ReportItem]) -> String {
+    match format {
+        ReportFormat::Markdown if items.is_empty() => "None".to_string(),
+        ReportFormat::Markdown => items
+            .iter()
+            .map(|item| {
+                let detail = item
+                    .detail
+                    .as_ref()
+                    .map(|detail| format!(" ({})", detail))
+                    .unwrap_or_default();
+                format!(
+                    "- [{}]({}){}",
+                    escape(format, &item.title),
+                    item.url,
+                    detail
+                )
+            })
+            .collect::<Vec<String>>()
+            .join("\n"),
+        ReportFormat::Html if items.is_empty() => "<p>None</p>".to_string(),
+        ReportFormat::Html => {
+            let items = items
+                .iter()
+                .map(|item| {
+                    let detail = item
+                        .detail
+                        .as_ref()
+                        .map(|detail| {
+                            format!(" <span class=\"detail\">{}</span>", escape(format, detail))
+                        })
+                        .unwrap_or_default();
+                    format!(
+                        "<li><a href=\"{}\">{}</a>{}</li>",
+                        escape(format, &item.url),
+                        escape(format, &item.title),
+                        detail
+                    )
+                })
+                .collect::<Vec<String>>()
+                .join("\n");
+            format!("<ul>\n{}\n</ul>", items)
+        }
+    }
+}
+
+fn escape(format: ReportFormat, raw: &str) -> String {
+    match format {
+        ReportFormat::Markdown => raw.replace('[', "\\[").replace(']', "\\]"),
+        ReportFormat::Html => raw
+            .replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace('"', "&quot;"),
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum ReportError {
+    #[error("Config error: {0}")]
+    Config(#[from] ConfigError),
+    #[error("Can't read template: {0}")]
+    Io(#[from] io::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn placeholders_in_values_are_not_expanded() {
+        let report = Report {
+            date: "2024-05-02".to_string(),
+            merge_requests: vec![ReportItem::new(
+                "Fix {{date}} parsing",
+                "https://gitlab.com/roci/-/merge_requests/1",
+                None,
+            )],
+            ..Default::default()
+        };
+
+        let rendered = report.render(ReportFormat::Markdown, "{{date}}\n{{merge_requests}}");
+
+        assert_eq!(
+            rendered,
+            "2024-05-02\n- [Fix {{date}} parsing](https://gitlab.com/roci/-/merge_requests/1)"
+        );
+    }
+}
@@ -0,0 +1,81 @@
+use std::{collections::HashMap, fs, io};
+
+use gpui::{Bounds, Pixels};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::config::{Config, ConfigError};
+
+pub const WINDOWS_FILE: &str = "windows.ron";
+
+/// Last position and size of a window, kept across runs
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct WindowState {
+    /// Restore bounds if window is maximized
+    pub bounds: Option<Bounds<Pixels>>,
+    #[serde(default)]
+    pub maximized: bool,
+    #[serde(default)]
+    pub always_on_top: bool,
+}
+
+/// Window states by key (main window, popped out project, todos or pipeline log)
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct WindowStates(HashMap<String, WindowState>);
+
+impl WindowStates {
+    /// Empty if missing or unreadable, windows are then opened at their default place
+    pub fn load() -> Self {
+        let result = Config::data_dir()
+            .map_err(WindowStatesError::from)
+            .and_then(|dir| Ok(fs::read_to_string(dir.join(WINDOWS_FILE))?))
+            .and_then(|raw| {
+                ron::from_str(&raw).map_err(|error| WindowStatesError::Invalid(error.to_string()))
+            });
+
+        match result {
+            Ok(states) => states,
+            Err(WindowStatesError::Io(error)) if error.kind() == io::ErrorKind::NotFound => {
+                Self::default()
+            }
+            Err(error) => {
+                tracing::warn!("Unable to read window states: {}", error);
+                Self::default()
+            }
+        }
+    }
+
+    pub fn persist(&self) -> Result<(), WindowStatesError> {
+        let dir = Config::data_dir()?;
+        fs::create_dir_all(&dir)?;
+        let raw =
+            ron::to_string(self).map_err(|error| WindowStatesError::Invalid(error.to_string()))?;
+        fs::write(dir.join(WINDOWS_FILE), raw)?;
+
+        Ok(())
+    }
+
+    pub fn get(&self, key: &str) -> WindowState {
+        self.0.get(key).cloned().unwrap_or_default()
+    }
+
+    pub fn set_bounds(&mut self, key: &str, bounds: Bounds<Pixels>, maximized: bool) {
+        let state = self.0.entry(key.to_string()).or_default();
+        state.bounds = Some(bounds);
+        state.maximized = maximized;
+    }
+
+    pub fn set_always_on_top(&mut self, key: &str, always_on_top: bool) {
+        self.0.entry(key.to_string()).or_default().always_on_top = always_on_top;
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum WindowStatesError {
+    #[error("Io error: {0}")]
+    Io(#[from] io::Error),
+    #[error("Config error: {0}")]
+    Config(#[from] ConfigError),
+    #[error("Invalid window states: {0}")]
+    Invalid(String),
+}
@@ -0,0 +1,137 @@
+use futures::channel::mpsc::UnboundedSender;
+use ksni::{
+    blocking::{Handle, TrayMethods},
+    menu::StandardItem,
+    MenuItem, Status, ToolTip,
+};
+
+use crate::tray::{TrayCommand, TrayError, TrayItem, TraySummary, TRAY_TOP_ITEMS};
+
+pub struct RociTray {
+    pub summary: TraySummary,
+    sender: UnboundedSender<TrayCommand>,
+}
+
+pub fn spawn(sender: UnboundedSender<TrayCommand>) -> Result<Handle<RociTray>, TrayError> {
+    let tray = RociTray {
+        summary: TraySummary::default(),
+        sender,
+    };
+
+    Ok(tray.spawn()?)
+}
+
+impl RociTray {
+    fn send(&self, command: TrayCommand) {
+        // App is closing if receiver is gone
+        let _ = self.sender.unbounded_send(command);
+    }
+
+    fn items(title: &str, items: &[TrayItem]) -> Vec<MenuItem<Self>> {
+        if items.is_empty() {
+            return vec![];
+        }
+
+        let mut menu = vec![
+            MenuItem::Separator,
+            StandardItem {
+                label: title.to_string(),
+                enabled: false,
+                ..Default::default()
+            }
+            .into(),
+        ];
+        menu.extend(items.iter().take(TRAY_TOP_ITEMS).map(|item| {
+            let url = item.url.clone();
+
+            StandardItem {
+                // Underscores are access keys markers
+                label: item.label.replace('_', "__"),
+                activate: Box::new(move |this: &mut Self| {
+                    this.send(TrayCommand::Open(url.clone()))
+                }),
+                ..Default::default()
+            }
+            .into()
+        }));
+
+        menu
+    }
+}
+
+impl ksni::Tray for RociTray {
+    fn id(&self) -> String {
+        env!("CARGO_PKG_NAME").into()
+    }
+
+    fn title(&self) -> String {
+        "Roci".into()
+    }
+
+    fn icon_name(&self) -> String {
+        if !self.summary.failing_pipelines.is_empty() {
+            "dialog-error".into()
+        } else if !self.summary.todos.is_empty() || !self.summary.review_requests.is_empty() {
+            "mail-unread".into()
+        } else {
+            "emblem-default".into()
+        }
+    }
+
+    fn status(&self) -> Status {
+        if !self.summary.failing_pipelines.is_empty() {
+            Status::NeedsAttention
+        } else {
+            Status::Active
+        }
+    }
+
+    fn tool_tip(&self) -> ToolTip {
+        ToolTip {
+            title: "Roci".into(),
+            description: self.summary.description(),
+            ..Default::default()
+        }
+    }
+
+    fn activate(&mut self, _x: i32, _y: i32) {
+        self.send(TrayCommand::OpenWindow);
+    }
+
+    fn menu(&self) -> Vec<MenuItem<Self>> {
+        let mut menu: Vec<MenuItem<Self>> = vec![
+            StandardItem {
+                label: "Open Roci".into(),
+                activate: Box::new(|this: &mut Self| this.send(TrayCommand::OpenWindow)),
+                ..Default::default()
+            }
+            .into(),
+            StandardItem {
+                label: "Refresh".into(),
+                activate: Box::new(|this: &mut Self| this.send(TrayCommand::Refresh)),
+                ..Default::default()
+            }
+            .into(),
+        ];
+        menu.extend(Self::items(
+            "Failing pipelines",
+            &self.summary.failing_pipelines,
+        ));
+        menu.extend(Self::items(
+            "Review requests",
+            &self.summary.review_requests,
+        ));
+        menu.extend(Self::items("Todos", &self.summary.todos));
+        menu.push(MenuItem::Separator);
+        menu.push(
+            StandardItem {
+                label: "Quit".into(),
+                activate: Box::new(|this: &mut Self| this.send(TrayCommand::Quit)),
+                ..Default::default()
+            }
+            .into(),
+        );
+
+        menu
+    }
+}
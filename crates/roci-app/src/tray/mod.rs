@@ -0,0 +1,175 @@
+use std::time::Duration;
+
+use derive_more::Constructor;
+use futures::{channel::mpsc::UnboundedReceiver, StreamExt};
+use gpui::*;
+use thiserror::Error;
+use tracing_unwrap::ResultExt;
+
+use crate::{actions::Refresh, dashboard::Dashboard, state::AppState};
+
+#[cfg(target_os = "linux")]
+mod linux;
+
+/// Tray reads dashboard state, it doesn't query Gitlab itself
+pub const TRAY_UPDATE_EVERY: Duration = Duration::from_secs(5);
+/// Count of items of each kind listed in tray menu
+pub const TRAY_TOP_ITEMS: usize = 5;
+
+#[derive(Debug, Clone, PartialEq, Constructor)]
+pub struct TrayItem {
+    pub label: String,
+    pub url: String,
+}
+
+/// Aggregated state displayed by the tray icon
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TraySummary {
+    /// Projects with a failed last default branch pipeline
+    pub failing_pipelines: Vec<TrayItem>,
+    pub todos: Vec<TrayItem>,
+    pub review_requests: Vec<TrayItem>,
+}
+
+impl TraySummary {
+    pub fn description(&self) -> String {
+        format!(
+            "{} failing pipelines, {} todos, {} review requests",
+            self.failing_pipelines.len(),
+            self.todos.len(),
+            self.review_requests.len()
+        )
+    }
+}
+
+/// Commands sent from tray menu to the app
+#[derive(Debug, Clone)]
+pub enum TrayCommand {
+    Refresh,
+    OpenWindow,
+    Open(String),
+    Quit,
+}
+
+#[derive(Debug, Error)]
+pub enum TrayError {
+    #[cfg(target_os = "linux")]
+    #[error("Tray service error: {0}")]
+    Service(#[from] ksni::Error),
+    #[cfg(not(target_os = "linux"))]
+    #[error("Tray icon is only supported on Linux")]
+    Unsupported,
+}
+
+/// Running tray icon, if any
+#[derive(Default)]
+pub struct Tray {
+    #[cfg(target_os = "linux")]
+    handle: Option<ksni::blocking::Handle<linux::RociTray>>,
+    summary: TraySummary,
+}
+
+impl Global for Tray {}
+
+impl Tray {
+    pub fn spawn(
+        sender: futures::channel::mpsc::UnboundedSender<TrayCommand>,
+    ) -> Result<Self, TrayError> {
+        #[cfg(target_os = "linux")]
+        {
+            Ok(Self {
+                handle: Some(linux::spawn(sender)?),
+                summary: TraySummary::default(),
+            })
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            let _ = sender;
+            Err(TrayError::Unsupported)
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        #[cfg(target_os = "linux")]
+        {
+            self.handle
+                .as_ref()
+                .is_some_and(|handle| !handle.is_closed())
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            false
+        }
+    }
+
+    fn update(&mut self, summary: TraySummary) {
+        if self.summary == summary {
+            return;
+        }
+        self.summary = summary;
+
+        #[cfg(target_os = "linux")]
+        if let Some(handle) = &self.handle {
+            let summary = self.summary.clone();
+            handle.update(|tray| tray.summary = summary);
+        }
+    }
+}
+
+/// Window must be minimized instead of closed (to stay reachable from tray)
+pub fn keep_running(cx: &App) -> bool {
+    AppState::global(cx).config().tray.keep_running
+        && cx
+            .try_global::<Tray>()
+            .is_some_and(|tray| tray.is_running())
+}
+
+/// Minimize window, refreshes may be paused while it stays hidden
+pub fn hide_window(window: &mut Window, _cx: &mut App) {
+    window.minimize_window();
+}
+
+/// Feed tray with dashboard state and execute tray menu commands
+pub fn run(
+    window: &mut Window,
+    cx: &mut App,
+    dashboard: Entity<Dashboard>,
+    mut receiver: UnboundedReceiver<TrayCommand>,
+) {
+    window
+        .spawn(cx, {
+            let dashboard = dashboard.downgrade();
+
+            async move |cx| loop {
+                let Ok(summary) =
+                    dashboard.read_with(cx, |dashboard, cx| dashboard.tray_summary(cx))
+                else {
+                    break;
+                };
+                let _ = cx.update(|_window, cx| {
+                    if cx.has_global::<Tray>() {
+                        cx.global_mut::<Tray>().update(summary);
+                    }
+                });
+
+                Timer::after(TRAY_UPDATE_EVERY).await;
+            }
+        })
+        .detach();
+
+    window
+        .spawn(cx, async move |cx| {
+            while let Some(command) = receiver.next().await {
+                let updated = cx.update(|window, cx| match command {
+                    TrayCommand::Refresh => window.dispatch_action(Box::new(Refresh), cx),
+                    TrayCommand::OpenWindow => window.activate_window(),
+                    TrayCommand::Open(url) => open::that(url).unwrap_or_log(),
+                    TrayCommand::Quit => cx.quit(),
+                });
+                if updated.is_err() {
+                    break;
+                }
+            }
+        })
+        .detach();
+}
@@ -15,3 +15,66 @@ impl Truncate for String {
         format!("{truncated}{ELLIPSIS}")
     }
 }
+
+/// Display gitlab ISO 8601 dates (`2024-05-12T09:41:03.120Z`) as `2024-05-12 09:41`
+pub fn short_datetime(raw: &str) -> String {
+    raw.replace('T', " ").chars().take(16).collect()
+}
+
+/// Display elapsed time since gitlab ISO 8601 date (`2024-05-12T09:41:03.120Z`) as `3d`, `5h` or `12m`
+pub fn age(raw: &str) -> String {
+    let Ok(date) = chrono::DateTime::parse_from_rfc3339(raw) else {
+        return "?".to_string();
+    };
+    let elapsed = chrono::Utc::now().signed_duration_since(date);
+
+    if elapsed.num_days() > 0 {
+        format!("{}d", elapsed.num_days())
+    } else if elapsed.num_hours() > 0 {
+        format!("{}h", elapsed.num_hours())
+    } else {
+        format!("{}m", elapsed.num_minutes().max(0))
+    }
+}
+
+/// Whole days elapsed since gitlab ISO 8601 date (`2024-05-12T09:41:03.120Z`)
+pub fn days_since(raw: &str) -> Option<i64> {
+    let date = chrono::DateTime::parse_from_rfc3339(raw).ok()?;
+
+    Some(chrono::Utc::now().signed_duration_since(date).num_days())
+}
+
+/// Display a duration in seconds as `1h 05m`, `3m 12s` or `45s`
+pub fn duration(seconds: u64) -> String {
+    if seconds >= 3600 {
+        format!("{}h {:02}m", seconds / 3600, seconds % 3600 / 60)
+    } else if seconds >= 60 {
+        format!("{}m {:02}s", seconds / 60, seconds % 60)
+    } else {
+        format!("{}s", seconds)
+    }
+}
+
+/// `group/project` of a `https://host/group/project/-/merge_requests/12` web url
+pub fn project_path(web_url: &str) -> &str {
+    let path = web_url
+        .split_once("://")
+        .map(|(_, rest)| rest)
+        .unwrap_or(web_url);
+    let path = path.split_once('/').map(|(_, path)| path).unwrap_or(path);
+
+    path.split_once("/-/").map(|(path, _)| path).unwrap_or(path)
+}
+
+/// `WaitingForResource` as `waiting_for_resource`, like Gitlab statuses
+pub fn snake_case(value: &str) -> String {
+    let mut snake = String::new();
+    for (index, char) in value.chars().enumerate() {
+        if char.is_uppercase() && index > 0 {
+            snake.push('_');
+        }
+        snake.extend(char.to_lowercase());
+    }
+
+    snake
+}
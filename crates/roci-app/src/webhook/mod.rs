@@ -0,0 +1,256 @@
+use std::{io::Read, sync::Arc, thread};
+
+use futures::{
+    channel::mpsc::{UnboundedReceiver, UnboundedSender},
+    StreamExt,
+};
+use gpui::*;
+use serde::Deserialize;
+use thiserror::Error;
+use tiny_http::{Method, Request, Response, Server};
+use url::Url;
+
+use crate::{
+    config::{get_password, webhook::WebhookConfig, webhook::WEBHOOK_SECRET_KEY},
+    dashboard::Dashboard,
+    state::gitlab::project::pipeline::{Pipeline, PipelineStatus},
+};
+
+/// Larger payloads (eg. huge push events) are rejected
+pub const MAX_PAYLOAD_SIZE: usize = 5 * 1024 * 1024;
+
+/// Gitlab event applied to the dashboard
+#[derive(Debug, Clone)]
+pub struct WebhookEvent {
+    /// Instance host, as in configuration (with port if any)
+    pub host: String,
+    pub project_id: u64,
+    pub kind: WebhookKind,
+}
+
+#[derive(Debug, Clone)]
+pub enum WebhookKind {
+    Pipeline {
+        ref_: String,
+        pipeline: Box<Pipeline>,
+    },
+    MergeRequest,
+    Issue,
+    Note,
+}
+
+#[derive(Debug, Error)]
+pub enum WebhookError {
+    #[error("Unable to listen on {0}: {1}")]
+    Bind(String, String),
+    #[error("Webhook secret token not found in keyring: {0}")]
+    Secret(#[from] keyring::Error),
+}
+
+/// Running webhook listener, stopped when dropped
+pub struct Webhook {
+    server: Arc<Server>,
+}
+
+impl Global for Webhook {}
+
+impl Webhook {
+    pub fn spawn(
+        config: &WebhookConfig,
+        sender: UnboundedSender<WebhookEvent>,
+    ) -> Result<Self, WebhookError> {
+        let secret = get_password(WEBHOOK_SECRET_KEY)?;
+        let server = Server::http(&config.address)
+            .map_err(|error| WebhookError::Bind(config.address.clone(), error.to_string()))?;
+        let server = Arc::new(server);
+        tracing::info!("Listening for Gitlab webhooks on {}", config.address);
+
+        thread::spawn({
+            let server = server.clone();
+
+            move || {
+                for request in server.incoming_requests() {
+                    let (status, event) = handle(request, &secret);
+                    if let Some(event) = event {
+                        if sender.unbounded_send(event).is_err() {
+                            break;
+                        }
+                    }
+                    tracing::debug!("Webhook answered {}", status);
+                }
+            }
+        });
+
+        Ok(Self { server })
+    }
+}
+
+impl Drop for Webhook {
+    fn drop(&mut self) {
+        self.server.unblock();
+    }
+}
+
+/// Apply received events to the dashboard
+pub fn run(
+    window: &mut Window,
+    cx: &mut App,
+    dashboard: Entity<Dashboard>,
+    mut receiver: UnboundedReceiver<WebhookEvent>,
+) {
+    window
+        .spawn(cx, async move |cx| {
+            while let Some(event) = receiver.next().await {
+                let updated = cx.update(|_window, cx| {
+                    dashboard.update(cx, |dashboard, cx| dashboard.apply_webhook(event, cx))
+                });
+                if updated.is_err() {
+                    break;
+                }
+            }
+        })
+        .detach();
+}
+
+/// Answer a request, returning the event it carries if valid
+fn handle(mut request: Request, secret: &str) -> (u16, Option<WebhookEvent>) {
+    let (status, event) = if *request.method() != Method::Post {
+        (405, None)
+    } else if !request
+        .headers()
+        .iter()
+        .find(|header| header.field.equiv("X-Gitlab-Token"))
+        .is_some_and(|header| constant_time_eq(header.value.as_str(), secret))
+    {
+        (401, None)
+    } else if request
+        .body_length()
+        .is_some_and(|length| length > MAX_PAYLOAD_SIZE)
+    {
+        (413, None)
+    } else {
+        let mut body = vec![];
+        match request
+            .as_reader()
+            .take(MAX_PAYLOAD_SIZE as u64)
+            .read_to_end(&mut body)
+        {
+            Ok(_) => match parse(&body) {
+                Ok(event) => (200, event),
+                Err(error) => {
+                    tracing::warn!("Invalid webhook payload: {}", error);
+                    (400, None)
+                }
+            },
+            Err(error) => {
+                tracing::warn!("Unable to read webhook payload: {}", error);
+                (400, None)
+            }
+        }
+    };
+
+    if let Err(error) = request.respond(Response::empty(status)) {
+        tracing::warn!("Unable to answer webhook: {}", error);
+    }
+
+    (status, event)
+}
+
+#[derive(Debug, Deserialize)]
+struct Payload {
+    object_kind: String,
+    project: PayloadProject,
+    #[serde(default)]
+    object_attributes: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PayloadProject {
+    id: u64,
+    web_url: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct PipelineAttributes {
+    id: u64,
+    #[serde(default)]
+    iid: u64,
+    status: PipelineStatus,
+    #[serde(rename = "ref")]
+    ref_: String,
+    created_at: String,
+    #[serde(default)]
+    finished_at: Option<String>,
+    #[serde(default)]
+    duration: Option<u64>,
+    #[serde(default)]
+    queued_duration: Option<f64>,
+}
+
+/// `None` for events not displayed by Roci (push, tag, job...)
+fn parse(body: &[u8]) -> Result<Option<WebhookEvent>, serde_json::Error> {
+    let payload: Payload = serde_json::from_slice(body)?;
+    let Some(host) = host(&payload.project.web_url) else {
+        return Ok(None);
+    };
+
+    let kind = match payload.object_kind.as_str() {
+        "pipeline" => {
+            let attributes: PipelineAttributes =
+                serde_json::from_value(payload.object_attributes.unwrap_or_default())?;
+            WebhookKind::Pipeline {
+                ref_: attributes.ref_.clone(),
+                pipeline: Box::new(Pipeline {
+                    id: attributes.id,
+                    iid: attributes.iid,
+                    project_id: payload.project.id,
+                    status: attributes.status,
+                    web_url: format!("{}/-/pipelines/{}", payload.project.web_url, attributes.id),
+                    created_at: iso_datetime(attributes.created_at),
+                    ref_: attributes.ref_,
+                    // Webhooks have no start date
+                    started_at: None,
+                    finished_at: attributes.finished_at.map(iso_datetime),
+                    duration: attributes.duration,
+                    queued_duration: attributes.queued_duration,
+                }),
+            }
+        }
+        "merge_request" => WebhookKind::MergeRequest,
+        "issue" => WebhookKind::Issue,
+        "note" => WebhookKind::Note,
+        _ => return Ok(None),
+    };
+
+    Ok(Some(WebhookEvent {
+        host,
+        project_id: payload.project.id,
+        kind,
+    }))
+}
+
+/// Host of an url as written in configuration (`host` or `host:port`)
+fn host(url: &str) -> Option<String> {
+    let url = Url::parse(url).ok()?;
+    let host = url.host_str()?;
+
+    Some(match url.port() {
+        Some(port) => format!("{}:{}", host, port),
+        None => host.to_string(),
+    })
+}
+
+/// Webhooks send `2016-08-12 15:23:28 UTC` where REST API sends ISO 8601
+fn iso_datetime(raw: String) -> String {
+    chrono::NaiveDateTime::parse_from_str(&raw, "%Y-%m-%d %H:%M:%S UTC")
+        .map(|datetime| datetime.and_utc().to_rfc3339())
+        .unwrap_or(raw)
+}
+
+fn constant_time_eq(a: &str, b: &str) -> bool {
+    a.len() == b.len()
+        && a.bytes()
+            .zip(b.bytes())
+            .fold(0, |acc, (a, b)| acc | (a ^ b))
+            == 0
+}